@@ -15,4 +15,29 @@ echo 'Алексей' | ./сборка/хуяк интер ./примеры/03-
 ./сборка/хуяк интер ./примеры/09-правило110.хуя           > ./примеры/09-правило110.хуя.вывод
 ./сборка/хуяк интер ./примеры/10-игра-жизнь.хуя           > ./примеры/10-игра-жизнь.хуя.вывод
 ./сборка/хуяк интер ./тесты/тест.хуя                      > ./тесты/тест.хуя.вывод
+./сборка/хуяк интер ./тесты/вариативные.хуя               > ./тесты/вариативные.хуя.вывод
+echo 1 | ./сборка/хуяк интер -без-проверок ./тесты/границы.хуя > ./тесты/границы.хуя.вывод
+echo 0 | ./сборка/хуяк интер ./тесты/переполнение.хуя     > ./тесты/переполнение.хуя.вывод
+./сборка/хуяк интер ./тесты/константы.хуя                 > ./тесты/константы.хуя.вывод
+./сборка/хуяк интер ./тесты/индекс-строк.хуя              > ./тесты/индекс-строк.хуя.вывод
+./сборка/хуяк интер ./тесты/символы.хуя                   > ./тесты/символы.хуя.вывод
+./сборка/хуяк интер ./тесты/строки.хуя                    > ./тесты/строки.хуя.вывод
+./сборка/хуяк интер ./тесты/составное-присваивание.хуя    > ./тесты/составное-присваивание.хуя.вывод
+./сборка/хуяк интер ./тесты/если-выражение.хуя            > ./тесты/если-выражение.хуя.вывод
+./сборка/хуяк интер ./тесты/сокращённые-операции.хуя      > ./тесты/сокращённые-операции.хуя.вывод
+./сборка/хуяк интер ./тесты/для.хуя                       > ./тесты/для.хуя.вывод
+./сборка/хуяк интер ./тесты/кортежи.хуя                   > ./тесты/кортежи.хуя.вывод
+./сборка/хуяк интер ./тесты/литералы.хуя                  > ./тесты/литералы.хуя.вывод
+./сборка/хуяк интер ./тесты/методы.хуя                    > ./тесты/методы.хуя.вывод
+./сборка/хуяк интер -путь ./тесты/модули ./тесты/модули.хуя > ./тесты/модули.хуя.вывод
+./сборка/хуяк интер -путь ./тесты/модули ./тесты/видимость.хуя > ./тесты/видимость.хуя.вывод
+./сборка/хуяк интер -путь ./тесты/модули ./тесты/типы.хуя      > ./тесты/типы.хуя.вывод
+./сборка/хуяк интер ./тесты/целые.хуя     > ./тесты/целые.хуя.вывод
+./сборка/хуяк интер ./тесты/вещ64.хуя     > ./тесты/вещ64.хуя.вывод
+./сборка/хуяк интер ./тесты/конвертации.хуя > ./тесты/конвертации.хуя.вывод
+./сборка/хуяк интер ./тесты/математика.хуя  > ./тесты/математика.хуя.вывод
+./сборка/хуяк интер ./тесты/возвраты.хуя    > ./тесты/возвраты.хуя.вывод
+mkdir -p ./сборка/тесты/
+cc -shared -fPIC -o ./сборка/тесты/libффи.so ./тесты/ффи.c
+LD_LIBRARY_PATH=./сборка/тесты ./сборка/хуяк интер ./тесты/ффи.хуя > ./тесты/ффи.хуя.вывод
 
@@ -1,4 +1,5 @@
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Лок {
@@ -24,6 +25,29 @@ macro_rules! диагностика {
         let уровень = $уровень;
         eprint!("{путь_к_файлу}:{строка}:{столбец}: {уровень}: ", путь_к_файлу = путь_к_файлу.display());
         eprintln!($($аргы)*);
+        if уровень == "ОШИБКА" {
+            ::диагностика::КОЛИЧЕСТВО_ОШИБОК.fetch_add(1, ::std::sync::atomic::Ordering::Relaxed);
+        }
+    }};
+}
+
+/// Сообщает о предупреждении, если оно не выключено. С флагом
+/// «-ошибки-из-предупреждений» предупреждение становится ошибкой.
+/// Возвращает истину, если о предупреждении сообщили, чтобы можно было
+/// дополнить его сообщениями уровня ИНФО.
+macro_rules! предупреждение {
+    ($лок:expr, $вид:expr, $($аргы:tt)*) => {{
+        let вид: ::диагностика::Предупреждение = $вид;
+        let включено = ::диагностика::предупреждение_включено(вид);
+        if включено {
+            if ::диагностика::ошибки_из_предупреждений() {
+                ::диагностика::КОЛИЧЕСТВО_ОШИБОК_ИЗ_ПРЕДУПРЕЖДЕНИЙ.fetch_add(1, ::std::sync::atomic::Ordering::Relaxed);
+                диагностика!($лок, "ОШИБКА", "{} [{}]", format_args!($($аргы)*), вид.имя());
+            } else {
+                диагностика!($лок, "ПРЕДУПРЕЖДЕНИЕ", "{} [{}]", format_args!($($аргы)*), вид.имя());
+            }
+        }
+        включено
     }};
 }
 
@@ -31,9 +55,106 @@ macro_rules! сделать {
     ($лок:expr, $($аргы:tt)*) => {{
         диагностика!($лок, "СДЕЛАТЬ", $($аргы)*);
         диагностика!(&здесь!(), "СДЕЛАТЬ", "реализация находится здесь");
+        ::диагностика::учесть_ошибку();
     }};
 }
 
+/// Компиляция не прерывается на первой ошибке, поэтому ошибки
+/// подсчитываются по мере того, как о них сообщают.
+pub static КОЛИЧЕСТВО_ОШИБОК: AtomicUsize = AtomicUsize::new(0);
+
+/// Количество ошибок, после которого компиляция прекращается. Ноль
+/// означает, что предела нет.
+pub static ПРЕДЕЛ_ОШИБОК: AtomicUsize = AtomicUsize::new(0);
+
+pub fn количество_ошибок() -> usize {
+    КОЛИЧЕСТВО_ОШИБОК.load(Ordering::Relaxed)
+}
+
+/// Учитывает ошибку, о которой сообщили не через «диагностика!» с уровнем
+/// ОШИБКА: например, ошибку времени исполнения при вычислении константы.
+pub fn учесть_ошибку() {
+    КОЛИЧЕСТВО_ОШИБОК.fetch_add(1, Ordering::Relaxed);
+}
+
+pub fn задать_предел_ошибок(предел: usize) {
+    ПРЕДЕЛ_ОШИБОК.store(предел, Ordering::Relaxed);
+}
+
+pub fn предел_ошибок_достигнут() -> bool {
+    let предел = ПРЕДЕЛ_ОШИБОК.load(Ordering::Relaxed);
+    предел > 0 && количество_ошибок() >= предел
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Предупреждение {
+    НеиспользуемаяПеременная,
+    НеиспользуемыйПараметр,
+    Затенение,
+    НедостижимыйКод,
+    НеиспользуемаяПроцедура,
+}
+
+pub const ВСЕ_ПРЕДУПРЕЖДЕНИЯ: &[Предупреждение] = &[
+    Предупреждение::НеиспользуемаяПеременная,
+    Предупреждение::НеиспользуемыйПараметр,
+    Предупреждение::Затенение,
+    Предупреждение::НедостижимыйКод,
+    Предупреждение::НеиспользуемаяПроцедура,
+];
+
+impl Предупреждение {
+    /// Имя, под которым предупреждение включается и выключается флагами.
+    pub fn имя(self) -> &'static str {
+        match self {
+            Предупреждение::НеиспользуемаяПеременная => "неиспользуемая-переменная",
+            Предупреждение::НеиспользуемыйПараметр => "неиспользуемый-параметр",
+            Предупреждение::Затенение => "затенение",
+            Предупреждение::НедостижимыйКод => "недостижимый-код",
+            Предупреждение::НеиспользуемаяПроцедура => "неиспользуемая-процедура",
+        }
+    }
+
+    pub fn найти(имя: &str) -> Option<Предупреждение> {
+        ВСЕ_ПРЕДУПРЕЖДЕНИЯ.iter().copied().find(|предупреждение| предупреждение.имя() == имя)
+    }
+}
+
+/// Выключенные предупреждения хранятся битами по их порядку в
+/// «ВСЕ_ПРЕДУПРЕЖДЕНИЯ». По умолчанию включены все.
+static ВЫКЛЮЧЕННЫЕ_ПРЕДУПРЕЖДЕНИЯ: AtomicUsize = AtomicUsize::new(0);
+
+static ОШИБКИ_ИЗ_ПРЕДУПРЕЖДЕНИЙ: AtomicBool = AtomicBool::new(false);
+
+/// Сколько ошибок из «КОЛИЧЕСТВО_ОШИБОК» на самом деле являются
+/// предупреждениями.
+pub static КОЛИЧЕСТВО_ОШИБОК_ИЗ_ПРЕДУПРЕЖДЕНИЙ: AtomicUsize = AtomicUsize::new(0);
+
+pub fn предупреждение_включено(предупреждение: Предупреждение) -> bool {
+    ВЫКЛЮЧЕННЫЕ_ПРЕДУПРЕЖДЕНИЯ.load(Ordering::Relaxed) & (1 << предупреждение as usize) == 0
+}
+
+pub fn включить_предупреждение(предупреждение: Предупреждение, включить: bool) {
+    if включить {
+        ВЫКЛЮЧЕННЫЕ_ПРЕДУПРЕЖДЕНИЯ.fetch_and(!(1 << предупреждение as usize), Ordering::Relaxed);
+    } else {
+        ВЫКЛЮЧЕННЫЕ_ПРЕДУПРЕЖДЕНИЯ.fetch_or(1 << предупреждение as usize, Ordering::Relaxed);
+    }
+}
+
+pub fn ошибки_из_предупреждений() -> bool {
+    ОШИБКИ_ИЗ_ПРЕДУПРЕЖДЕНИЙ.load(Ordering::Relaxed)
+}
+
+pub fn задать_ошибки_из_предупреждений(значение: bool) {
+    ОШИБКИ_ИЗ_ПРЕДУПРЕЖДЕНИЙ.store(значение, Ordering::Relaxed);
+}
+
+/// Ошибки, после которых часть кода могла остаться нескомпилированной.
+pub fn количество_настоящих_ошибок() -> usize {
+    количество_ошибок() - КОЛИЧЕСТВО_ОШИБОК_ИЗ_ПРЕДУПРЕЖДЕНИЙ.load(Ordering::Relaxed)
+}
+
 pub enum Род {
     Муж,
     Жен,
@@ -133,3 +254,27 @@ pub const ЧИСУЩ_ИНСТРУКЦИЙ: Чисущ = Чисущ {
     два_три_четыре: "инструкции",
     ноль_пять_много: "инструкций",
 };
+
+pub const ЧИСУЩ_ШАГ: Чисущ = Чисущ {
+    один: "шаг",
+    два_три_четыре: "шага",
+    ноль_пять_много: "шагов",
+};
+
+pub const ЧИСУЩ_ЭЛЕМЕНТ: Чисущ = Чисущ {
+    один: "элемент",
+    два_три_четыре: "элемента",
+    ноль_пять_много: "элементов",
+};
+
+pub const ЧИСУЩ_ОШИБКА: Чисущ = Чисущ {
+    один: "ошибка",
+    два_три_четыре: "ошибки",
+    ноль_пять_много: "ошибок",
+};
+
+pub const ЧИСУЩ_ПЕРЕМЕННАЯ: Чисущ = Чисущ {
+    один: "переменная",
+    два_три_четыре: "переменные",
+    ноль_пять_много: "переменных",
+};
@@ -3,13 +3,20 @@ use std::io::{Read, Write, BufRead};
 use std::convert::TryInto;
 use super::Результат;
 use std::mem;
-use компилятор::{ПП, ВидИнструкции, Инструкция, Имена};
+use компилятор::{ПП, ВидИнструкции, Инструкция, Имена, Арифметика, пределы_целого, пределы_вещественных};
 use типизация::*;
+use мост;
+use диагностика::ЧИСУЩ_ШАГ;
 
 // Разметка памяти
 // |    второй стек    | инициализированные данные | неинициализированные данные |    куча?    |
 // ^                   ^
-// 0                   Начало стека и данных. Стек растет в сторону нуля.
+// база                Начало стека и данных. Стек растет в сторону базы.
+//
+// Адреса машины являются настоящими адресами внутри вектора
+// «память». Благодаря этому их можно передавать во внешние процедуры
+// как есть, а указатели полученные из внешних процедур, указывающие
+// внутрь памяти машины, остаются корректными.
 
 pub const РАЗМЕР_СЛОВА: usize = mem::size_of::<u64>();
 
@@ -24,10 +31,22 @@ pub struct Машина<'ы> {
     // стековая.
 
     pub стек: Vec<usize>,
+    база: usize,
     начало_данных: usize,
     начало_второго_стека: usize,
+    // Память никогда не меняет свой размер после создания машины,
+    // иначе все адреса станут некорректными.
     pub память: Vec<u8>,
     инструкции: &'ы [Инструкция],
+    внешние_символы: Vec<usize>,
+    начало_неиниц_данных: usize,
+
+    /// Машина вычисляет константу во время компиляции: ввод-вывод,
+    /// системные и внешние вызовы, а также глобальные переменные
+    /// недоступны.
+    pub вычисление_константы: bool,
+    /// Максимальное количество инструкций, которое может исполнить машина.
+    pub лимит_шагов: Option<usize>,
 }
 
 macro_rules! ошибка_времени_исполнения {
@@ -45,30 +64,58 @@ macro_rules! ошибка_времени_исполнения {
 
 impl<'ы> Машина<'ы> {
     pub fn новая(пп: &ПП, объём_второго_стека: usize) -> Машина {
-        let начало_данных = объём_второго_стека;
-        let второй_стек = объём_второго_стека;
-        let кадр_второго_стека = объём_второго_стека;
-        let начало_второго_стека = объём_второго_стека;
-        let mut машина = Машина {
+        let mut память = vec![0; объём_второго_стека];
+        память.extend_from_slice(пп.иниц_данные.as_slice());
+        память.resize(память.len() + пп.размер_неиниц_данных, 0);
+
+        for заплатка in &пп.заплатки_иниц_указателей {
+            let индекс = объём_второго_стека + заплатка;
+            let смещение = u64::from_le_bytes(память[индекс..индекс+8].try_into().unwrap());
+            let адрес = память.as_ptr() as u64 + объём_второго_стека as u64 + смещение;
+            память[индекс..индекс+8].copy_from_slice(&адрес.to_le_bytes());
+        }
+
+        let база = память.as_ptr() as usize;
+        let начало_данных = база + объём_второго_стека;
+        let второй_стек = база + объём_второго_стека;
+        let кадр_второго_стека = база + объём_второго_стека;
+        let начало_второго_стека = база + объём_второго_стека;
+        Машина {
             индекс_инструкции: 0,
             кадр: 0,
             второй_стек,
             кадр_второго_стека,
             стек: Vec::new(),
 
+            база,
             начало_данных,
             начало_второго_стека,
 
-            память: vec![],
+            память,
             инструкции: &пп.код,
-        };
-
-        // СДЕЛАТЬ: Ресайз вектора капец какой медленный. Возможно из-за
-        // инициализации. Надо что-нибудь с этим сделать.
-        машина.память.resize(машина.память.len() + объём_второго_стека, 0);
-        машина.память.extend_from_slice(пп.иниц_данные.as_slice());
-        машина.память.resize(машина.память.len() + пп.размер_неиниц_данных, 0);
-        машина
+            внешние_символы: Vec::new(),
+            начало_неиниц_данных: начало_данных + пп.иниц_данные.len(),
+
+            вычисление_константы: false,
+            лимит_шагов: None,
+        }
+    }
+
+    pub fn загрузить_внешние_символы(&mut self, пп: &ПП) -> Результат<()> {
+        let mut библиотеки: Vec<_> = пп.библиотеки.iter().collect();
+        библиотеки.sort_by_key(|(_, индекс)| *индекс);
+        let mut загруженные_библиотеки = Vec::new();
+        for (имя, _) in библиотеки {
+            загруженные_библиотеки.push(мост::загрузить_библиотеку(имя)?);
+        }
+
+        let mut внешние_символы: Vec<_> = пп.внешние_символы.iter().collect();
+        внешние_символы.sort_by_key(|(_, индекс)| *индекс);
+        self.внешние_символы.clear();
+        for (имя, _) in внешние_символы {
+            self.внешние_символы.push(мост::найти_символ(&загруженные_библиотеки, имя)?);
+        }
+        Ok(())
     }
 
     fn протолкнуть_значение_нат(&mut self, значение: usize) -> Результат<()> {
@@ -95,24 +142,43 @@ impl<'ы> Машина<'ы> {
         Ok(f32::from_le_bytes(self.вытолкнуть_значение_нат()?.to_le_bytes()[0..4].try_into().unwrap()))
     }
 
+    fn протолкнуть_значение_вещ64(&mut self, значение: f64) -> Результат<()> {
+        self.протолкнуть_значение_нат(значение.to_bits() as usize)
+    }
+
+    fn вытолкнуть_значение_вещ64(&mut self) -> Результат<f64> {
+        Ok(f64::from_bits(self.вытолкнуть_значение_нат()? as u64))
+    }
+
     // СДЕЛАТЬ: вариант функции срез_памяти, который возвращает массив размера известного на этапе компиляции
     // Возможно через какие-нибудь дженерики. Такой вариант будет очень удобен для чтения примитивных типов
     // из памяти безо всяких этих try_into().unwrap() и прочей лабуды.
     pub fn срез_памяти(&mut self, адрес: usize, размер: usize) -> Результат<&mut [u8]> {
+        let индекс = self.индекс_памяти(адрес, размер)?;
+        Ok(&mut self.память[индекс..индекс+размер])
+    }
+
+    fn индекс_памяти(&self, адрес: usize, размер: usize) -> Результат<usize> {
         let мин = self.второй_стек;
-        let макс = self.память.len();
+        let макс = self.база + self.память.len();
 
-        if адрес < мин {
-            ошибка_времени_исполнения!(self, "Попытка получить доступ к некорректнному диапазону памяти [{начало}..{конец}). Разрешенный диапазон [{мин}..{макс})", начало = адрес, конец = адрес+размер);
+        if адрес < мин || адрес.checked_add(размер).is_none_or(|конец| конец > макс) {
+            // Адреса печатаются относительно начала памяти машины, чтобы
+            // сообщение не менялось от запуска к запуску.
+            let начало = адрес.wrapping_sub(self.база) as isize;
+            ошибка_времени_исполнения!(self, "Попытка получить доступ к некорректнному диапазону памяти [{начало}..{конец}). Разрешенный диапазон [{мин}..{макс})",
+                                       конец = начало.wrapping_add(размер as isize),
+                                       мин = мин - self.база,
+                                       макс = макс - self.база);
             return Err(())
         }
 
-        if let Some(срез) = self.память.get_mut(адрес..адрес+размер) {
-            Ok(срез)
-        } else {
-            ошибка_времени_исполнения!(self, "Попытка получить доступ к некорректнному диапазону памяти [{начало}..{конец}). Разрешенный диапазон [{мин}..{макс})", начало = адрес, конец = адрес+размер);
-            Err(())
+        if self.вычисление_константы && адрес + размер > self.начало_неиниц_данных {
+            ошибка_времени_исполнения!(self, "Глобальные переменные недоступны во время компиляции");
+            return Err(())
         }
+
+        Ok(адрес - self.база)
     }
 
     fn количество_элементов_стека(&self) -> usize {
@@ -129,7 +195,7 @@ impl<'ы> Машина<'ы> {
         }
     }
 
-    fn инструкция(&self) -> Результат<&Инструкция> {
+    fn инструкция(&self) -> Результат<&'ы Инструкция> {
         match self.инструкции.get(self.индекс_инструкции) {
             Some(инструкция) => Ok(инструкция),
             None => {
@@ -140,7 +206,8 @@ impl<'ы> Машина<'ы> {
     }
 
     fn выделить_на_втором_стеке(&mut self, размер: usize) -> Результат<()> {
-        if self.второй_стек < размер {
+        // Второй стек растёт вниз к началу памяти машины.
+        if self.второй_стек - self.база < размер {
             ошибка_времени_исполнения!(self, "переполнение второго стека");
             return Err(())
         }
@@ -174,11 +241,42 @@ impl<'ы> Машина<'ы> {
 
         let mut глубина_вызовов = 0;
         let mut цель_перешагивания: Option<usize> = None;
+        let mut шаги = 0;
         self.протолкнуть_значение_нат(self.инструкции.len())?;
         loop {
             let индекс_инструкции = self.индекс_инструкции;
             let инструкция = self.инструкция()?;
 
+            if let Some(лимит) = self.лимит_шагов {
+                if шаги >= лимит {
+                    ошибка_времени_исполнения!(self, "Превышен лимит в {лимит} {шагов} исполнения", шагов = ЧИСУЩ_ШАГ.текст(лимит));
+                    return Err(())
+                }
+                шаги += 1;
+            }
+
+            if self.вычисление_константы {
+                match &инструкция.вид {
+                    ВидИнструкции::ПечатьСтроки | ВидИнструкции::Ввод => {
+                        ошибка_времени_исполнения!(self, "Ввод-вывод недоступен во время компиляции");
+                        return Err(())
+                    }
+                    ВидИнструкции::СисВызов{..} => {
+                        ошибка_времени_исполнения!(self, "Системные вызовы недоступны во время компиляции");
+                        return Err(())
+                    }
+                    ВидИнструкции::ВнешнийВызов{..} => {
+                        ошибка_времени_исполнения!(self, "Внешние процедуры недоступны во время компиляции");
+                        return Err(())
+                    }
+                    ВидИнструкции::АдресПамяти => {
+                        ошибка_времени_исполнения!(self, "Адреса памяти недоступны во время компиляции");
+                        return Err(())
+                    }
+                    _ => {}
+                }
+            }
+
             if режим_отладки {
                 if let Some(цель) = цель_перешагивания.clone() {
                     if глубина_вызовов <= цель {
@@ -190,12 +288,13 @@ impl<'ы> Машина<'ы> {
                     диагностика!(&инструкция.лок, "ИНСТРУКЦИЯ", "{индекс_инструкции}: {вид_инструкции:?}", вид_инструкции = инструкция.вид);
                     eprintln!("стек = {стек:?}", стек = self.стек);
                     eprintln!("кадр = {кадр}", кадр = self.кадр);
-                    eprintln!("второй_стек = {второй_стек}", второй_стек = self.второй_стек);
-                    eprintln!("кадр_второго_стека = {кадр_второго_стека}", кадр_второго_стека = self.кадр_второго_стека);
+                    eprintln!("второй_стек = {второй_стек}", второй_стек = self.второй_стек - self.база);
+                    eprintln!("кадр_второго_стека = {кадр_второго_стека}", кадр_второго_стека = self.кадр_второго_стека - self.база);
                     eprintln!("переменные");
                     for (имя, переменная) in имена.переменные.iter() {
                         let адрес = self.начало_данных + переменная.смещение as usize;
-                        eprintln!("  {имя}: {адрес:#X} = {:?}", &self.память[адрес..адрес+переменная.тип.размер(&имена.структуры)]);
+                        let индекс = адрес - self.база;
+                        eprintln!("  {имя}: {индекс} = {:?}", &self.память[индекс..индекс+переменная.тип.размер(&имена.структуры)]);
                     }
                     loop {
                         let mut команда = String::new();
@@ -238,7 +337,7 @@ impl<'ы> Машина<'ы> {
             }
 
             match &инструкция.вид {
-                ВидИнструкции::Ноп => {
+                ВидИнструкции::Ноп | ВидИнструкции::АдресПамяти => {
                     self.индекс_инструкции += 1;
                 }
                 ВидИнструкции::Вытолкнуть => {
@@ -251,8 +350,14 @@ impl<'ы> Машина<'ы> {
                     self.протолкнуть_значение_нат(значение)?;
                     self.индекс_инструкции += 1;
                 }
+                ВидИнструкции::Поменять => {
+                    self.проверить_арность_аргументов(2)?;
+                    let длина = self.стек.len();
+                    self.стек.swap(длина - 1, длина - 2);
+                    self.индекс_инструкции += 1;
+                }
                 &ВидИнструкции::ГлобальныеДанные(смещение) => {
-                    self.протолкнуть_значение_нат((self.начало_данных as i32 + смещение) as usize)?;
+                    self.протолкнуть_значение_нат((self.начало_данных as i64 + смещение as i64) as usize)?;
                     self.индекс_инструкции += 1;
                 }
                 &ВидИнструкции::Натуральное(значение)  => {
@@ -272,7 +377,7 @@ impl<'ы> Машина<'ы> {
                     self.индекс_инструкции += 1;
                 }
                 &ВидИнструкции::ВершинаСтека(смещение) => {
-                    self.протолкнуть_значение_нат((self.второй_стек as i32 + смещение) as usize)?;
+                    self.протолкнуть_значение_нат((self.второй_стек as i64 + смещение as i64) as usize)?;
                     self.индекс_инструкции += 1;
                 }
                 ВидИнструкции::СохранитьКадр => {
@@ -286,7 +391,7 @@ impl<'ы> Машина<'ы> {
                     self.индекс_инструкции += 1;
                 }
                 ВидИнструкции::Кадр(смещение) => {
-                    self.протолкнуть_значение_нат((self.кадр_второго_стека as i32 + смещение) as usize)?;
+                    self.протолкнуть_значение_нат((self.кадр_второго_стека as i64 + *смещение as i64) as usize)?;
                     self.индекс_инструкции += 1;
                 }
                 ВидИнструкции::АргументНаСтек => {
@@ -304,9 +409,53 @@ impl<'ы> Машина<'ы> {
                     self.протолкнуть_значение_нат(индекс_инструкции + 1)?;
                     self.индекс_инструкции = адрекс;
                 }
-                ВидИнструкции::ВнешнийВызов{..} => {
-                    ошибка_времени_исполнения!(self, "вынешние вызовы не поддерживаются в режиме интерпретации");
-                    return Err(())
+                ВидИнструкции::ВнешнийВызов{индекс, параметры, вариативные_аргументы, результат} => {
+                    let Some(&адрес) = self.внешние_символы.get(*индекс) else {
+                        ошибка_времени_исполнения!(self, "внешний символ под индексом {индекс} не был загружен");
+                        return Err(())
+                    };
+                    let вариативные_аргументы = вариативные_аргументы.as_deref().unwrap_or(&[]);
+                    self.проверить_арность_аргументов(параметры.len() + вариативные_аргументы.len())?;
+                    let mut целые_аргументы = Vec::new();
+                    let mut вещ_аргументы = Vec::new();
+                    let вариативные = вариативные_аргументы.iter().map(|тип| (тип, true));
+                    for (тип, вариативный) in параметры.iter().map(|тип| (тип, false)).chain(вариативные) {
+                        let значение = self.вытолкнуть_значение_нат()? as u64;
+                        match тип {
+                            Тип::Вещ32 if вариативный => {
+                                // Вариативные вещественные аргументы повышаются до double
+                                вещ_аргументы.push((f32::from_bits(значение as u32) as f64).to_bits());
+                            }
+                            Тип::Вещ32 | Тип::Вещ64 => вещ_аргументы.push(значение),
+                            _ => целые_аргументы.push(значение),
+                        }
+                    }
+                    if целые_аргументы.len() > мост::ЦЕЛЫХ_РЕГИСТРОВ || вещ_аргументы.len() > мост::ВЕЩ_РЕГИСТРОВ {
+                        ошибка_времени_исполнения!(self, "передача аргументов внешним процедурам через стек не поддерживается");
+                        return Err(())
+                    }
+                    let вещ_результат = matches!(результат, Some(Тип::Вещ32 | Тип::Вещ64));
+                    let значение = unsafe {
+                        мост::вызвать(адрес, &целые_аргументы, &вещ_аргументы, вещ_результат)
+                    };
+                    match результат {
+                        None => {}
+                        Some(Тип::Нат64 | Тип::Цел64 | Тип::Вещ64) => self.протолкнуть_значение_нат(значение as usize)?,
+                        // Старшие биты узких целых в регистре не определены.
+                        Some(Тип::Нат8) => self.протолкнуть_значение_нат(значение as u8 as usize)?,
+                        Some(Тип::Нат16) => self.протолкнуть_значение_нат(значение as u16 as usize)?,
+                        Some(Тип::Нат32) => self.протолкнуть_значение_нат(значение as u32 as usize)?,
+                        Some(Тип::Цел8) => self.протолкнуть_значение_нат(значение as i8 as i64 as usize)?,
+                        Some(Тип::Цел16) => self.протолкнуть_значение_нат(значение as i16 as i64 as usize)?,
+                        Some(Тип::Цел32) => self.протолкнуть_значение_нат(значение as i32 as i64 as usize)?,
+                        Some(Тип::Лог) => self.протолкнуть_значение_нат((значение & 0xFF != 0) as usize)?,
+                        Some(Тип::Вещ32) => self.протолкнуть_значение_нат((значение & 0xFFFFFFFF) as usize)?,
+                        Some(тип) => {
+                            ошибка_времени_исполнения!(self, "возврат значений типа «{тип}» из внешних процедур не поддерживается", тип = тип.текст());
+                            return Err(())
+                        }
+                    }
+                    self.индекс_инструкции += 1;
                 }
                 ВидИнструкции::Записать8 => {
                     self.проверить_арность_аргументов(2)?;
@@ -317,8 +466,11 @@ impl<'ы> Машина<'ы> {
                     self.индекс_инструкции += 1;
                 }
                 ВидИнструкции::Записать16 => {
-                    сделать!(&инструкция.лок, "Интерпретация инструкции Записать16");
-                    return Err(());
+                    self.проверить_арность_аргументов(2)?;
+                    let адрес = self.вытолкнуть_значение_нат()?;
+                    let значение = self.вытолкнуть_значение_нат()? as u16;
+                    self.срез_памяти(адрес, 2)?.copy_from_slice(&значение.to_le_bytes());
+                    self.индекс_инструкции += 1;
                 }
                 ВидИнструкции::Записать32 => {
                     self.проверить_арность_аргументов(2)?;
@@ -343,8 +495,11 @@ impl<'ы> Машина<'ы> {
                     self.индекс_инструкции += 1;
                 }
                 ВидИнструкции::ПрочитатьБезЗнак16 => {
-                    сделать!(&инструкция.лок, "Интерпртация инструкции ПрочитатьБезЗнак32");
-                    return Err(());
+                    self.проверить_арность_аргументов(1)?;
+                    let адрес = self.вытолкнуть_значение_нат()?;
+                    let значение: u16 = u16::from_le_bytes(self.срез_памяти(адрес, 2)?.try_into().unwrap());
+                    self.протолкнуть_значение_нат(значение as usize)?;
+                    self.индекс_инструкции += 1;
                 }
                 ВидИнструкции::ПрочитатьБезЗнак32 => {
                     self.проверить_арность_аргументов(1)?;
@@ -354,16 +509,25 @@ impl<'ы> Машина<'ы> {
                     self.индекс_инструкции += 1;
                 }
                 ВидИнструкции::ПрочитатьЗнак8 => {
-                    сделать!(&инструкция.лок, "Интерпртация инструкции ПрочитатьЗнак8");
-                    return Err(());
+                    self.проверить_арность_аргументов(1)?;
+                    let адрес = self.вытолкнуть_значение_нат()?;
+                    let значение: i8 = i8::from_le_bytes(self.срез_памяти(адрес, 1)?.try_into().unwrap());
+                    self.протолкнуть_значение_нат(значение as i64 as usize)?;
+                    self.индекс_инструкции += 1;
                 }
                 ВидИнструкции::ПрочитатьЗнак16 => {
-                    сделать!(&инструкция.лок, "Интерпртация инструкции ПрочитатьЗнак8");
-                    return Err(());
+                    self.проверить_арность_аргументов(1)?;
+                    let адрес = self.вытолкнуть_значение_нат()?;
+                    let значение: i16 = i16::from_le_bytes(self.срез_памяти(адрес, 2)?.try_into().unwrap());
+                    self.протолкнуть_значение_нат(значение as i64 as usize)?;
+                    self.индекс_инструкции += 1;
                 }
                 ВидИнструкции::ПрочитатьЗнак32 => {
-                    сделать!(&инструкция.лок, "Интерпртация инструкции ПрочитатьЗнак32");
-                    return Err(());
+                    self.проверить_арность_аргументов(1)?;
+                    let адрес = self.вытолкнуть_значение_нат()?;
+                    let значение: i32 = i32::from_le_bytes(self.срез_памяти(адрес, 4)?.try_into().unwrap());
+                    self.протолкнуть_значение_нат(значение as i64 as usize)?;
+                    self.индекс_инструкции += 1;
                 }
                 ВидИнструкции::Прочитать64 => {
                     self.проверить_арность_аргументов(1)?;
@@ -377,23 +541,18 @@ impl<'ы> Машина<'ы> {
                     let размер = self.вытолкнуть_значение_нат()?;
                     let цель = self.вытолкнуть_значение_нат()?;
                     let источник = self.вытолкнуть_значение_нат()?;
-                    for индекс in 0..размер {
-                        self.память[цель + индекс] = self.память[источник + индекс];
-                    }
+                    let цель = self.индекс_памяти(цель, размер)?;
+                    let источник = self.индекс_памяти(источник, размер)?;
+                    self.память.copy_within(источник..источник+размер, цель);
                     self.индекс_инструкции += 1;
                 }
                 ВидИнструкции::ПамятьРавно => {
                     let размер = self.вытолкнуть_значение_нат()?;
                     let цель = self.вытолкнуть_значение_нат()?;
                     let источник = self.вытолкнуть_значение_нат()?;
-                    let mut равна = true;
-                    for индекс in 0..размер {
-                        if self.память[цель + индекс] != self.память[источник + индекс] {
-                            равна = false;
-                            break;
-                        }
-                    }
-                    if равна {
+                    let цель = self.индекс_памяти(цель, размер)?;
+                    let источник = self.индекс_памяти(источник, размер)?;
+                    if self.память[цель..цель+размер] == self.память[источник..источник+размер] {
                         self.протолкнуть_значение_нат(1)?;
                     } else {
                         self.протолкнуть_значение_нат(0)?;
@@ -475,13 +634,17 @@ impl<'ы> Машина<'ы> {
                     self.проверить_арность_аргументов(2)?;
                     let правый = self.вытолкнуть_значение_нат()?;
                     let левый = self.вытолкнуть_значение_нат()?;
-                    self.протолкнуть_значение_нат(левый * правый)?;
+                    self.протолкнуть_значение_нат(левый.wrapping_mul(правый))?;
                     self.индекс_инструкции += 1;
                 }
                 ВидИнструкции::НатДеление => {
                     self.проверить_арность_аргументов(2)?;
                     let правый = self.вытолкнуть_значение_нат()?;
                     let левый = self.вытолкнуть_значение_нат()?;
+                    if правый == 0 {
+                        ошибка_времени_исполнения!(self, "Деление {левый} на ноль");
+                        return Err(())
+                    }
                     self.протолкнуть_значение_нат(левый / правый)?;
                     self.индекс_инструкции += 1;
                 }
@@ -489,6 +652,10 @@ impl<'ы> Машина<'ы> {
                     self.проверить_арность_аргументов(2)?;
                     let правый = self.вытолкнуть_значение_нат()?;
                     let левый = self.вытолкнуть_значение_нат()?;
+                    if правый == 0 {
+                        ошибка_времени_исполнения!(self, "Деление {левый} на ноль");
+                        return Err(())
+                    }
                     self.протолкнуть_значение_нат(левый % правый)?;
                     self.индекс_инструкции += 1;
                 }
@@ -539,28 +706,36 @@ impl<'ы> Машина<'ы> {
                 ВидИнструкции::ЦелОтриц => {
                     self.проверить_арность_аргументов(1)?;
                     let значение = self.вытолкнуть_значение_нат()? as i64;
-                    self.протолкнуть_значение_нат((-значение) as usize)?;
+                    self.протолкнуть_значение_нат(значение.wrapping_neg() as usize)?;
                     self.индекс_инструкции += 1;
                 }
                 ВидИнструкции::ЦелУмножение => {
                     self.проверить_арность_аргументов(2)?;
                     let правый = self.вытолкнуть_значение_нат()? as i64;
                     let левый = self.вытолкнуть_значение_нат()? as i64;
-                    self.протолкнуть_значение_нат((левый * правый) as usize)?;
+                    self.протолкнуть_значение_нат(левый.wrapping_mul(правый) as usize)?;
                     self.индекс_инструкции += 1;
                 }
                 ВидИнструкции::ЦелДеление => {
                     self.проверить_арность_аргументов(2)?;
                     let правый = self.вытолкнуть_значение_нат()? as i64;
                     let левый = self.вытолкнуть_значение_нат()? as i64;
-                    self.протолкнуть_значение_нат((левый / правый) as usize)?;
+                    if правый == 0 {
+                        ошибка_времени_исполнения!(self, "Деление {левый} на ноль");
+                        return Err(())
+                    }
+                    self.протолкнуть_значение_нат(левый.wrapping_div(правый) as usize)?;
                     self.индекс_инструкции += 1;
                 }
                 ВидИнструкции::ЦелОстаток => {
                     self.проверить_арность_аргументов(2)?;
                     let правый = self.вытолкнуть_значение_нат()? as i64;
                     let левый = self.вытолкнуть_значение_нат()? as i64;
-                    self.протолкнуть_значение_нат((левый % правый) as usize)?;
+                    if правый == 0 {
+                        ошибка_времени_исполнения!(self, "Деление {левый} на ноль");
+                        return Err(())
+                    }
+                    self.протолкнуть_значение_нат(левый.wrapping_rem(правый) as usize)?;
                     self.индекс_инструкции += 1;
                 }
                 ВидИнструкции::КонвертНат64Вещ32 => {
@@ -597,6 +772,13 @@ impl<'ы> Машина<'ы> {
                     self.протолкнуть_значение_вещ32(левый / правый)?;
                     self.индекс_инструкции += 1;
                 }
+                ВидИнструкции::Вещ32Остаток => {
+                    self.проверить_арность_аргументов(2)?;
+                    let правый = self.вытолкнуть_значение_вещ32()?;
+                    let левый = self.вытолкнуть_значение_вещ32()?;
+                    self.протолкнуть_значение_вещ32(левый % правый)?;
+                    self.индекс_инструкции += 1;
+                }
                 ВидИнструкции::Вещ32Сложение => {
                     self.проверить_арность_аргументов(2)?;
                     let правый = self.вытолкнуть_значение_вещ32()?;
@@ -655,11 +837,147 @@ impl<'ы> Машина<'ы> {
                     }
                     self.индекс_инструкции += 1;
                 }
+                ВидИнструкции::Вещ32Равно => {
+                    self.проверить_арность_аргументов(2)?;
+                    let правый = self.вытолкнуть_значение_вещ32()?;
+                    let левый = self.вытолкнуть_значение_вещ32()?;
+                    if левый == правый {
+                        self.протолкнуть_значение_нат(1)?;
+                    } else {
+                        self.протолкнуть_значение_нат(0)?;
+                    }
+                    self.индекс_инструкции += 1;
+                }
                 ВидИнструкции::Вещ32Отриц => {
                     let значение = self.вытолкнуть_значение_вещ32()?;
                     self.протолкнуть_значение_вещ32(-значение)?;
                     self.индекс_инструкции += 1;
                 }
+                ВидИнструкции::КонвертНат64Вещ64 => {
+                    let значение = self.вытолкнуть_значение_нат()?;
+                    self.протолкнуть_значение_вещ64(значение as f64)?;
+                    self.индекс_инструкции += 1;
+                }
+                ВидИнструкции::КонвертЦел64Вещ64 => {
+                    let значение = self.вытолкнуть_значение_нат()? as i64;
+                    self.протолкнуть_значение_вещ64(значение as f64)?;
+                    self.индекс_инструкции += 1;
+                }
+                ВидИнструкции::КонвертВещ64Нат64 => {
+                    let значение = self.вытолкнуть_значение_вещ64()?;
+                    self.протолкнуть_значение_нат(значение as usize)?;
+                    self.индекс_инструкции += 1;
+                }
+                ВидИнструкции::КонвертВещ64Цел64 => {
+                    let значение = self.вытолкнуть_значение_вещ64()?;
+                    self.протолкнуть_значение_нат((значение as i64) as usize)?;
+                    self.индекс_инструкции += 1;
+                }
+                ВидИнструкции::КонвертВещ32Вещ64 => {
+                    let значение = self.вытолкнуть_значение_вещ32()?;
+                    self.протолкнуть_значение_вещ64(значение as f64)?;
+                    self.индекс_инструкции += 1;
+                }
+                ВидИнструкции::КонвертВещ64Вещ32 => {
+                    let значение = self.вытолкнуть_значение_вещ64()?;
+                    self.протолкнуть_значение_вещ32(значение as f32)?;
+                    self.индекс_инструкции += 1;
+                }
+                ВидИнструкции::Вещ64Умножение => {
+                    self.проверить_арность_аргументов(2)?;
+                    let правый = self.вытолкнуть_значение_вещ64()?;
+                    let левый = self.вытолкнуть_значение_вещ64()?;
+                    self.протолкнуть_значение_вещ64(левый * правый)?;
+                    self.индекс_инструкции += 1;
+                }
+                ВидИнструкции::Вещ64Деление => {
+                    self.проверить_арность_аргументов(2)?;
+                    let правый = self.вытолкнуть_значение_вещ64()?;
+                    let левый = self.вытолкнуть_значение_вещ64()?;
+                    self.протолкнуть_значение_вещ64(левый / правый)?;
+                    self.индекс_инструкции += 1;
+                }
+                ВидИнструкции::Вещ64Остаток => {
+                    self.проверить_арность_аргументов(2)?;
+                    let правый = self.вытолкнуть_значение_вещ64()?;
+                    let левый = self.вытолкнуть_значение_вещ64()?;
+                    self.протолкнуть_значение_вещ64(левый % правый)?;
+                    self.индекс_инструкции += 1;
+                }
+                ВидИнструкции::Вещ64Сложение => {
+                    self.проверить_арность_аргументов(2)?;
+                    let правый = self.вытолкнуть_значение_вещ64()?;
+                    let левый = self.вытолкнуть_значение_вещ64()?;
+                    self.протолкнуть_значение_вещ64(левый + правый)?;
+                    self.индекс_инструкции += 1;
+                }
+                ВидИнструкции::Вещ64Вычитание => {
+                    self.проверить_арность_аргументов(2)?;
+                    let правый = self.вытолкнуть_значение_вещ64()?;
+                    let левый = self.вытолкнуть_значение_вещ64()?;
+                    self.протолкнуть_значение_вещ64(левый - правый)?;
+                    self.индекс_инструкции += 1;
+                }
+                ВидИнструкции::Вещ64Меньше => {
+                    self.проверить_арность_аргументов(2)?;
+                    let правый = self.вытолкнуть_значение_вещ64()?;
+                    let левый = self.вытолкнуть_значение_вещ64()?;
+                    if левый < правый {
+                        self.протолкнуть_значение_нат(1)?;
+                    } else {
+                        self.протолкнуть_значение_нат(0)?;
+                    }
+                    self.индекс_инструкции += 1;
+                }
+                ВидИнструкции::Вещ64МеньшеРавно => {
+                    self.проверить_арность_аргументов(2)?;
+                    let правый = self.вытолкнуть_значение_вещ64()?;
+                    let левый = self.вытолкнуть_значение_вещ64()?;
+                    if левый <= правый {
+                        self.протолкнуть_значение_нат(1)?;
+                    } else {
+                        self.протолкнуть_значение_нат(0)?;
+                    }
+                    self.индекс_инструкции += 1;
+                }
+                ВидИнструкции::Вещ64Больше => {
+                    self.проверить_арность_аргументов(2)?;
+                    let правый = self.вытолкнуть_значение_вещ64()?;
+                    let левый = self.вытолкнуть_значение_вещ64()?;
+                    if левый > правый {
+                        self.протолкнуть_значение_нат(1)?;
+                    } else {
+                        self.протолкнуть_значение_нат(0)?;
+                    }
+                    self.индекс_инструкции += 1;
+                }
+                ВидИнструкции::Вещ64БольшеРавно => {
+                    self.проверить_арность_аргументов(2)?;
+                    let правый = self.вытолкнуть_значение_вещ64()?;
+                    let левый = self.вытолкнуть_значение_вещ64()?;
+                    if левый >= правый {
+                        self.протолкнуть_значение_нат(1)?;
+                    } else {
+                        self.протолкнуть_значение_нат(0)?;
+                    }
+                    self.индекс_инструкции += 1;
+                }
+                ВидИнструкции::Вещ64Равно => {
+                    self.проверить_арность_аргументов(2)?;
+                    let правый = self.вытолкнуть_значение_вещ64()?;
+                    let левый = self.вытолкнуть_значение_вещ64()?;
+                    if левый == правый {
+                        self.протолкнуть_значение_нат(1)?;
+                    } else {
+                        self.протолкнуть_значение_нат(0)?;
+                    }
+                    self.индекс_инструкции += 1;
+                }
+                ВидИнструкции::Вещ64Отриц => {
+                    let значение = self.вытолкнуть_значение_вещ64()?;
+                    self.протолкнуть_значение_вещ64(-значение)?;
+                    self.индекс_инструкции += 1;
+                }
                 ВидИнструкции::ЛогОтрицание => {
                     self.проверить_арность_аргументов(1)?;
                     let значение = self.вытолкнуть_значение_нат()?;
@@ -688,16 +1006,23 @@ impl<'ы> Машина<'ы> {
                     self.протолкнуть_значение_нат(а ^ б)?;
                     self.индекс_инструкции += 1;
                 }
+                // Как и процессор, учитываем только младшие 6 бит сдвига.
                 ВидИнструкции::БитСмещениеВлево => {
                     let сдвиг = self.вытолкнуть_значение_нат()?;
                     let значение = self.вытолкнуть_значение_нат()?;
-                    self.протолкнуть_значение_нат(значение << сдвиг)?;
+                    self.протолкнуть_значение_нат(значение.wrapping_shl(сдвиг as u32))?;
                     self.индекс_инструкции += 1;
                 }
                 ВидИнструкции::БитСмещениеВправо => {
                     let сдвиг = self.вытолкнуть_значение_нат()?;
                     let значение = self.вытолкнуть_значение_нат()?;
-                    self.протолкнуть_значение_нат(значение >> сдвиг)?;
+                    self.протолкнуть_значение_нат(значение.wrapping_shr(сдвиг as u32))?;
+                    self.индекс_инструкции += 1;
+                }
+                ВидИнструкции::ЦелСмещениеВправо => {
+                    let сдвиг = self.вытолкнуть_значение_нат()?;
+                    let значение = self.вытолкнуть_значение_нат()? as i64;
+                    self.протолкнуть_значение_нат(значение.wrapping_shr(сдвиг as u32) as usize)?;
                     self.индекс_инструкции += 1;
                 }
                 ВидИнструкции::Прыжок(индекс) => {
@@ -747,6 +1072,122 @@ impl<'ы> Машина<'ы> {
                     ошибка_времени_исполнения!(self, "системные вызовы не поддерживаются в режиме интерпретации");
                     return Err(())
                 }
+                &ВидИнструкции::ПроверитьГраницы{знаковый} => {
+                    self.проверить_арность_аргументов(2)?;
+                    let индекс = self.вытолкнуть_значение_нат()?;
+                    let длина = self.вытолкнуть_значение_нат()?;
+                    if индекс >= длина {
+                        if знаковый {
+                            ошибка_времени_исполнения!(self, "Индекс {индекс} выходит за границы [0..{длина})", индекс = индекс as i64);
+                        } else {
+                            ошибка_времени_исполнения!(self, "Индекс {индекс} выходит за границы [0..{длина})");
+                        }
+                        return Err(())
+                    }
+                    self.протолкнуть_значение_нат(индекс)?;
+                    self.индекс_инструкции += 1;
+                }
+                ВидИнструкции::ПроверитьСрез => {
+                    self.проверить_арность_аргументов(4)?;
+                    let длина = self.вытолкнуть_значение_нат()?;
+                    let начало = self.стек[self.стек.len() - 1];
+                    let размер = self.стек[self.стек.len() - 3];
+                    if начало > длина || размер > длина - начало {
+                        ошибка_времени_исполнения!(self, "Срез с началом {начало} и размером {размер} выходит за границы [0..{длина})");
+                        return Err(())
+                    }
+                    self.индекс_инструкции += 1;
+                }
+                &ВидИнструкции::ПроверитьАрифметику{операция, знаковый, разрядность} => {
+                    let (мин, макс) = пределы_целого(знаковый, разрядность);
+                    let значение = |слово: usize| -> i128 {
+                        if знаковый { слово as i64 as i128 } else { слово as u64 as i128 }
+                    };
+                    let результат = if операция == Арифметика::Отрицание {
+                        self.проверить_арность_аргументов(1)?;
+                        let операнд = значение(self.стек[self.стек.len() - 1]);
+                        if -операнд < мин as i128 || -операнд > макс as i128 {
+                            ошибка_времени_исполнения!(self, "Переполнение при отрицании {операнд}");
+                            return Err(())
+                        }
+                        None
+                    } else {
+                        self.проверить_арность_аргументов(2)?;
+                        let правый = значение(self.стек[self.стек.len() - 1]);
+                        let левый = значение(self.стек[self.стек.len() - 2]);
+                        let результат = match операция {
+                            Арифметика::Сложение => левый.checked_add(правый),
+                            Арифметика::Вычитание => левый.checked_sub(правый),
+                            Арифметика::Умножение => левый.checked_mul(правый),
+                            Арифметика::Деление => {
+                                if правый == 0 {
+                                    ошибка_времени_исполнения!(self, "Деление {левый} на ноль");
+                                    return Err(())
+                                }
+                                левый.checked_div(правый)
+                            }
+                            Арифметика::Отрицание => unreachable!(),
+                        };
+                        match результат {
+                            Some(результат) if мин as i128 <= результат && результат <= макс as i128 => None,
+                            _ => Some((левый, правый)),
+                        }
+                    };
+                    if let Some((левый, правый)) = результат {
+                        match операция {
+                            Арифметика::Сложение => ошибка_времени_исполнения!(self, "Переполнение при сложении {левый} и {правый}"),
+                            Арифметика::Вычитание => ошибка_времени_исполнения!(self, "Переполнение при вычитании {правый} из {левый}"),
+                            Арифметика::Умножение => ошибка_времени_исполнения!(self, "Переполнение при умножении {левый} и {правый}"),
+                            Арифметика::Деление => ошибка_времени_исполнения!(self, "Переполнение при делении {левый} на {правый}"),
+                            Арифметика::Отрицание => unreachable!(),
+                        }
+                        return Err(())
+                    }
+                    self.индекс_инструкции += 1;
+                }
+                &ВидИнструкции::ПроверитьДиапазон{знаковый, мин, макс} => {
+                    self.проверить_арность_аргументов(1)?;
+                    let слово = self.стек[self.стек.len() - 1];
+                    let значение = if знаковый { слово as i64 as i128 } else { слово as u64 as i128 };
+                    if значение < мин as i128 || значение > макс as i128 {
+                        ошибка_времени_исполнения!(self, "Значение {значение} выходит за пределы [{мин}..{макс}]");
+                        return Err(())
+                    }
+                    self.индекс_инструкции += 1;
+                }
+                &ВидИнструкции::ПроверитьДиапазонВещ32{мин, макс} => {
+                    self.проверить_арность_аргументов(1)?;
+                    let значение = f32::from_bits(self.стек[self.стек.len() - 1] as u32) as f64;
+                    let (нижний, включительно, верхний) = пределы_вещественных(мин, макс);
+                    let выше_нижнего = if включительно { значение >= нижний } else { значение > нижний };
+                    // NaN не проходит ни одно из сравнений.
+                    if !(выше_нижнего && значение < верхний) {
+                        ошибка_времени_исполнения!(self, "Вещественное значение выходит за пределы [{мин}..{макс}]");
+                        return Err(())
+                    }
+                    self.индекс_инструкции += 1;
+                }
+                ВидИнструкции::ПроверитьСимвол => {
+                    self.проверить_арность_аргументов(1)?;
+                    let значение = self.стек[self.стек.len() - 1];
+                    if СУРРОГАТЫ.contains(&(значение as u32)) {
+                        ошибка_времени_исполнения!(self, "Значение {значение} является суррогатом UTF-16 и не может быть символом");
+                        return Err(())
+                    }
+                    self.индекс_инструкции += 1;
+                }
+                &ВидИнструкции::ПроверитьДиапазонВещ64{мин, макс} => {
+                    self.проверить_арность_аргументов(1)?;
+                    let значение = f64::from_bits(self.стек[self.стек.len() - 1] as u64);
+                    let (нижний, включительно, верхний) = пределы_вещественных(мин, макс);
+                    let выше_нижнего = if включительно { значение >= нижний } else { значение > нижний };
+                    // NaN не проходит ни одно из сравнений.
+                    if !(выше_нижнего && значение < верхний) {
+                        ошибка_времени_исполнения!(self, "Вещественное значение выходит за пределы [{мин}..{макс}]");
+                        return Err(())
+                    }
+                    self.индекс_инструкции += 1;
+                }
             }
         }
         Ok(())
@@ -1,8 +1,10 @@
 /// Промежуточное Представление
 
 use super::Результат;
+use std::cell::Cell;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::env;
+use std::path::{Path, PathBuf};
 use синтаксис::*;
 use диагностика::*;
 use лексика::*;
@@ -20,6 +22,7 @@ pub enum ВидИнструкции {
     Целое(i64),
     Вытолкнуть,
     Продублировать,
+    Поменять,
     /// Протолкнуть указатель на глобальные данные.
     ///
     /// Эта инструкция нужна потому, что мы не знаем во время
@@ -77,22 +80,49 @@ pub enum ВидИнструкции {
     Вещ32Вычитание,
     Вещ32Умножение,
     Вещ32Деление,
+    /// Остаток с отброшенной дробной частью частного, как fmodf в Си.
+    /// Знак остатка совпадает со знаком делимого.
+    Вещ32Остаток,
     Вещ32Меньше,
     Вещ32МеньшеРавно,
     Вещ32Больше,
     Вещ32БольшеРавно,
+    /// Сравнение по IEEE 754: «-0.0» равен «0.0», а NaN не равен ничему.
+    Вещ32Равно,
     Вещ32Отриц,
+    КонвертНат64Вещ64,
+    КонвертЦел64Вещ64,
+    КонвертВещ64Нат64,
+    КонвертВещ64Цел64,
+    КонвертВещ32Вещ64,
+    КонвертВещ64Вещ32,
+    Вещ64Сложение,
+    Вещ64Вычитание,
+    Вещ64Умножение,
+    Вещ64Деление,
+    Вещ64Остаток,
+    Вещ64Меньше,
+    Вещ64МеньшеРавно,
+    Вещ64Больше,
+    Вещ64БольшеРавно,
+    Вещ64Равно,
+    Вещ64Отриц,
     ЛогОтрицание,
     БитИли,
     БитИ,
     БитЛибо,
     БитСмещениеВлево,
     БитСмещениеВправо,
+    /// Арифметическое смещение вправо, сохраняющее знак.
+    ЦелСмещениеВправо,
     ПечатьСтроки,
     Ввод,
     Возврат,
     ВнутреннийВызов(usize),
-    ВнешнийВызов{индекс: usize, арность: usize, результат: Option<Тип>},
+    /// Аргументы лежат на стеке в порядке параметров: первый аргумент на
+    /// вершине. Если процедура вариативная, то за фиксированными параметрами
+    /// следуют типы переданных вариативных аргументов.
+    ВнешнийВызов{индекс: usize, параметры: Vec<Тип>, вариативные_аргументы: Option<Vec<Тип>>, результат: Option<Тип>},
     // СДЕЛАТЬ: инструкции изменения потока исполнения должны принимать относительное смещение.
     // Данный подход позволяет создавать легко перемещаемый код. Хотя, я не уверен, что это подойдет
     // для ВызватьВнутренююПроцедуру.
@@ -102,6 +132,45 @@ pub enum ВидИнструкции {
     Прыжок(usize),
     УсловныйПрыжок(usize),
     СисВызов{ арность: usize },
+    /// Снимает со стека индекс и длину под ним и возвращает индекс
+    /// обратно на стек, если он находится в пределах [0..длина).
+    ПроверитьГраницы{ знаковый: bool },
+    /// Снимает со стека длину и проверяет, что срез с началом и
+    /// размером лежащими под ней находится в пределах [0..длина).
+    /// Ожидаемый стек: размер, <любое значение>, начало, длина.
+    ПроверитьСрез,
+    /// Проверяет, что операция над целыми на вершине стека не выйдет за
+    /// пределы целого заданной разрядности и не поделит на ноль. Стек
+    /// не изменяется.
+    ПроверитьАрифметику{ операция: Арифметика, знаковый: bool, разрядность: u32 },
+    /// Проверяет, что целое на вершине стека лежит в пределах [мин..макс].
+    /// Стек не изменяется.
+    ПроверитьДиапазон{ знаковый: bool, мин: i64, макс: u64 },
+    /// Проверяет, что вещ32 на вершине стека после отбрасывания дробной
+    /// части лежит в пределах [мин..макс]. Стек не изменяется.
+    ПроверитьДиапазонВещ32{ мин: i64, макс: u64 },
+    /// То же самое для вещ64.
+    ПроверитьДиапазонВещ64{ мин: i64, макс: u64 },
+    /// Проверяет, что целое на вершине стека не является суррогатом
+    /// UTF-16, то есть не лежит в пределах [0xD800..0xDFFF]. Стек не
+    /// изменяется.
+    ПроверитьСимвол,
+    /// Отмечает, что на вершине стека лежит адрес памяти машины. Во время
+    /// исполнения ничего не делает, а во время вычисления констант
+    /// является ошибкой: адрес в памяти компилятора ничего не значит
+    /// для скомпилированной программы.
+    АдресПамяти,
+}
+
+/// Операции, которые проверяет ВидИнструкции::ПроверитьАрифметику.
+/// Деление также проверяет остаток.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Арифметика {
+    Сложение,
+    Вычитание,
+    Умножение,
+    Деление,
+    Отрицание,
 }
 
 #[derive(Clone)]
@@ -115,6 +184,11 @@ pub struct СкомпПеременная {
     pub имя: Лексема,
     pub тип: Тип,
     pub смещение: i32,
+    /// По смещению лежит не само значение, а его адрес.
+    pub ссылка: bool,
+    /// К переменной обращались по имени. Переменные, о неиспользовании
+    /// которых предупреждать не нужно, сразу считаются использованными.
+    pub использована: Cell<bool>,
 }
 
 #[derive(Default, Clone)]
@@ -127,6 +201,10 @@ pub struct ОбластьВидимости {
 #[derive(Default, Clone)]
 pub struct ЛокальныеИмена {
     pub стек_областей: Vec<ОбластьВидимости>,
+    /// Неиспользованные переменные закрытых областей. О них предупреждают
+    /// в конце процедуры, если она скомпилировалась без ошибок, ведь
+    /// утверждение с ошибкой могло не дойти до упоминания переменной.
+    pub неиспользованные: Vec<Лексема>,
 }
 
 impl ЛокальныеИмена {
@@ -137,6 +215,23 @@ impl ЛокальныеИмена {
     fn найти_константу_по_имени(&self, имя: &str) -> Option<&СкомпКонстанта> {
         self.стек_областей.iter().rev().find_map(|область| область.константы.get(имя))
     }
+
+    fn найти_переменную_в_текущей_области(&self, имя: &str) -> Option<&СкомпПеременная> {
+        self.стек_областей.last().and_then(|область| область.переменные.get(имя))
+    }
+
+    fn найти_константу_в_текущей_области(&self, имя: &str) -> Option<&СкомпКонстанта> {
+        self.стек_областей.last().and_then(|область| область.константы.get(имя))
+    }
+}
+
+/// Имена, видимые из компилируемого кода процедуры. Собраны вместе для
+/// процедур, которым кроме них нужно много собственных параметров.
+#[derive(Clone, Copy)]
+struct Окружение<'ы> {
+    имена: &'ы Имена,
+    текущая_процедура: &'ы СкомпПроцедура,
+    локальные_имена: &'ы ЛокальныеИмена,
 }
 
 #[derive(Debug, Clone)]
@@ -157,6 +252,16 @@ pub struct СкомпПроцедура {
     pub параметры: Vec<СкомпПеременная>,
     pub результат: Option<РезультатСкомпПроцедуры>,
     pub точка_входа: ТочкаВхода,
+    pub вариативная: bool,
+    /// Процедуру вызывали из кода программы.
+    pub использована: Cell<bool>,
+}
+
+/// Тип, объявленный через «тип Имя := ...;».
+#[derive(Debug, Clone)]
+pub struct СкомпТип {
+    pub имя: Лексема,
+    pub тип: Тип,
 }
 
 #[derive(Debug, Clone)]
@@ -166,6 +271,30 @@ pub struct СкомпКонстанта {
     pub значение_или_адрес: u64,
 }
 
+impl СкомпКонстанта {
+    /// Константа, значение которой не удалось вычислить.
+    fn отравленная(имя: &Лексема) -> СкомпКонстанта {
+        СкомпКонстанта {
+            имя: имя.clone(),
+            тип: Тип::Ошибка,
+            значение_или_адрес: 0,
+        }
+    }
+}
+
+/// Значение константы, вычисленное во время компиляции.
+struct ЗначениеКонстанты {
+    /// Байты значения вместе со всеми данными, на которые ссылаются
+    /// срезы внутри него.
+    байты: Vec<u8>,
+    /// Смещение самого значения внутри байтов.
+    смещение: usize,
+    /// Смещения указателей внутри байтов. Указатели записаны
+    /// относительно начала байтов.
+    указатели: Vec<usize>,
+    тип: Тип,
+}
+
 /// Промежуточное Представление
 #[derive(Default, Clone)]
 pub struct ПП {
@@ -173,12 +302,55 @@ pub struct ПП {
     pub иниц_данные: Vec<u8>,
     pub размер_неиниц_данных: usize,
     pub заплатки_неиниц_указателей: Vec<usize>,
+    /// Смещения внутри иниц_данные, по которым лежат указатели на
+    /// иниц_данные. Указатели записаны относительно начала данных и
+    /// превращаются в настоящие адреса при загрузке программы.
+    pub заплатки_иниц_указателей: Vec<usize>,
+    /// Смещения срезов уже размещённых строковых литералов.
     pub строки: HashMap<String, usize>,
     pub внешние_символы: HashMap<String, usize>,
     pub библиотеки: HashMap<String, usize>,
+    /// Не генерировать проверки времени исполнения (выход за границы и т.п.)
+    pub без_проверок: bool,
 }
 
 impl ПП {
+    /// Возвращает значение примитивной константы, либо адрес сложной
+    /// константы относительно начала данных.
+    fn разместить_константу(&mut self, значение: ЗначениеКонстанты) -> u64 {
+        let ЗначениеКонстанты{mut байты, смещение, указатели, тип} = значение;
+        if тип.примитивный() {
+            return u64::from_le_bytes(байты[смещение..смещение+8].try_into().unwrap())
+        }
+        let начало = self.иниц_данные.len();
+        for указатель in указатели {
+            let адрес = u64::from_le_bytes(байты[указатель..указатель+8].try_into().unwrap()) + начало as u64;
+            байты[указатель..указатель+8].copy_from_slice(&адрес.to_le_bytes());
+            self.заплатки_иниц_указателей.push(начало + указатель);
+        }
+        self.иниц_данные.extend(байты);
+        (начало + смещение) as u64
+    }
+
+    /// Размещает строковый литерал вместе с указывающим на него срезом
+    /// в инициализированных данных и возвращает смещение среза.
+    /// Одинаковые литералы размещаются лишь однажды.
+    fn интернировать_строку(&mut self, текст: &str) -> usize {
+        if let Some(срез) = self.строки.get(текст) {
+            return *срез;
+        }
+        let указатель = self.иниц_данные.len();
+        self.иниц_данные.extend(текст.as_bytes());
+        self.иниц_данные.push(0); // ЗАМЕТКА: нулл-терминатор на случае если мы захотим что-то передать в сишный код
+        let срез = self.иниц_данные.len();
+        self.иниц_данные.extend([0; 16]);
+        self.иниц_данные[срез+СРЕЗ_РАЗМЕР_СМЕЩЕНИЕ..срез+СРЕЗ_РАЗМЕР_СМЕЩЕНИЕ+8].copy_from_slice(&(текст.len() as u64).to_le_bytes());
+        self.иниц_данные[срез+СРЕЗ_АДРЕС_СМЕЩЕНИЕ..срез+СРЕЗ_АДРЕС_СМЕЩЕНИЕ+8].copy_from_slice(&(указатель as u64).to_le_bytes());
+        self.заплатки_иниц_указателей.push(срез + СРЕЗ_АДРЕС_СМЕЩЕНИЕ);
+        self.строки.insert(текст.to_string(), срез);
+        срез
+    }
+
     pub fn вывалить(&self, точка_входа: usize) {
         println!("Инструкции ({количество} {инструкций}):",
                  количество = self.код.len(),
@@ -234,17 +406,188 @@ impl ПП {
     }
 }
 
+#[derive(Clone, Copy, PartialEq)]
+pub enum Видимость {
+    Открытая,
+    Закрытая,
+}
+
+#[derive(Default, Clone)]
+pub struct Модуль {
+    /// Модули, включённые данным модулем через «вкл».
+    pub включённые: Vec<String>,
+    /// Имена, перечисленные в «экспорт». Если модуль ничего не
+    /// экспортирует явно, то открыты все его имена.
+    pub экспорт: Option<Vec<Лексема>>,
+    /// Видимость, явно заданная объявлениям через «открытая» и
+    /// «закрытая». Она важнее списка «экспорт».
+    pub видимость: HashMap<String, Видимость>,
+}
+
+impl Модуль {
+    /// Видимость имени модуля определяется так:
+    ///
+    /// 1. «закрытая» закрывает имя. Перечислить такое имя в «экспорт»
+    ///    нельзя, это ошибка (см. `проверить_экспорт`).
+    /// 2. «открытая» открывает имя, даже если модуль перечисляет в
+    ///    «экспорт» другие имена.
+    /// 3. Без явной видимости имя открыто, если оно перечислено в
+    ///    «экспорт» либо модуль не содержит ни одного «экспорт».
+    ///
+    /// Процедуры структур сюда не попадают: они открыты вместе со
+    /// структурой, если не объявлены закрытыми (см.
+    /// `процедура_структуры_закрыта`).
+    fn открывает(&self, имя: &str) -> bool {
+        match self.видимость.get(имя) {
+            Some(Видимость::Открытая) => true,
+            Some(Видимость::Закрытая) => false,
+            None => match &self.экспорт {
+                Some(экспорт) => экспорт.iter().any(|открытое| открытое.текст == имя),
+                None => true,
+            }
+        }
+    }
+}
+
+/// Все имена программы хранятся в общих таблицах под полными именами
+/// вида «модуль.имя». Имена главного файла программы приставки не имеют.
+/// Имена процедур структур имеют вид «модуль.Структура.процедура».
 #[derive(Default, Clone)]
 pub struct Имена {
     pub константы: HashMap<String, СкомпКонстанта>,
     pub процедуры: HashMap<String, СкомпПроцедура>,
     pub переменные: HashMap<String, СкомпПеременная>,
     pub структуры: HashMap<String, Структура>,
+    pub типы: HashMap<String, СкомпТип>,
+    pub модули: HashMap<String, Модуль>,
+    /// Модуль, который компилируется в данный момент. Пустая строка
+    /// означает главный файл программы.
+    pub текущий_модуль: String,
 }
 
 impl Имена {
+    fn полное_имя(&self, имя: &str) -> String {
+        if self.текущий_модуль.is_empty() {
+            имя.to_string()
+        } else {
+            format!("{}.{}", self.текущий_модуль, имя)
+        }
+    }
+
+    /// Ищет имя, видимое из текущего модуля: сначала среди его
+    /// собственных объявлений, затем среди открытых имён включённых им
+    /// модулей. Имена с точкой считаются уже полными.
+    fn найти<'ы, Т>(&self, таблица: &'ы HashMap<String, Т>, имя: &str) -> Option<&'ы Т> {
+        if имя.contains('.') {
+            return таблица.get(имя)
+        }
+        if let Some(значение) = таблица.get(&self.полное_имя(имя)) {
+            return Some(значение)
+        }
+        let модуль = self.модули.get(&self.текущий_модуль)?;
+        for включённый in &модуль.включённые {
+            if self.модули[включённый].открывает(имя) {
+                if let Some(значение) = таблица.get(&format!("{}.{}", включённый, имя)) {
+                    return Some(значение)
+                }
+            }
+        }
+        None
+    }
+
+    pub fn найти_процедуру(&self, имя: &str) -> Option<&СкомпПроцедура> {
+        self.найти(&self.процедуры, имя)
+    }
+
+    fn найти_константу(&self, имя: &str) -> Option<&СкомпКонстанта> {
+        self.найти(&self.константы, имя)
+    }
+
+    fn найти_переменную(&self, имя: &str) -> Option<&СкомпПеременная> {
+        self.найти(&self.переменные, имя)
+    }
+
+    /// Имя найденной структуры является её полным именем.
+    fn найти_структуру(&self, имя: &str) -> Option<&Структура> {
+        self.найти(&self.структуры, имя)
+    }
+
+    fn найти_тип(&self, имя: &str) -> Option<&СкомпТип> {
+        self.найти(&self.типы, имя)
+    }
+
+    /// Процедуры структур открыты вместе со структурой, если только они
+    /// не объявлены закрытыми.
+    fn процедура_структуры_закрыта(&self, структура: &str, процедура: &str) -> bool {
+        let (модуль, имя_структуры) = структура.rsplit_once('.').unwrap_or(("", структура));
+        if модуль == self.текущий_модуль {
+            return false
+        }
+        self.модули.get(модуль).is_some_and(|модуль| {
+            модуль.видимость.get(&format!("{}.{}", имя_структуры, процедура)) == Some(&Видимость::Закрытая)
+        })
+    }
+
+    /// Является ли имя модулем, включённым в текущий модуль.
+    fn включён_модуль(&self, имя: &str) -> bool {
+        self.модули
+            .get(&self.текущий_модуль)
+            .is_some_and(|модуль| модуль.включённые.iter().any(|включённый| включённый == имя))
+    }
+
+    /// Место определения видимого из текущего модуля имени любого вида.
+    fn найти_определение(&self, имя: &str) -> Option<&Лок> {
+        if let Some(процедура) = self.найти_процедуру(имя) {
+            return Some(&процедура.имя.лок)
+        }
+        if let Some(константа) = self.найти_константу(имя) {
+            return Some(&константа.имя.лок)
+        }
+        if let Some(переменная) = self.найти_переменную(имя) {
+            return Some(&переменная.имя.лок)
+        }
+        if let Some(структура) = self.найти_структуру(имя) {
+            return Some(&структура.имя.лок)
+        }
+        if let Some(тип) = self.найти_тип(имя) {
+            return Some(&тип.имя.лок)
+        }
+        None
+    }
+
+    /// Открытые имена модуля вместе с местом их определения.
+    fn открытые_имена(&self, модуль: &str) -> Vec<(String, Лок)> {
+        let приставка = format!("{}.", модуль);
+        let mut имена: Vec<(String, Лок)> = Vec::new();
+        let mut добавить = |полное_имя: &String, лок: &Лок| {
+            if let Some(имя) = полное_имя.strip_prefix(&приставка) {
+                // Процедуры структур открываются вместе со структурой.
+                if !имя.contains('.') && self.модули[модуль].открывает(имя) {
+                    имена.push((имя.to_string(), лок.clone()));
+                }
+            }
+        };
+        for (полное_имя, константа) in &self.константы {
+            добавить(полное_имя, &константа.имя.лок);
+        }
+        for (полное_имя, процедура) in &self.процедуры {
+            добавить(полное_имя, &процедура.имя.лок);
+        }
+        for (полное_имя, переменная) in &self.переменные {
+            добавить(полное_имя, &переменная.имя.лок);
+        }
+        for (полное_имя, структура) in &self.структуры {
+            добавить(полное_имя, &структура.имя.лок);
+        }
+        for (полное_имя, тип) in &self.типы {
+            добавить(полное_имя, &тип.имя.лок);
+        }
+        имена.sort_by(|а, б| а.0.cmp(&б.0));
+        имена
+    }
+
     fn верифицировать_переопределение_имени(&self, имя: &Лексема) -> Результат<()> {
-        if let Some(существующая_переменная) = self.переменные.get(&имя.текст) {
+        if let Some(существующая_переменная) = self.найти_переменную(&имя.текст) {
             диагностика!(&имя.лок, "ОШИБКА",
                          "уже существует переменная с именем «{имя}»",
                          имя = имя.текст);
@@ -253,7 +596,7 @@ impl Имена {
             return Err(())
         }
 
-        if let Some(существующая_процедура) = self.процедуры.get(&имя.текст) {
+        if let Some(существующая_процедура) = self.найти_процедуру(&имя.текст) {
             диагностика!(&имя.лок, "ОШИБКА",
                          "уже существует процедура с именем «{имя}»",
                          имя = имя.текст);
@@ -262,7 +605,7 @@ impl Имена {
             return Err(())
         }
 
-        if let Some(существующая_константа) = self.константы.get(&имя.текст) {
+        if let Some(существующая_константа) = self.найти_константу(&имя.текст) {
             диагностика!(&имя.лок, "ОШИБКА",
                          "уже существует константа с именем «{имя}»",
                          имя = имя.текст);
@@ -275,10 +618,21 @@ impl Имена {
     }
 }
 
+/// Переменная окружения со списком каталогов поиска модулей,
+/// разделённых так же, как в «PATH». Имя латиницей, так как командные
+/// оболочки не позволяют задавать переменные с кириллическими именами.
+pub const ПЕРЕМЕННАЯ_ПУТЕЙ_ПОИСКА: &str = "HUYA_PATH";
+
 #[derive(Default)]
 pub struct Программа {
     pub пп: ПП,
     pub имена: Имена,
+    /// Каталоги поиска модулей, заданные флагом «-путь». Просматриваются
+    /// раньше каталогов из переменной окружения и каталогов по умолчанию.
+    pub пути_поиска: Vec<PathBuf>,
+    /// «вкл» модулей, компиляция которых ещё не завершена. Нужны для
+    /// обнаружения циклических включений.
+    стек_включений: Vec<Лексема>,
 }
 
 fn скомпилировать_утверждение_вызова_функции_по_имени(пп: &mut ПП, имена: &Имена, текущая_процедура: &СкомпПроцедура, локальные_имена: &mut ЛокальныеИмена, имя: &Лексема, аргументы: &[Выражение]) -> Результат<()> {
@@ -287,19 +641,32 @@ fn скомпилировать_утверждение_вызова_функци
         "печать" => {
             let mut временный_размер = 0;
             for арг in аргументы {
-                let тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &арг, &mut временный_размер)?;
+                // Отдельные типы печатаются так же, как их основа.
+                let тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &арг, &mut временный_размер)?.основа().clone();
                 match тип {
-                    Тип::Нат64 => {
-                        let процедура = достать_и_проверить_процедуру_печати(имена, "печать_нат", &тип, &арг.лок())?;
-                        вызвать_процедуру(арг.лок().clone(), имена, процедура, &mut пп.код, &mut временный_размер)?;
+                    // Узкие целые лежат на стеке уже расширенными до 64 бит.
+                    Тип::Нат8 | Тип::Нат16 | Тип::Нат32 | Тип::Нат64 => {
+                        let процедура = достать_и_проверить_процедуру_печати(имена, "печать_нат", &Тип::Нат64, &арг.лок())?;
+                        вызвать_процедуру(арг.лок().clone(), имена, текущая_процедура, процедура, &[], &mut пп.код, &mut временный_размер)?;
+                    }
+                    Тип::Цел8 | Тип::Цел16 | Тип::Цел32 | Тип::Цел64 => {
+                        let процедура = достать_и_проверить_процедуру_печати(имена, "печать_цел", &Тип::Цел64, &арг.лок())?;
+                        вызвать_процедуру(арг.лок().clone(), имена, текущая_процедура, процедура, &[], &mut пп.код, &mut временный_размер)?;
                     }
-                    Тип::Цел64 => {
-                        let процедура = достать_и_проверить_процедуру_печати(имена, "печать_цел", &тип, &арг.лок())?;
-                        вызвать_процедуру(арг.лок().clone(), имена, процедура, &mut пп.код, &mut временный_размер)?;
+                    Тип::Вещ32 | Тип::Вещ64 => {
+                        if тип == Тип::Вещ32 {
+                            пп.код.push(Инструкция{ вид: ВидИнструкции::КонвертВещ32Вещ64, лок: арг.лок().clone() });
+                        }
+                        let процедура = достать_и_проверить_процедуру_печати(имена, "печать_вещ", &Тип::Вещ64, &арг.лок())?;
+                        вызвать_процедуру(арг.лок().clone(), имена, текущая_процедура, процедура, &[], &mut пп.код, &mut временный_размер)?;
                     }
                     Тип::Лог => {
                         let процедура = достать_и_проверить_процедуру_печати(имена, "печать_лог", &тип, &арг.лок())?;
-                        вызвать_процедуру(арг.лок().clone(), имена, процедура, &mut пп.код, &mut временный_размер)?;
+                        вызвать_процедуру(арг.лок().clone(), имена, текущая_процедура, процедура, &[], &mut пп.код, &mut временный_размер)?;
+                    }
+                    Тип::Символ => {
+                        let процедура = достать_и_проверить_процедуру_печати(имена, "печать_символ", &тип, &арг.лок())?;
+                        вызвать_процедуру(арг.лок().clone(), имена, текущая_процедура, процедура, &[], &mut пп.код, &mut временный_размер)?;
                     }
                     Тип::Срез{тип_элемента} => {
                         if *тип_элемента != Тип::Нат8 {
@@ -321,29 +688,16 @@ fn скомпилировать_утверждение_вызова_функци
             Ok(())
         },
         _ => {
-            if let Some(вызываемая_процедура) = имена.процедуры.get(&имя.текст) {
+            if let Some(вызываемая_процедура) = имена.найти_процедуру(&имя.текст) {
                 if let Some(результат) = &вызываемая_процедура.результат {
                     диагностика!(&имя.лок, "ОШИБКА", "Данная процедура возвращает результат типа «{тип}». Результаты процедур нельзя игнорировать.", тип = результат.тип.текст());
                     диагностика!(&вызываемая_процедура.имя.лок, "ИНФО", "Процедура определена тут.");
                     return Err(())
                 }
 
-                let количество_аргументов = аргументы.len();
-                let количество_параметров = вызываемая_процедура.параметры.len();
-                if количество_аргументов != количество_параметров {
-                    диагностика!(&имя.лок, "ОШИБКА",
-                                 "Неверное количество аргументов вызова процедуры. Процедура принимает {количество_параметров} {параметров}, но в данном вызове предоставлено лишь {количество_аргументов} {аргументов}.",
-                                 параметров = ЧИСУЩ_ПАРАМЕТР.текст(количество_параметров),
-                                 аргументов = ЧИСУЩ_АРГУМЕНТ.текст(количество_аргументов));
-                    return Err(());
-                }
-
                 let mut временный_размер = 0;
-                for (параметр, аргумент) in вызываемая_процедура.параметры.iter().zip(аргументы.iter()).rev() {
-                    let тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, аргумент, &mut временный_размер)?;
-                    проверить_типы(&аргумент.лок(), &параметр.тип, &тип)?;
-                }
-                вызвать_процедуру(имя.лок.clone(), имена, вызываемая_процедура, &mut пп.код, &mut временный_размер)?;
+                let вариативные_аргументы = скомпилировать_аргументы_вызова(пп, Окружение{имена, текущая_процедура, локальные_имена}, имя, вызываемая_процедура, аргументы, &mut временный_размер)?;
+                вызвать_процедуру(имя.лок.clone(), имена, текущая_процедура, вызываемая_процедура, &вариативные_аргументы, &mut пп.код, &mut временный_размер)?;
                 if временный_размер > 0 {
                     пп.код.push(Инструкция{ вид: ВидИнструкции::ОсвободитьСоСтека(временный_размер as u64), лок: имя.лок.clone() });
                 }
@@ -375,6 +729,9 @@ fn скомпилировать_выражение_вызова_функции_
                     return Err(())
                 }
                 if let Some((переменная, локация)) = найти_переменную_по_имени(&имя.текст, имена, текущая_процедура, локальные_имена) {
+                    if переменная.тип.отравлен() {
+                        return Err(())
+                    }
                     if let Тип::Массив{размер, тип_элемента} = &переменная.тип {
                         if **тип_элемента != Тип::Нат8 {
                             диагностика!(&имя.лок, "ОШИБКА", "Ввод возможет только в массива элементов «{тип_элемента}»", тип_элемента = тип_элемента.текст());
@@ -417,85 +774,110 @@ fn скомпилировать_выражение_вызова_функции_
                 return Err(());
             };
 
-            if let Выражение::Идент(имя) = массив {
-                if let Some((переменная, локация)) = найти_переменную_по_имени(&имя.текст, имена, текущая_процедура, локальные_имена) {
-                    match &переменная.тип {
-                        Тип::Массив{размер: _, тип_элемента} => {
-                            let тип_среза = Тип::Срез {
-                                тип_элемента: тип_элемента.clone()
-                            };
-
-                            let тип_размера_среза = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &размер_среза, временный_размер)?;
-                            проверить_типы(&размер_среза.лок(), &Тип::Нат64, &тип_размера_среза)?;
-                            локация.протолкнуть_смещение(пп, &имя.лок, переменная.смещение);
+            let (тип, локация, смещение) = match массив {
+                Выражение::Идент(имя) => {
+                    let Some(значение) = найти_значение_в_памяти(&имя.текст, имена, текущая_процедура, локальные_имена) else {
+                        диагностика!(&имя.лок, "ОШИБКА",
+                                     "не существует переменных с имением «{имя}»",
+                                     имя = &имя.текст);
+                        return Err(());
+                    };
+                    значение
+                }
+                Выражение::Строка(строка) => {
+                    let тип = Тип::Срез { тип_элемента: Box::new(Тип::Нат8) };
+                    (тип, Локация::Данные, пп.интернировать_строку(&строка.текст) as i32)
+                }
+                _ => {
+                    // СДЕЛАТЬ: возможно данные аргумент должен быть передан по ссылке.
+                    // Но мы пока не имеем подобного механизма.
+                    диагностика!(&имя.лок, "ОШИБКА", "Срез требует имя массива в качестве первого аргумента");
+                    return Err(());
+                }
+            };
+            if тип.отравлен() {
+                return Err(())
+            }
+            let лок = массив.лок();
 
-                            let тип_начала = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &начало_среза, временный_размер)?;
-                            проверить_типы(&размер_среза.лок(), &Тип::Нат64, &тип_начала)?;
+            match &тип {
+                Тип::Массив{размер, тип_элемента} => {
+                    let тип_среза = Тип::Срез {
+                        тип_элемента: тип_элемента.clone()
+                    };
 
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(тип_элемента.размер(&имена.структуры)), лок: имя.лок.clone() });
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::НатУмножение, лок: имя.лок.clone() });
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: имя.лок.clone() });
+                    let тип_размера_среза = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &размер_среза, временный_размер)?;
+                    проверить_типы(&размер_среза.лок(), &Тип::Нат64, &тип_размера_среза)?;
+                    локация.протолкнуть_смещение(пп, лок, смещение);
 
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::ВыделитьНаСтеке(тип_среза.размер(&имена.структуры) as u64), лок: имя.лок.clone() });
-                            *временный_размер += тип_среза.размер(&имена.структуры);
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::ВершинаСтека(СРЕЗ_АДРЕС_СМЕЩЕНИЕ as i32), лок: имя.лок.clone() });
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::Записать64, лок: имя.лок.clone() });
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::ВершинаСтека(СРЕЗ_РАЗМЕР_СМЕЩЕНИЕ as i32), лок: имя.лок.clone() });
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::Записать64, лок: имя.лок.clone() });
+                    let тип_начала = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &начало_среза, временный_размер)?;
+                    проверить_типы(&размер_среза.лок(), &Тип::Нат64, &тип_начала)?;
 
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::ВершинаСтека(0), лок: имя.лок.clone() });
+                    if !пп.без_проверок {
+                        пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(*размер), лок: лок.clone() });
+                        пп.код.push(Инструкция{ вид: ВидИнструкции::ПроверитьСрез, лок: лок.clone() });
+                    }
 
-                            return Ok(тип_среза);
-                        }
-                        Тип::Срез{тип_элемента} => {
-                            let тип_среза = Тип::Срез {
-                                тип_элемента: тип_элемента.clone()
-                            };
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(тип_элемента.размер(&имена.структуры)), лок: лок.clone() });
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::НатУмножение, лок: лок.clone() });
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: лок.clone() });
 
-                            let тип_размера_среза = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &размер_среза, временный_размер)?;
-                            проверить_типы(&размер_среза.лок(), &Тип::Нат64, &тип_размера_среза)?;
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::ВыделитьНаСтеке(тип_среза.размер(&имена.структуры) as u64), лок: лок.clone() });
+                    *временный_размер += тип_среза.размер(&имена.структуры);
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::ВершинаСтека(СРЕЗ_АДРЕС_СМЕЩЕНИЕ as i32), лок: лок.clone() });
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Записать64, лок: лок.clone() });
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::ВершинаСтека(СРЕЗ_РАЗМЕР_СМЕЩЕНИЕ as i32), лок: лок.clone() });
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Записать64, лок: лок.clone() });
 
-                            локация.протолкнуть_смещение(пп, &имя.лок, переменная.смещение);
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(СРЕЗ_АДРЕС_СМЕЩЕНИЕ), лок: имя.лок.clone()});
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: имя.лок.clone()});
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::Прочитать64, лок: имя.лок.clone()});
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::ВершинаСтека(0), лок: лок.clone() });
 
-                            let тип_начала = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &начало_среза, временный_размер)?;
-                            проверить_типы(&размер_среза.лок(), &Тип::Нат64, &тип_начала)?;
+                    return Ok(тип_среза);
+                }
+                Тип::Срез{тип_элемента} => {
+                    let тип_среза = Тип::Срез {
+                        тип_элемента: тип_элемента.clone()
+                    };
+
+                    let тип_размера_среза = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &размер_среза, временный_размер)?;
+                    проверить_типы(&размер_среза.лок(), &Тип::Нат64, &тип_размера_среза)?;
+
+                    локация.протолкнуть_смещение(пп, лок, смещение);
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(СРЕЗ_АДРЕС_СМЕЩЕНИЕ), лок: лок.clone()});
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: лок.clone()});
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Прочитать64, лок: лок.clone()});
+
+                    let тип_начала = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &начало_среза, временный_размер)?;
+                    проверить_типы(&размер_среза.лок(), &Тип::Нат64, &тип_начала)?;
+
+                    if !пп.без_проверок {
+                        локация.протолкнуть_смещение(пп, лок, смещение);
+                        пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(СРЕЗ_РАЗМЕР_СМЕЩЕНИЕ), лок: лок.clone()});
+                        пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: лок.clone()});
+                        пп.код.push(Инструкция{ вид: ВидИнструкции::Прочитать64, лок: лок.clone()});
+                        пп.код.push(Инструкция{ вид: ВидИнструкции::ПроверитьСрез, лок: лок.clone() });
+                    }
 
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(тип_элемента.размер(&имена.структуры)), лок: имя.лок.clone() });
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::НатУмножение, лок: имя.лок.clone() });
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: имя.лок.clone() });
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(тип_элемента.размер(&имена.структуры)), лок: лок.clone() });
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::НатУмножение, лок: лок.clone() });
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: лок.clone() });
 
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::ВыделитьНаСтеке(тип_среза.размер(&имена.структуры) as u64), лок: имя.лок.clone() });
-                            *временный_размер += тип_среза.размер(&имена.структуры);
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::ВершинаСтека(СРЕЗ_АДРЕС_СМЕЩЕНИЕ as i32), лок: имя.лок.clone() });
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::Записать64, лок: имя.лок.clone() });
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::ВершинаСтека(СРЕЗ_РАЗМЕР_СМЕЩЕНИЕ as i32), лок: имя.лок.clone() });
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::Записать64, лок: имя.лок.clone() });
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::ВыделитьНаСтеке(тип_среза.размер(&имена.структуры) as u64), лок: лок.clone() });
+                    *временный_размер += тип_среза.размер(&имена.структуры);
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::ВершинаСтека(СРЕЗ_АДРЕС_СМЕЩЕНИЕ as i32), лок: лок.clone() });
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Записать64, лок: лок.clone() });
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::ВершинаСтека(СРЕЗ_РАЗМЕР_СМЕЩЕНИЕ as i32), лок: лок.clone() });
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Записать64, лок: лок.clone() });
 
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::ВершинаСтека(0), лок: имя.лок.clone() });
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::ВершинаСтека(0), лок: лок.clone() });
 
-                            return Ok(тип_среза);
-                        }
-                        _ => {
-                            диагностика!(&имя.лок, "ОШИБКА",
-                                         "Переменная «{имя}» не является массивом. Срезы возможны только для массивов либо других срезов.",
-                                         имя = &имя.текст);
-                            return Err(());
-                        }
-                    }
+                    return Ok(тип_среза);
+                }
+                _ => {
+                    диагностика!(лок, "ОШИБКА",
+                                 "Значение типа «{тип}» не является массивом. Срезы возможны только для массивов либо других срезов.",
+                                 тип = тип.текст());
+                    Err(())
                 }
-
-                диагностика!(&имя.лок, "ОШИБКА",
-                             "не существует переменных с имением «{имя}»",
-                             имя = &имя.текст);
-                Err(())
-            } else {
-                // СДЕЛАТЬ: возможно данные аргумент должен быть передан по ссылке.
-                // Но мы пока не имеем подобного механизма.
-                диагностика!(&имя.лок, "ОШИБКА", "Срез требует имя массива в качестве первого аргумента");
-                Err(())
             }
         }
         "сисвызов" => {
@@ -556,6 +938,7 @@ fn скомпилировать_выражение_вызова_функции_
                     пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(СРЕЗ_АДРЕС_СМЕЩЕНИЕ), лок: имя.лок.clone()});
                     пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: имя.лок.clone()});
                     пп.код.push(Инструкция{ вид: ВидИнструкции::Прочитать64, лок: имя.лок.clone()});
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::АдресПамяти, лок: имя.лок.clone()});
                     Ok(Тип::Нат64)
                 }
                 _ => {
@@ -566,24 +949,9 @@ fn скомпилировать_выражение_вызова_функции_
             }
         }
         _ => {
-            if let Some(процедура) = имена.процедуры.get(&имя.текст) {
-                // СДЕЛАТЬ: дупликация кода между компиляцией вызова процедур как выражений и как утверждений.
-
-                let количество_аргументов = аргументы.len();
-                let количество_параметров = процедура.параметры.len();
-                if количество_аргументов != количество_параметров {
-                    диагностика!(&имя.лок, "ОШИБКА",
-                                 "Неверное количество аргументов вызова процедуры. Процедура принимает {количество_параметров} {параметров}, но в данном вызове предоставлено лишь {количество_аргументов} {аргументов}.",
-                                 параметров = ЧИСУЩ_ПАРАМЕТР.текст(количество_параметров),
-                                 аргументов = ЧИСУЩ_АРГУМЕНТ.текст(количество_аргументов));
-                    return Err(());
-                }
-
-                for (параметр, аргумент) in процедура.параметры.iter().zip(аргументы.iter()).rev() {
-                    let тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, аргумент, временный_размер)?;
-                    проверить_типы(&аргумент.лок(), &параметр.тип, &тип)?;
-                }
-                вызвать_процедуру(имя.лок.clone(), имена, &процедура, &mut пп.код, временный_размер)?;
+            if let Some(процедура) = имена.найти_процедуру(&имя.текст) {
+                let вариативные_аргументы = скомпилировать_аргументы_вызова(пп, Окружение{имена, текущая_процедура, локальные_имена}, имя, процедура, аргументы, временный_размер)?;
+                вызвать_процедуру(имя.лок.clone(), имена, текущая_процедура, &процедура, &вариативные_аргументы, &mut пп.код, временный_размер)?;
 
                 if let Some(результат) = &процедура.результат {
                     return Ok(результат.тип.clone())
@@ -593,39 +961,12 @@ fn скомпилировать_выражение_вызова_функции_
                 }
             }
 
-            if let Some((переменная, локация)) = найти_переменную_по_имени(&имя.текст, имена, текущая_процедура, локальные_имена) {
-                match &переменная.тип {
-                    Тип::Массив{тип_элемента, ..} => {
-                        let [индекс] = &аргументы[..] else {
-                            диагностика!(&имя.лок, "ОШИБКА", "Некорректная размерность массива. Ожидался 1 индекс, но повстречалось {ранк}", ранк = аргументы.len());
-                            return Err(());
-                        };
-
-                        локация.протолкнуть_смещение(пп, &имя.лок, переменная.смещение);
-                        индексировать_массив(тип_элемента, пп, имена, текущая_процедура, локальные_имена, индекс, временный_размер)?;
-                        if тип_элемента.примитивный() {
-                            прочитать_примитивный_тип(&имя.лок, &mut пп.код, &имена.структуры, &тип_элемента)?;
-                        }
-                        return Ok(*тип_элемента.clone())
-                    }
-                    Тип::Срез{тип_элемента} => {
-                        let [индекс] = &аргументы[..] else {
-                            диагностика!(&имя.лок, "ОШИБКА", "Некорректная размерность среза. Ожидался 1 индекс, но повстречалось {ранк}", ранк = аргументы.len());
-                            return Err(());
-                        };
-                        локация.протолкнуть_смещение(пп, &имя.лок, переменная.смещение + СРЕЗ_АДРЕС_СМЕЩЕНИЕ as i32);
-                        прочитать_примитивный_тип(&имя.лок, &mut пп.код, &имена.структуры, &Тип::Нат64)?;
-                        индексировать_массив(тип_элемента, пп, имена, текущая_процедура, локальные_имена, индекс, временный_размер)?;
-                        if тип_элемента.примитивный() {
-                            прочитать_примитивный_тип(&имя.лок, &mut пп.код, &имена.структуры, &тип_элемента)?;
-                        }
-                        return Ok(*тип_элемента.clone())
-                    }
-                    _ => {
-                        диагностика!(&имя.лок, "ОШИБКА", "Невозможно индексировать тип «{тип}»", тип = переменная.тип.текст());
-                        return Err(())
-                    }
+            if let Some((тип, локация, смещение)) = найти_значение_в_памяти(&имя.текст, имена, текущая_процедура, локальные_имена) {
+                if тип.отравлен() {
+                    return Err(())
                 }
+                локация.протолкнуть_смещение(пп, &имя.лок, смещение);
+                return проиндексировать_значение(пп, Окружение{имена, текущая_процедура, локальные_имена}, &имя.лок, &тип, аргументы, временный_размер);
             }
 
             println!("{текущая_процедура:?}");
@@ -635,7 +976,17 @@ fn скомпилировать_выражение_вызова_функции_
     }
 }
 
+/// Значение отравленного типа (см. «Тип::Ошибка») не компилируется, но об
+/// этом не сообщается: ошибка, из-за которой тип отравлен, уже сообщена.
 fn скомпилировать_выражение(пп: &mut ПП, имена: &Имена, текущая_процедура: &СкомпПроцедура, локальные_имена: &ЛокальныеИмена, выражение: &Выражение, временный_размер: &mut usize) -> Результат<Тип> {
+    let тип = скомпилировать_выражение_как_есть(пп, имена, текущая_процедура, локальные_имена, выражение, временный_размер)?;
+    if тип.отравлен() {
+        return Err(())
+    }
+    Ok(тип)
+}
+
+fn скомпилировать_выражение_как_есть(пп: &mut ПП, имена: &Имена, текущая_процедура: &СкомпПроцедура, локальные_имена: &ЛокальныеИмена, выражение: &Выражение, временный_размер: &mut usize) -> Результат<Тип> {
     match выражение {
         Выражение::Лог(лексема, значение) => {
             if *значение {
@@ -645,52 +996,49 @@ fn скомпилировать_выражение(пп: &mut ПП, имена:
             }
             Ok(Тип::Лог)
         }
-        Выражение::ЦелЧисло(лексема, число) => {
+        Выражение::ЦелЧисло(лексема, число, разрядность) => {
             пп.код.push(Инструкция{ вид: ВидИнструкции::Целое(*число), лок: лексема.лок.clone() });
-            Ok(Тип::Цел64)
+            Ok(Тип::целое_разрядности(true, *разрядность))
         },
-        Выражение::НатЧисло(лексема, число) => {
+        Выражение::НатЧисло(лексема, число, разрядность) => {
             пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(*число as usize), лок: лексема.лок.clone() });
-            Ok(Тип::Нат64)
+            Ok(Тип::целое_разрядности(false, *разрядность))
         },
-        Выражение::ВещЧисло(лексема, число) => {
-            let число = u32::from_le_bytes(число.to_le_bytes()) as usize;
+        Выражение::ВещЧисло(лексема, число, 64) => {
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(число.to_bits() as usize), лок: лексема.лок.clone() });
+            Ok(Тип::Вещ64)
+        },
+        Выражение::ВещЧисло(лексема, число, _) => {
+            let число = (*число as f32).to_bits() as usize;
             пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(число), лок: лексема.лок.clone() });
             Ok(Тип::Вещ32)
         },
-        Выражение::Строка(строка) => {
-            let (указатель, длинна) = if let Some(указатель) = пп.строки.get(&строка.текст) {
-                (*указатель, строка.текст.len())
+        Выражение::Символ(лексема, символ) => {
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(*символ as usize), лок: лексема.лок.clone() });
+            if символ.is_ascii() {
+                Ok(Тип::Нат8)
             } else {
-                let указатель = пп.иниц_данные.len();
-                let длинна = строка.текст.len();
-                пп.иниц_данные.extend(строка.текст.as_bytes());
-                пп.иниц_данные.push(0); // ЗАМЕТКА: нулл-терминатор на случае если мы захотим что-то передать в сишный код
-                пп.строки.insert(строка.текст.clone(), указатель);
-                (указатель, длинна)
-            };
+                Ok(Тип::Символ)
+            }
+        }
+        Выражение::Строка(строка) => {
+            let срез = пп.интернировать_строку(&строка.текст);
+            Локация::Данные.протолкнуть_смещение(пп, &строка.лок, срез as i32);
+            Ok(Тип::Срез { тип_элемента: Box::new(Тип::Нат8) })
+        }
+        Выражение::ИндексСтроки{строка, аргументы} => {
+            let срез = пп.интернировать_строку(&строка.текст);
+            Локация::Данные.протолкнуть_смещение(пп, &строка.лок, срез as i32);
             let тип = Тип::Срез { тип_элемента: Box::new(Тип::Нат8) };
-            пп.код.push(Инструкция{ вид: ВидИнструкции::ВыделитьНаСтеке(тип.размер(&имена.структуры) as u64), лок: строка.лок.clone() });
-            *временный_размер += тип.размер(&имена.структуры);
-
-            // СДЕЛАТЬ: несколько много инструкций для компиляции простого строкового литерала
-            // Может быть имеет смысл ввести сокращения для инструкций? Что-то типа Записать64НаСтеке(i32).
-
-            пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(длинна), лок: строка.лок.clone() });
-            пп.код.push(Инструкция{ вид: ВидИнструкции::ВершинаСтека(СРЕЗ_РАЗМЕР_СМЕЩЕНИЕ as i32), лок: строка.лок.clone() });
-            пп.код.push(Инструкция{ вид: ВидИнструкции::Записать64, лок: строка.лок.clone() });
-
-            пп.код.push(Инструкция{ вид: ВидИнструкции::ГлобальныеДанные(указатель as i32), лок: строка.лок.clone() });
-            пп.код.push(Инструкция{ вид: ВидИнструкции::ВершинаСтека(СРЕЗ_АДРЕС_СМЕЩЕНИЕ as i32), лок: строка.лок.clone() });
-            пп.код.push(Инструкция{ вид: ВидИнструкции::Записать64, лок: строка.лок.clone() });
-
-            пп.код.push(Инструкция{ вид: ВидИнструкции::ВершинаСтека(0), лок: строка.лок.clone() });
-
-            Ok(тип)
+            проиндексировать_значение(пп, Окружение{имена, текущая_процедура, локальные_имена}, &аргументы.ключ.лок, &тип, &аргументы.выражения, временный_размер)
         }
         Выражение::Идент(лексема) => {
             if let Some(константа) = найти_константу_по_имени(&лексема.текст, имена, Some(локальные_имена)) {
-                пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(константа.значение_или_адрес as usize), лок: лексема.лок.clone() });
+                if константа.тип.примитивный() {
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(константа.значение_или_адрес as usize), лок: лексема.лок.clone() });
+                } else {
+                    Локация::Данные.протолкнуть_смещение(пп, &лексема.лок, константа.значение_или_адрес as i32);
+                }
                 return Ok(константа.тип.clone());
             }
             if let Some((переменная, локация)) = найти_переменную_по_имени(&лексема.текст, имена, текущая_процедура, локальные_имена) {
@@ -709,67 +1057,49 @@ fn скомпилировать_выражение(пп: &mut ПП, имена:
         Выражение::Биноп {ключ, вид, левое, правое} => {
             match вид {
                 ВидБинопа::Поле => {
+                    if let Some(выражение) = квалифицировать_имя(имена, Some((текущая_процедура, локальные_имена)), &левое, &правое)? {
+                        return скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &выражение, временный_размер);
+                    }
+                    if let Выражение::ЦепочкаВызовов{имя: имя_процедуры, цепочка_аргументов} = &**правое {
+                        let mut аргументы_цепочки = цепочка_аргументов.iter();
+                        let аргументы = &аргументы_цепочки.next().expect("Цепочка аргументов вызова не может быть пустой").выражения;
+                        if let Some((имя_процедуры, аргументы)) = разрешить_вызов_процедуры_структуры(пп, имена, текущая_процедура, локальные_имена, &левое, имя_процедуры, аргументы)? {
+                            let mut тип = скомпилировать_выражение_вызова_функции_по_имени(пп, имена, текущая_процедура, локальные_имена, &имя_процедуры, &аргументы, временный_размер)?;
+                            for аргументы in аргументы_цепочки {
+                                тип = проиндексировать_значение(пп, Окружение{имена, текущая_процедура, локальные_имена}, &аргументы.ключ.лок, &тип, &аргументы.выражения, временный_размер)?;
+                            }
+                            return Ok(тип)
+                        }
+                    }
                     let левый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &левое, временный_размер)?;
                     match &**правое {
                         Выражение::Идент(имя_поля) => {
-                            match &левый_тип {
-                                Тип::Структура(имя_структуры) => {
-                                    let структура = имена
-                                        .структуры
-                                        .get(имя_структуры)
-                                        .expect("Существование структуры должно быть уже проверено на этапе компиляции типа");
-                                    if let Some(поле) = структура.поля.get(&имя_поля.текст) {
-                                        пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(поле.смещение), лок: ключ.лок.clone() });
-                                        пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: ключ.лок.clone() });
-                                        if поле.тип.примитивный() {
-                                            прочитать_примитивный_тип(&ключ.лок, &mut пп.код, &имена.структуры, &поле.тип)?;
-                                        }
-                                        Ok(поле.тип.clone())
-                                    } else {
-                                        диагностика!(&ключ.лок, "ОШИБКА", "У структуры «{тип}» отстутствует поле «{поле}»", тип = левый_тип.текст(), поле = имя_поля.текст);
-                                        Err(())
-                                    }
-                                }
-                                _ => {
-                                    диагностика!(&ключ.лок, "ОШИБКА", "У типа «{тип}» отстутствует поле «{поле}»", тип = левый_тип.текст(), поле = имя_поля.текст);
-                                    Err(())
-                                }
+                            let поле = найти_поле(имена, ключ, &левый_тип, имя_поля)?;
+                            пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(поле.смещение), лок: ключ.лок.clone() });
+                            пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: ключ.лок.clone() });
+                            if поле.тип.примитивный() {
+                                прочитать_примитивный_тип(&ключ.лок, &mut пп.код, &имена.структуры, &поле.тип)?;
                             }
+                            Ok(поле.тип.clone())
                         }
                         Выражение::ЦепочкаВызовов{имя: имя_поля, цепочка_аргументов} => {
-                            let mut тип_вызываемого = match &левый_тип {
-                                Тип::Структура(имя_структуры) => {
-                                    let структура = имена
-                                        .структуры
-                                        .get(имя_структуры)
-                                        .expect("Существование структуры должно быть уже проверено на этапе компиляции типа");
-                                    if let Some(поле) = структура.поля.get(&имя_поля.текст) {
-                                        пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(поле.смещение), лок: ключ.лок.clone() });
-                                        пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: ключ.лок.clone() });
-                                        if поле.тип.примитивный() {
-                                            прочитать_примитивный_тип(&ключ.лок, &mut пп.код, &имена.структуры, &поле.тип)?;
-                                        }
-                                        поле.тип.clone()
-                                    } else {
-                                        диагностика!(&ключ.лок, "ОШИБКА", "У структуры «{тип}» отстутствует поле «{поле}»", тип = левый_тип.текст(), поле = имя_поля.текст);
-                                        return Err(())
-                                    }
-                                }
-                                _ => {
-                                    диагностика!(&ключ.лок, "ОШИБКА", "У типа «{тип}» отстутствует поле «{поле}»", тип = левый_тип.текст(), поле = имя_поля.текст);
-                                    return Err(())
-                                }
-                            };
+                            let поле = найти_поле(имена, ключ, &левый_тип, имя_поля)?;
+                            пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(поле.смещение), лок: ключ.лок.clone() });
+                            пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: ключ.лок.clone() });
+                            if поле.тип.примитивный() {
+                                прочитать_примитивный_тип(&ключ.лок, &mut пп.код, &имена.структуры, &поле.тип)?;
+                            }
+                            let mut тип_вызываемого = поле.тип.clone();
 
                             for аргументы in цепочка_аргументов {
                                 match &тип_вызываемого {
-                                    Тип::Массив{тип_элемента, ..} => {
+                                    Тип::Массив{размер, тип_элемента} => {
                                         let [индекс] = &аргументы.выражения[..] else {
                                             диагностика!(&аргументы.ключ.лок, "ОШИБКА", "Некорректная размерность массива. Ожидался 1 индекс, но повстречалось {ранк}", ранк = аргументы.выражения.len());
                                             return Err(());
                                         };
 
-                                        индексировать_массив(тип_элемента, пп, имена, текущая_процедура, локальные_имена, индекс, временный_размер)?;
+                                        индексировать_массив(тип_элемента, *размер, пп, Окружение{имена, текущая_процедура, локальные_имена}, индекс, временный_размер)?;
                                         if тип_элемента.примитивный() {
                                             прочитать_примитивный_тип(&аргументы.ключ.лок, &mut пп.код, &имена.структуры, &тип_элемента)?;
                                         }
@@ -782,11 +1112,7 @@ fn скомпилировать_выражение(пп: &mut ПП, имена:
                                             return Err(());
                                         };
 
-                                        пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(СРЕЗ_АДРЕС_СМЕЩЕНИЕ), лок: аргументы.ключ.лок.clone()});
-                                        пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: аргументы.ключ.лок.clone()});
-                                        пп.код.push(Инструкция{ вид: ВидИнструкции::Прочитать64, лок: аргументы.ключ.лок.clone()});
-
-                                        индексировать_массив(тип_элемента, пп, имена, текущая_процедура, локальные_имена, индекс, временный_размер)?;
+                                        индексировать_срез(тип_элемента, &аргументы.ключ.лок, пп, Окружение{имена, текущая_процедура, локальные_имена}, индекс, временный_размер)?;
                                         if тип_элемента.примитивный() {
                                             прочитать_примитивный_тип(&аргументы.ключ.лок, &mut пп.код, &имена.структуры, &тип_элемента)?;
                                         }
@@ -812,7 +1138,7 @@ fn скомпилировать_выражение(пп: &mut ПП, имена:
                     let правый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &правое, временный_размер)?;
                     проверить_типы(правое.лок(), &левый_тип, &правый_тип)?;
                     if левый_тип.примитивный() {
-                        пп.код.push(Инструкция{ вид: ВидИнструкции::НатРавно, лок: ключ.лок.clone() });
+                        пп.код.push(Инструкция{ вид: инструкция_равенства(&левый_тип), лок: ключ.лок.clone() });
                     } else {
                         пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(левый_тип.размер(&имена.структуры)), лок: ключ.лок.clone() });
                         пп.код.push(Инструкция{ вид: ВидИнструкции::ПамятьРавно, лок: ключ.лок.clone() });
@@ -825,7 +1151,7 @@ fn скомпилировать_выражение(пп: &mut ПП, имена:
                     let правый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &правое, временный_размер)?;
                     проверить_типы(правое.лок(), &левый_тип, &правый_тип)?;
                     if левый_тип.примитивный() {
-                        пп.код.push(Инструкция{ вид: ВидИнструкции::НатРавно, лок: ключ.лок.clone() });
+                        пп.код.push(Инструкция{ вид: инструкция_равенства(&левый_тип), лок: ключ.лок.clone() });
                     } else {
                         пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(левый_тип.размер(&имена.структуры)), лок: ключ.лок.clone() });
                         пп.код.push(Инструкция{ вид: ВидИнструкции::ПамятьРавно, лок: ключ.лок.clone() });
@@ -836,16 +1162,19 @@ fn скомпилировать_выражение(пп: &mut ПП, имена:
                     let левый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &левое, временный_размер)?;
                     let правый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &правое, временный_размер)?;
                     проверить_типы(правое.лок(), &левый_тип, &правый_тип)?;
-                    match левый_тип {
-                        Тип::Нат64 | Тип::Нат8 => {
+                    match левый_тип.основа() {
+                        Тип::Нат8 | Тип::Нат16 | Тип::Нат32 | Тип::Нат64 | Тип::Символ => {
                             пп.код.push(Инструкция{ вид: ВидИнструкции::НатМеньшеРавно, лок: ключ.лок.clone() });
                         }
-                        Тип::Цел64 => {
+                        Тип::Цел8 | Тип::Цел16 | Тип::Цел32 | Тип::Цел64 => {
                             пп.код.push(Инструкция{ вид: ВидИнструкции::ЦелМеньшеРавно, лок: ключ.лок.clone() });
                         }
                         Тип::Вещ32 => {
                             пп.код.push(Инструкция{ вид: ВидИнструкции::Вещ32МеньшеРавно, лок: ключ.лок.clone() });
                         }
+                        Тип::Вещ64 => {
+                            пп.код.push(Инструкция{ вид: ВидИнструкции::Вещ64МеньшеРавно, лок: ключ.лок.clone() });
+                        }
                         _ => {
                             диагностика!(&ключ.лок, "ОШИБКА", "Меньше либо равно для типов «{тип}» не поддерживается",
                                          тип = левый_тип.текст());
@@ -858,16 +1187,19 @@ fn скомпилировать_выражение(пп: &mut ПП, имена:
                     let левый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &левое, временный_размер)?;
                     let правый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &правое, временный_размер)?;
                     проверить_типы(правое.лок(), &левый_тип, &правый_тип)?;
-                    match левый_тип {
-                        Тип::Нат64 | Тип::Нат8 => {
+                    match левый_тип.основа() {
+                        Тип::Нат8 | Тип::Нат16 | Тип::Нат32 | Тип::Нат64 | Тип::Символ => {
                             пп.код.push(Инструкция{ вид: ВидИнструкции::НатБольшеРавно, лок: ключ.лок.clone() });
                         }
-                        Тип::Цел64 => {
+                        Тип::Цел8 | Тип::Цел16 | Тип::Цел32 | Тип::Цел64 => {
                             пп.код.push(Инструкция{ вид: ВидИнструкции::ЦелБольшеРавно, лок: ключ.лок.clone() });
                         }
                         Тип::Вещ32 => {
                             пп.код.push(Инструкция{ вид: ВидИнструкции::Вещ32БольшеРавно, лок: ключ.лок.clone() });
                         }
+                        Тип::Вещ64 => {
+                            пп.код.push(Инструкция{ вид: ВидИнструкции::Вещ64БольшеРавно, лок: ключ.лок.clone() });
+                        }
                         _ => {
                             диагностика!(&ключ.лок, "ОШИБКА", "Больше либо равно для типов «{тип}» не поддерживается",
                                          тип = левый_тип.текст());
@@ -880,16 +1212,19 @@ fn скомпилировать_выражение(пп: &mut ПП, имена:
                     let левый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &левое, временный_размер)?;
                     let правый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &правое, временный_размер)?;
                     проверить_типы(правое.лок(), &левый_тип, &правый_тип)?;
-                    match левый_тип {
-                        Тип::Нат64 | Тип::Нат8 => {
+                    match левый_тип.основа() {
+                        Тип::Нат8 | Тип::Нат16 | Тип::Нат32 | Тип::Нат64 | Тип::Символ => {
                             пп.код.push(Инструкция{ вид: ВидИнструкции::НатМеньше, лок: ключ.лок.clone() });
                         }
-                        Тип::Цел64 => {
+                        Тип::Цел8 | Тип::Цел16 | Тип::Цел32 | Тип::Цел64 => {
                             пп.код.push(Инструкция{ вид: ВидИнструкции::ЦелМеньше, лок: ключ.лок.clone() });
                         }
                         Тип::Вещ32 => {
                             пп.код.push(Инструкция{ вид: ВидИнструкции::Вещ32Меньше, лок: ключ.лок.clone() });
                         }
+                        Тип::Вещ64 => {
+                            пп.код.push(Инструкция{ вид: ВидИнструкции::Вещ64Меньше, лок: ключ.лок.clone() });
+                        }
                         _ => {
                             // СДЕЛАТЬ: Поддержка бинопа «меньше» для всех нужных типов
                             диагностика!(&ключ.лок, "ОШИБКА", "Меньше для типов «{тип}» не поддерживается",
@@ -903,16 +1238,19 @@ fn скомпилировать_выражение(пп: &mut ПП, имена:
                     let левый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &левое, временный_размер)?;
                     let правый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &правое, временный_размер)?;
                     проверить_типы(правое.лок(), &левый_тип, &правый_тип)?;
-                    match левый_тип {
-                        Тип::Цел64 | Тип::Нат8 => {
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::ЦелБольше, лок: ключ.лок.clone() });
-                        }
-                        Тип::Нат64 => {
+                    match левый_тип.основа() {
+                        Тип::Нат8 | Тип::Нат16 | Тип::Нат32 | Тип::Нат64 | Тип::Символ => {
                             пп.код.push(Инструкция{ вид: ВидИнструкции::НатБольше, лок: ключ.лок.clone() });
                         }
+                        Тип::Цел8 | Тип::Цел16 | Тип::Цел32 | Тип::Цел64 => {
+                            пп.код.push(Инструкция{ вид: ВидИнструкции::ЦелБольше, лок: ключ.лок.clone() });
+                        }
                         Тип::Вещ32 => {
                             пп.код.push(Инструкция{ вид: ВидИнструкции::Вещ32Больше, лок: ключ.лок.clone() });
                         }
+                        Тип::Вещ64 => {
+                            пп.код.push(Инструкция{ вид: ВидИнструкции::Вещ64Больше, лок: ключ.лок.clone() });
+                        }
                         _ => {
                             диагностика!(&ключ.лок, "ОШИБКА", "Больше для типов «{тип}» не поддерживается",
                                          тип = левый_тип.текст());
@@ -921,324 +1259,137 @@ fn скомпилировать_выражение(пп: &mut ПП, имена:
                     }
                     Ok(Тип::Лог)
                 }
-                ВидБинопа::Или => {
+                ВидБинопа::Или | ВидБинопа::И | ВидБинопа::Либо |
+                ВидБинопа::Сложение | ВидБинопа::Вычитание | ВидБинопа::Умножение |
+                ВидБинопа::СложениеПоМодулю | ВидБинопа::ВычитаниеПоМодулю | ВидБинопа::УмножениеПоМодулю |
+                ВидБинопа::Деление | ВидБинопа::Остаток |
+                ВидБинопа::ЛевоеБитовоеСмещение | ВидБинопа::ПравоеБитовоеСмещение => {
                     let левый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &левое, временный_размер)?;
+                    if левый_тип == Тип::Лог && matches!(вид, ВидБинопа::И | ВидБинопа::Или) {
+                        return скомпилировать_сокращённую_операцию(пп, Окружение{имена, текущая_процедура, локальные_имена}, ключ, вид, правое, временный_размер);
+                    }
                     let правый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &правое, временный_размер)?;
-                    проверить_типы(правое.лок(), &левый_тип, &правый_тип)?;
-                    match левый_тип {
-                        Тип::Лог | Тип::Нат64 | Тип::Нат8 | Тип::Цел64 => {
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::БитИли, лок: ключ.лок.clone() });
+                    проверить_типы_операндов(вид, правое.лок(), &левый_тип, &правый_тип)?;
+                    скомпилировать_операцию(пп, ключ, вид, левое.лок(), левый_тип)
+                }
+                ВидБинопа::Как => {
+                    let значение = левое;
+                    let тип = правое;
+                    let тип_цели = скомпилировать_тип(пп, тип, имена, Some(локальные_имена))?;
+                    let тип_значения = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &значение, временный_размер)?;
+
+                    // Отдельный тип конвертируется так же, как его основа.
+                    let тип_результата = тип_цели;
+                    let тип_цели = тип_результата.основа().clone();
+                    let тип_значения = тип_значения.основа().clone();
+                    if тип_значения == тип_цели {
+                        return Ok(тип_результата);
+                    }
+                    проверить_диапазон(пп, &тип_значения, &тип_цели, &ключ.лок);
+
+                    match (тип_значения.целое(), &тип_значения, тип_цели.целое(), &тип_цели) {
+                        (Some((знаковый, разрядность)), _, Some((знаковый_цели, разрядность_цели)), _) => {
+                            // Целые на стеке уже расширены до 64 бит, поэтому
+                            // достаточно отбросить биты, не помещающиеся в цель.
+                            let (мин, макс) = пределы_целого(знаковый, разрядность);
+                            let (мин_цели, макс_цели) = пределы_целого(знаковый_цели, разрядность_цели);
+                            if мин < мин_цели || макс > макс_цели {
+                                обрезать_до_разрядности(пп, &тип_цели, &ключ.лок);
+                            }
                         }
-                        _ => {
-                            диагностика!(&левое.лок(), "ОШИБКА", "Операция «или» не поддерживается для типов «{тип}»", тип = левый_тип.текст());
-                            return Err(())
+                        (Some(_), _, None, Тип::Лог) => {
+                            пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(0), лок: ключ.лок.clone() });
+                            пп.код.push(Инструкция{ вид: ВидИнструкции::НатРавно, лок: ключ.лок.clone() });
+                            пп.код.push(Инструкция{ вид: ВидИнструкции::ЛогОтрицание, лок: ключ.лок.clone() });
                         }
-                    }
-                    Ok(левый_тип.clone())
-                }
-                ВидБинопа::И => {
-                    let левый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &левое, временный_размер)?;
-                    let правый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &правое, временный_размер)?;
-                    проверить_типы(правое.лок(), &левый_тип, &правый_тип)?;
-                    match левый_тип {
-                        Тип::Лог |Тип::Нат64 | Тип::Нат8 | Тип::Цел64 => {
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::БитИ, лок: ключ.лок.clone() });
+                        (None, Тип::Лог, Some(_), _) => {}
+                        (Some((знаковый, _)), _, None, Тип::Вещ32) => {
+                            if знаковый {
+                                пп.код.push(Инструкция{ вид: ВидИнструкции::КонвертЦел64Вещ32, лок: ключ.лок.clone() });
+                            } else {
+                                пп.код.push(Инструкция{ вид: ВидИнструкции::КонвертНат64Вещ32, лок: ключ.лок.clone() });
+                            }
                         }
-                        _ => {
-                            диагностика!(&левое.лок(), "ОШИБКА", "Операция «и» не поддерживается для типов «{тип}»", тип = левый_тип.текст());
-                            return Err(())
+                        (None, Тип::Вещ32, Some((знаковый_цели, _)), _) => {
+                            if знаковый_цели {
+                                пп.код.push(Инструкция{ вид: ВидИнструкции::КонвертВещ32Цел64, лок: ключ.лок.clone() });
+                            } else {
+                                пп.код.push(Инструкция{ вид: ВидИнструкции::КонвертВещ32Нат64, лок: ключ.лок.clone() });
+                            }
+                            обрезать_до_разрядности(пп, &тип_цели, &ключ.лок);
                         }
-                    }
-                    Ok(левый_тип.clone())
-                }
-                ВидБинопа::Либо => {
-                    let левый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &левое, временный_размер)?;
-                    let правый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &правое, временный_размер)?;
-                    проверить_типы(правое.лок(), &левый_тип, &правый_тип)?;
-                    match левый_тип {
-                        Тип::Лог | Тип::Нат64 | Тип::Нат8 | Тип::Цел64 => {
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::БитЛибо, лок: ключ.лок.clone() });
+                        (None, Тип::Вещ32, None, Тип::Лог) => {
+                            пп.код.push(Инструкция{ вид: ВидИнструкции::КонвертВещ32Нат64, лок: ключ.лок.clone() });
+                            пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(0), лок: ключ.лок.clone() });
+                            пп.код.push(Инструкция{ вид: ВидИнструкции::НатРавно, лок: ключ.лок.clone() });
+                            пп.код.push(Инструкция{ вид: ВидИнструкции::ЛогОтрицание, лок: ключ.лок.clone() });
                         }
-                        _ => {
-                            диагностика!(&левое.лок(), "ОШИБКА", "Операция «либо» не поддерживается для типов «{тип}»", тип = левый_тип.текст());
-                            return Err(())
+                        (None, Тип::Лог, None, Тип::Вещ32) => {
+                            пп.код.push(Инструкция{ вид: ВидИнструкции::КонвертНат64Вещ32, лок: ключ.лок.clone() });
                         }
-                    }
-                    Ok(левый_тип.clone())
-                }
-                ВидБинопа::Сложение => {
-                    let левый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &левое, временный_размер)?;
-                    let правый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &правое, временный_размер)?;
-                    проверить_типы(правое.лок(), &левый_тип, &правый_тип)?;
-                    match левый_тип {
-                        Тип::Нат8 | Тип::Нат64 | Тип::Цел64 => {
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: ключ.лок.clone() });
+                        (Some((знаковый, _)), _, None, Тип::Вещ64) => {
+                            if знаковый {
+                                пп.код.push(Инструкция{ вид: ВидИнструкции::КонвертЦел64Вещ64, лок: ключ.лок.clone() });
+                            } else {
+                                пп.код.push(Инструкция{ вид: ВидИнструкции::КонвертНат64Вещ64, лок: ключ.лок.clone() });
+                            }
                         }
-                        Тип::Вещ32 => {
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::Вещ32Сложение, лок: ключ.лок.clone() });
+                        (None, Тип::Вещ64, Some((знаковый_цели, _)), _) => {
+                            if знаковый_цели {
+                                пп.код.push(Инструкция{ вид: ВидИнструкции::КонвертВещ64Цел64, лок: ключ.лок.clone() });
+                            } else {
+                                пп.код.push(Инструкция{ вид: ВидИнструкции::КонвертВещ64Нат64, лок: ключ.лок.clone() });
+                            }
+                            обрезать_до_разрядности(пп, &тип_цели, &ключ.лок);
+                        }
+                        (None, Тип::Вещ64, None, Тип::Лог) => {
+                            пп.код.push(Инструкция{ вид: ВидИнструкции::КонвертВещ64Нат64, лок: ключ.лок.clone() });
+                            пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(0), лок: ключ.лок.clone() });
+                            пп.код.push(Инструкция{ вид: ВидИнструкции::НатРавно, лок: ключ.лок.clone() });
+                            пп.код.push(Инструкция{ вид: ВидИнструкции::ЛогОтрицание, лок: ключ.лок.clone() });
+                        }
+                        (None, Тип::Лог, None, Тип::Вещ64) => {
+                            пп.код.push(Инструкция{ вид: ВидИнструкции::КонвертНат64Вещ64, лок: ключ.лок.clone() });
+                        }
+                        (None, Тип::Вещ32, None, Тип::Вещ64) => {
+                            пп.код.push(Инструкция{ вид: ВидИнструкции::КонвертВещ32Вещ64, лок: ключ.лок.clone() });
+                        }
+                        (None, Тип::Вещ64, None, Тип::Вещ32) => {
+                            пп.код.push(Инструкция{ вид: ВидИнструкции::КонвертВещ64Вещ32, лок: ключ.лок.clone() });
+                        }
+                        (None, Тип::Срез{..}, None, Тип::Срез{..}) => {}
+                        (None, Тип::Срез{..}, _, _) => {
+                            диагностика!(&ключ.лок, "ОШИБКА", "Срезы можно конвертировать только в другие срезы.");
+                            return Err(());
                         }
                         _ => {
-                            диагностика!(&ключ.лок, "ОШИБКА", "Сложение для типов «{тип}» не поддерживается",
-                                         тип = левый_тип.текст());
+                            диагностика!(&ключ.лок, "ОШИБКА", "Конвертация типа «{тип_значения}» в тип «{тип_цели}» невозможна", тип_значения = тип_значения.текст(), тип_цели = тип_цели.текст());
                             return Err(());
                         }
                     }
-                    Ok(левый_тип)
-                }
-                ВидБинопа::Вычитание => {
-                    let левый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &левое, временный_размер)?;
-                    let правый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &правое, временный_размер)?;
-                    проверить_типы(правое.лок(), &левый_тип, &правый_тип)?;
-                    match левый_тип {
-                        Тип::Нат8 | Тип::Нат64 | Тип::Цел64 => {
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::НатВычитание, лок: ключ.лок.clone() });
-                        }
-                        Тип::Вещ32 => {
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::Вещ32Вычитание, лок: ключ.лок.clone() });
-                        }
-                        _ => {
-                            диагностика!(&ключ.лок, "ОШИБКА", "Вычитание для типов «{тип}» не поддерживается",
-                                         тип = левый_тип.текст());
-                            return Err(());
-                        }
-                    }
-                    Ok(левый_тип)
-                }
-                ВидБинопа::Умножение => {
-                    let левый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &левое, временный_размер)?;
-                    let правый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &правое, временный_размер)?;
-                    проверить_типы(правое.лок(), &левый_тип, &правый_тип)?;
-                    match левый_тип {
-                        Тип::Цел64 => {
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::ЦелУмножение, лок: ключ.лок.clone() });
-                        }
-                        Тип::Нат8 | Тип::Нат64 => {
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::НатУмножение, лок: ключ.лок.clone() });
-                        }
-                        Тип::Вещ32 => {
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::Вещ32Умножение, лок: ключ.лок.clone() });
-                        }
-                        _ => {
-                            диагностика!(&ключ.лок, "ОШИБКА", "Перемножение для типов «{тип}» не поддерживается",
-                                         тип = левый_тип.текст());
-                            return Err(());
-                        }
-                    }
-                    Ok(левый_тип)
-                }
-                ВидБинопа::Деление => {
-                    let левый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &левое, временный_размер)?;
-                    let правый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &правое, временный_размер)?;
-                    проверить_типы(правое.лок(), &левый_тип, &правый_тип)?;
-                    match левый_тип {
-                        Тип::Цел64 => {
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::ЦелДеление, лок: ключ.лок.clone() });
-                        }
-                        Тип::Нат8 | Тип::Нат64 => {
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::НатДеление, лок: ключ.лок.clone() });
-                        }
-                        Тип::Вещ32 => {
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::Вещ32Деление, лок: ключ.лок.clone() });
-                        }
-                        _ => {
-                            диагностика!(&ключ.лок, "ОШИБКА", "Деление для типов «{тип}» не поддерживается",
-                                         тип = левый_тип.текст());
-                            return Err(());
-                        }
-                    }
-                    Ok(левый_тип)
-                }
-                ВидБинопа::Остаток => {
-                    let левый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &левое, временный_размер)?;
-                    let правый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &правое, временный_размер)?;
-                    проверить_типы(правое.лок(), &левый_тип, &правый_тип)?;
-                    match левый_тип {
-                        Тип::Цел64 => пп.код.push(Инструкция{ вид: ВидИнструкции::ЦелОстаток, лок: ключ.лок.clone() }),
-                        Тип::Нат8 | Тип::Нат64 => пп.код.push(Инструкция{ вид: ВидИнструкции::НатОстаток, лок: ключ.лок.clone() }),
-                        Тип::Вещ32 => {
-                            сделать!(&ключ.лок, "Остаток от деления вещественных чисел. Что-то типа fmodf, только встроенный.");
-                            return Err(());
-                        }
-                        _ => {
-                            диагностика!(&ключ.лок, "ОШИБКА", "Остаток для типов «{тип}» не поддерживается",
-                                         тип = левый_тип.текст());
-                            return Err(());
-                        }
-                    }
-                    Ok(левый_тип)
-                }
-                ВидБинопа::Как => {
-                    let значение = левое;
-                    let тип = правое;
-                    let тип_цели = скомпилировать_тип(пп, тип, имена, Some(локальные_имена))?;
-                    let тип_значения = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &значение, временный_размер)?;
-
-                    if тип_значения == тип_цели {
-                        return Ok(тип_цели);
-                    }
-
-                    match &тип_значения {
-                        Тип::Нат8 => match &тип_цели {
-                            Тип::Нат8 | Тип::Нат64 | Тип::Цел64 => {},
-                            Тип::Лог => {
-                                пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(0), лок: ключ.лок.clone() });
-                                пп.код.push(Инструкция{ вид: ВидИнструкции::НатРавно, лок: ключ.лок.clone() });
-                                пп.код.push(Инструкция{ вид: ВидИнструкции::ЛогОтрицание, лок: ключ.лок.clone() });
-                            }
-                            Тип::Вещ32 => {
-                                пп.код.push(Инструкция{ вид: ВидИнструкции::КонвертНат64Вещ32, лок: ключ.лок.clone() });
-                            }
-                            _ => {
-                                диагностика!(&ключ.лок, "ОШИБКА", "Конвертация типа «{тип_значения}» в тип «{тип_цели}» невозможна", тип_значения = тип_значения.текст(), тип_цели = тип_цели.текст());
-                                return Err(());
-                            }
-                        }
-                        Тип::Нат64 => match &тип_цели {
-                            Тип::Нат64 | Тип::Цел64 => {},
-                            Тип::Нат8 => {
-                                пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(0xFF), лок: ключ.лок.clone() });
-                                пп.код.push(Инструкция{ вид: ВидИнструкции::БитИ, лок: ключ.лок.clone() });
-                            }
-                            Тип::Лог => {
-                                пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(0), лок: ключ.лок.clone() });
-                                пп.код.push(Инструкция{ вид: ВидИнструкции::НатРавно, лок: ключ.лок.clone() });
-                                пп.код.push(Инструкция{ вид: ВидИнструкции::ЛогОтрицание, лок: ключ.лок.clone() });
-                            }
-                            Тип::Вещ32 => {
-                                пп.код.push(Инструкция{ вид: ВидИнструкции::КонвертНат64Вещ32, лок: ключ.лок.clone() });
-                            }
-                            _ => {
-                                диагностика!(&ключ.лок, "ОШИБКА", "Конвертация типа «{тип_значения}» в тип «{тип_цели}» невозможна", тип_значения = тип_значения.текст(), тип_цели = тип_цели.текст());
-                                return Err(());
-                            }
-                        }
-                        Тип::Цел64 => match &тип_цели {
-                            Тип::Нат64 | Тип::Цел64 => {},
-                            Тип::Нат8 => {
-                                пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(0xFF), лок: ключ.лок.clone() });
-                                пп.код.push(Инструкция{ вид: ВидИнструкции::БитИ, лок: ключ.лок.clone() });
-                            }
-                            Тип::Лог => {
-                                пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(0), лок: ключ.лок.clone() });
-                                пп.код.push(Инструкция{ вид: ВидИнструкции::НатРавно, лок: ключ.лок.clone() });
-                                пп.код.push(Инструкция{ вид: ВидИнструкции::ЛогОтрицание, лок: ключ.лок.clone() });
-                            }
-                            Тип::Вещ32 => {
-                                пп.код.push(Инструкция{ вид: ВидИнструкции::КонвертЦел64Вещ32, лок: ключ.лок.clone() });
-                            }
-                            _ => {
-                                диагностика!(&ключ.лок, "ОШИБКА", "Конвертация типа «{тип_значения}» в тип «{тип_цели}» невозможна", тип_значения = тип_значения.текст(), тип_цели = тип_цели.текст());
-                                return Err(());
-                            }
-                        }
-                        Тип::Вещ32 => match &тип_цели {
-                            Тип::Нат64 => {
-                                пп.код.push(Инструкция{ вид: ВидИнструкции::КонвертВещ32Нат64, лок: ключ.лок.clone() });
-                            }
-                            Тип::Цел64 => {
-                                пп.код.push(Инструкция{ вид: ВидИнструкции::КонвертВещ32Цел64, лок: ключ.лок.clone() });
-                            }
-                            Тип::Нат8 => {
-                                пп.код.push(Инструкция{ вид: ВидИнструкции::КонвертВещ32Нат64, лок: ключ.лок.clone() });
-                                пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(0xFF), лок: ключ.лок.clone() });
-                                пп.код.push(Инструкция{ вид: ВидИнструкции::БитИ, лок: ключ.лок.clone() });
-                            }
-                            Тип::Лог => {
-                                пп.код.push(Инструкция{ вид: ВидИнструкции::КонвертВещ32Нат64, лок: ключ.лок.clone() });
-                                пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(0), лок: ключ.лок.clone() });
-                                пп.код.push(Инструкция{ вид: ВидИнструкции::НатРавно, лок: ключ.лок.clone() });
-                                пп.код.push(Инструкция{ вид: ВидИнструкции::ЛогОтрицание, лок: ключ.лок.clone() });
-                            }
-                            Тип::Вещ32 => {}
-                            _ => {
-                                диагностика!(&ключ.лок, "ОШИБКА", "Конвертация типа «{тип_значения}» в тип «{тип_цели}» невозможна", тип_значения = тип_значения.текст(), тип_цели = тип_цели.текст());
-                                return Err(());
-                            }
-                        }
-                        Тип::Лог => match &тип_цели {
-                            Тип::Нат8 | Тип::Нат64 | Тип::Цел64 | Тип::Лог => {},
-                            Тип::Вещ32 => {
-                                пп.код.push(Инструкция{ вид: ВидИнструкции::КонвертНат64Вещ32, лок: ключ.лок.clone() });
-                            }
-                            _ => {
-                                диагностика!(&ключ.лок, "ОШИБКА", "Конвертация типа «{тип_значения}» в тип «{тип_цели}» невозможна", тип_значения = тип_значения.текст(), тип_цели = тип_цели.текст());
-                                return Err(());
-                            }
-                        }
-                        Тип::Срез{..} => match &тип_цели {
-                            Тип::Срез{..} => {}
-                            _ => {
-                                диагностика!(&ключ.лок, "ОШИБКА", "Срезы можно конвертировать только в другие срезы.");
-                                return Err(());
-                            }
-                        }
-                        _ => {
-                            диагностика!(&ключ.лок, "ОШИБКА", "Конвертация типа «{тип_значения}» в тип «{тип_цели}» невозможна", тип_значения = тип_значения.текст(), тип_цели = тип_цели.текст());
-                            return Err(());
-                        }
-                    }
-                    Ok(тип_цели)
-                }
-                ВидБинопа::ЛевоеБитовоеСмещение => {
-                    let левый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &левое, временный_размер)?;
-                    let правый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &правое, временный_размер)?;
-                    проверить_типы(правое.лок(), &Тип::Нат64, &правый_тип)?;
-                    match левый_тип {
-                        Тип::Нат64 => {
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::БитСмещениеВлево, лок: ключ.лок.clone() });
-                        }
-                        Тип::Нат8 => {
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::БитСмещениеВлево, лок: ключ.лок.clone() });
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(0xFF), лок: ключ.лок.clone() });
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::БитИ, лок: ключ.лок.clone() });
-                        }
-                        Тип::Цел64 => {
-                            сделать!(левое.лок(), "Знаковое битовое смещение пока не поддерживается");
-                            return Err(())
-                        }
-                        _ => {
-                            диагностика!(левое.лок(), "ОШИБКА", "Левое битовое смещение для типа «{тип}» не поддерживается", тип = левый_тип.текст());
-                            return Err(())
-                        }
-                    }
-                    Ok(левый_тип.clone())
-                }
-                ВидБинопа::ПравоеБитовоеСмещение => {
-                    let левый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &левое, временный_размер)?;
-                    let правый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &правое, временный_размер)?;
-                    проверить_типы(правое.лок(), &Тип::Нат64, &правый_тип)?;
-                    match левый_тип {
-                        Тип::Нат64 => {
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::БитСмещениеВправо, лок: ключ.лок.clone() });
-                        }
-                        Тип::Нат8 => {
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::БитСмещениеВправо, лок: ключ.лок.clone() });
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(0xFF), лок: ключ.лок.clone() });
-                            пп.код.push(Инструкция{ вид: ВидИнструкции::БитИ, лок: ключ.лок.clone() });
-                        }
-                        Тип::Цел64 => {
-                            сделать!(левое.лок(), "Знаковое битовое смещение пока не поддерживается");
-                            return Err(())
-                        }
-                        _ => {
-                            диагностика!(левое.лок(), "ОШИБКА", "Правое битовое смещение для типа «{тип}» не поддерживается", тип = левый_тип.текст());
-                            return Err(())
-                        }
-                    }
-                    Ok(левый_тип.clone())
+                    if тип_цели == Тип::Символ {
+                        проверить_суррогат(пп, &тип_значения, &ключ.лок);
+                    }
+                    Ok(тип_результата)
                 }
             }
         }
 
         Выражение::УнарныйМинус {ключ, выражение} => {
             let тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &выражение, временный_размер)?;
-            match тип {
+            match тип.основа() {
                 Тип::Вещ32 => {
                     пп.код.push(Инструкция{ вид: ВидИнструкции::Вещ32Отриц, лок: ключ.лок.clone() });
                     Ok(тип)
                 }
-                Тип::Цел64 => {
+                Тип::Вещ64 => {
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Вещ64Отриц, лок: ключ.лок.clone() });
+                    Ok(тип)
+                }
+                Тип::Цел8 | Тип::Цел16 | Тип::Цел32 | Тип::Цел64 => {
+                    проверить_арифметику(пп, Арифметика::Отрицание, &тип, &ключ.лок);
                     пп.код.push(Инструкция{ вид: ВидИнструкции::ЦелОтриц, лок: ключ.лок.clone() });
+                    обрезать_без_проверок(пп, &тип, &ключ.лок);
                     Ok(тип)
                 }
                 _ => {
@@ -1254,13 +1405,13 @@ fn скомпилировать_выражение(пп: &mut ПП, имена:
 
             for аргументы in аргументы {
                 match &тип_вызываемого {
-                    Тип::Массив{тип_элемента, ..} => {
+                    Тип::Массив{размер, тип_элемента} => {
                         let [индекс] = &аргументы.выражения[..] else {
                             диагностика!(&аргументы.ключ.лок, "ОШИБКА", "Некорректная размерность массива. Ожидался 1 индекс, но повстречалось {ранк}", ранк = аргументы.выражения.len());
                             return Err(());
                         };
 
-                        индексировать_массив(тип_элемента, пп, имена, текущая_процедура, локальные_имена, индекс, временный_размер)?;
+                        индексировать_массив(тип_элемента, *размер, пп, Окружение{имена, текущая_процедура, локальные_имена}, индекс, временный_размер)?;
                         if тип_элемента.примитивный() {
                             прочитать_примитивный_тип(&аргументы.ключ.лок, &mut пп.код, &имена.структуры, &тип_элемента)?;
                         }
@@ -1272,10 +1423,7 @@ fn скомпилировать_выражение(пп: &mut ПП, имена:
                             return Err(());
                         };
 
-                        пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(СРЕЗ_АДРЕС_СМЕЩЕНИЕ), лок: аргументы.ключ.лок.clone()});
-                        пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: аргументы.ключ.лок.clone()});
-                        пп.код.push(Инструкция{ вид: ВидИнструкции::Прочитать64, лок: аргументы.ключ.лок.clone()});
-                        индексировать_массив(тип_элемента, пп, имена, текущая_процедура, локальные_имена, индекс, временный_размер)?;
+                        индексировать_срез(тип_элемента, &аргументы.ключ.лок, пп, Окружение{имена, текущая_процедура, локальные_имена}, индекс, временный_размер)?;
                         if тип_элемента.примитивный() {
                             прочитать_примитивный_тип(&аргументы.ключ.лок, &mut пп.код, &имена.структуры, &тип_элемента)?;
                         }
@@ -1297,11 +1445,595 @@ fn скомпилировать_выражение(пп: &mut ПП, имена:
             пп.код.push(Инструкция{ вид: ВидИнструкции::ЛогОтрицание, лок: ключ.лок.clone() });
             Ok(Тип::Лог)
         }
+
+        Выражение::Если{ключ, условие, то, иначе} => {
+            let тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, условие, временный_размер)?;
+            проверить_типы(&условие.лок(), &Тип::Лог, &тип)?;
+            пп.код.push(Инструкция{ вид: ВидИнструкции::ЛогОтрицание, лок: ключ.лок.clone() });
+            let точка_условного_прыжка = пп.код.len();
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Ноп, лок: ключ.лок.clone() });
+
+            // Ветки могут выделить разное количество временной памяти, а
+            // освобождать её будет утверждение, в котором находится выражение.
+            // Поэтому выравниваем ветки по большей из них.
+            let mut временный_размер_то = 0;
+            let тип_то = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, то, &mut временный_размер_то)?;
+            let точка_дополнения_то = пп.код.len();
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Ноп, лок: ключ.лок.clone() });
+            let точка_конца_то = пп.код.len();
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Ноп, лок: ключ.лок.clone() });
+
+            let точка_иначе = пп.код.len();
+            пп.код[точка_условного_прыжка].вид = ВидИнструкции::УсловныйПрыжок(точка_иначе);
+            let mut временный_размер_иначе = 0;
+            let тип_иначе = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, иначе, &mut временный_размер_иначе)?;
+            проверить_типы(&иначе.лок(), &тип_то, &тип_иначе)?;
+            if временный_размер_иначе < временный_размер_то {
+                пп.код.push(Инструкция{ вид: ВидИнструкции::ВыделитьНаСтеке((временный_размер_то - временный_размер_иначе) as u64), лок: ключ.лок.clone() });
+            } else if временный_размер_то < временный_размер_иначе {
+                пп.код[точка_дополнения_то].вид = ВидИнструкции::ВыделитьНаСтеке((временный_размер_иначе - временный_размер_то) as u64);
+            }
+            let точка_выхода = пп.код.len();
+            пп.код[точка_конца_то].вид = ВидИнструкции::Прыжок(точка_выхода);
+            *временный_размер += временный_размер_то.max(временный_размер_иначе);
+            Ok(тип_то)
+        }
+
+        Выражение::Кортеж{ключ, элементы} => {
+            let mut типы = Vec::new();
+            for элемент in элементы.iter() {
+                типы.push(скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, элемент, временный_размер)?);
+            }
+            let смещения = Тип::смещения_элементов(&типы, &имена.структуры);
+            let части: Vec<(Тип, usize)> = типы.iter().cloned().zip(смещения).collect();
+            let тип = Тип::Кортеж(типы);
+            разместить_составное_значение(пп, &ключ.лок, &имена.структуры, &части, тип.размер(&имена.структуры), временный_размер)?;
+            Ok(тип)
+        }
+
+        Выражение::ЛитералСтруктуры{имя, поля} => {
+            let Some(структура) = имена.найти_структуру(&имя.текст) else {
+                диагностика!(&имя.лок, "ОШИБКА", "Неизвестная структура «{имя}»", имя = имя.текст);
+                return Err(());
+            };
+            let mut части = Vec::new();
+            for (индекс, (имя_поля, значение)) in поля.iter().enumerate() {
+                let Some(поле) = структура.поля.get(&имя_поля.текст) else {
+                    диагностика!(&имя_поля.лок, "ОШИБКА", "Структура «{структура}» не содержит поля «{поле}»", структура = имя.текст, поле = имя_поля.текст);
+                    return Err(());
+                };
+                if let Some((предыдущее, _)) = поля[..индекс].iter().find(|(предыдущее, _)| предыдущее.текст == имя_поля.текст) {
+                    диагностика!(&имя_поля.лок, "ОШИБКА", "Поле «{поле}» уже было задано", поле = имя_поля.текст);
+                    диагностика!(&предыдущее.лок, "ИНФО", "Первое значение поля находится здесь");
+                    return Err(());
+                }
+                let тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, значение, временный_размер)?;
+                проверить_типы(значение.лок(), &поле.тип, &тип)?;
+                части.push((тип, поле.смещение));
+            }
+            let mut пропущенные: Vec<&Поле> = структура.поля.values()
+                .filter(|поле| !поля.iter().any(|(имя_поля, _)| имя_поля.текст == поле.имя.текст))
+                .collect();
+            if !пропущенные.is_empty() {
+                пропущенные.sort_by_key(|поле| поле.смещение);
+                let имена_полей: Vec<String> = пропущенные.iter().map(|поле| format!("«{}»", поле.имя.текст)).collect();
+                диагностика!(&имя.лок, "ОШИБКА", "Не заданы значения полей {поля} структуры «{структура}»", поля = имена_полей.join(", "), структура = имя.текст);
+                return Err(());
+            }
+            разместить_составное_значение(пп, &имя.лок, &имена.структуры, &части, структура.размер, временный_размер)?;
+            Ok(Тип::Структура(структура.имя.текст.clone()))
+        }
+
+        Выражение::ЛитералМассива{ключ, элементы} => {
+            let mut тип_элемента: Option<Тип> = None;
+            let mut части = Vec::new();
+            for элемент in элементы.iter() {
+                let тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, элемент, временный_размер)?;
+                match &тип_элемента {
+                    Some(ожидаемый) => проверить_типы(элемент.лок(), ожидаемый, &тип)?,
+                    None => тип_элемента = Some(тип.clone()),
+                }
+                части.push((тип, 0));
+            }
+            let Some(тип_элемента) = тип_элемента else {
+                диагностика!(&ключ.лок, "ОШИБКА", "Невозможно определить тип элементов пустого литерала массива");
+                return Err(());
+            };
+            let размер_элемента = тип_элемента.размер(&имена.структуры);
+            for (индекс, (_, смещение)) in части.iter_mut().enumerate() {
+                *смещение = индекс*размер_элемента;
+            }
+            let тип = Тип::Массив{размер: элементы.len(), тип_элемента: Box::new(тип_элемента)};
+            разместить_составное_значение(пп, &ключ.лок, &имена.структуры, &части, тип.размер(&имена.структуры), временный_размер)?;
+            Ok(тип)
+        }
+    }
+}
+
+/// Выводит тип выражения, не компилируя его. Сообщает лишь об ошибках,
+/// без которых тип не вывести, остальные сообщит компиляция выражения.
+fn вывести_тип_выражения(пп: &ПП, имена: &Имена, текущая_процедура: &СкомпПроцедура, локальные_имена: &ЛокальныеИмена, выражение: &Выражение) -> Результат<Тип> {
+    let тип = вывести_тип_выражения_как_есть(пп, имена, текущая_процедура, локальные_имена, выражение)?;
+    if тип.отравлен() {
+        return Err(())
+    }
+    Ok(тип)
+}
+
+fn вывести_тип_выражения_как_есть(пп: &ПП, имена: &Имена, текущая_процедура: &СкомпПроцедура, локальные_имена: &ЛокальныеИмена, выражение: &Выражение) -> Результат<Тип> {
+    match выражение {
+        Выражение::Лог(..) => Ok(Тип::Лог),
+        Выражение::ЦелЧисло(_, _, разрядность) => Ok(Тип::целое_разрядности(true, *разрядность)),
+        Выражение::НатЧисло(_, _, разрядность) => Ok(Тип::целое_разрядности(false, *разрядность)),
+        Выражение::ВещЧисло(_, _, 64) => Ok(Тип::Вещ64),
+        Выражение::ВещЧисло(..) => Ok(Тип::Вещ32),
+        Выражение::Символ(_, символ) => {
+            if символ.is_ascii() {
+                Ok(Тип::Нат8)
+            } else {
+                Ok(Тип::Символ)
+            }
+        }
+        Выражение::Строка(_) => Ok(Тип::Срез { тип_элемента: Box::new(Тип::Нат8) }),
+        Выражение::ИндексСтроки{аргументы, ..} => {
+            let тип = Тип::Срез { тип_элемента: Box::new(Тип::Нат8) };
+            вывести_тип_элемента(&аргументы.ключ.лок, &тип, &аргументы.выражения)
+        }
+        Выражение::Идент(лексема) => {
+            if let Some(константа) = найти_константу_по_имени(&лексема.текст, имена, Some(локальные_имена)) {
+                return Ok(константа.тип.clone());
+            }
+            if let Some((тип, ..)) = найти_значение_в_памяти(&лексема.текст, имена, текущая_процедура, локальные_имена) {
+                return Ok(тип);
+            }
+            диагностика!(&лексема.лок, "ОШИБКА",
+                         "не существует ни констант, ни переменных с имением «{имя}»",
+                         имя = &лексема.текст);
+            Err(())
+        }
+        Выражение::Биноп{ключ, вид, левое, правое} => {
+            match вид {
+                ВидБинопа::Поле => {
+                    if let Some(выражение) = квалифицировать_имя(имена, Some((текущая_процедура, локальные_имена)), левое, правое)? {
+                        return вывести_тип_выражения(пп, имена, текущая_процедура, локальные_имена, &выражение);
+                    }
+                    match &**правое {
+                        Выражение::Идент(имя_поля) => {
+                            let левый_тип = вывести_тип_выражения(пп, имена, текущая_процедура, локальные_имена, левое)?;
+                            Ok(найти_поле(имена, ключ, &левый_тип, имя_поля)?.тип.clone())
+                        }
+                        Выражение::ЦепочкаВызовов{имя, цепочка_аргументов} => {
+                            let mut аргументы_цепочки = цепочка_аргументов.iter();
+                            let аргументы = &аргументы_цепочки.next().expect("Цепочка аргументов вызова не может быть пустой").выражения;
+                            let mut тип = if let Some((имя_процедуры, _)) = разрешить_вызов_процедуры_структуры(пп, имена, текущая_процедура, локальные_имена, левое, имя, аргументы)? {
+                                тип_результата_процедуры(имена, &имя_процедуры)?
+                            } else {
+                                let левый_тип = вывести_тип_выражения(пп, имена, текущая_процедура, локальные_имена, левое)?;
+                                let поле = найти_поле(имена, ключ, &левый_тип, имя)?;
+                                вывести_тип_элемента(&цепочка_аргументов[0].ключ.лок, &поле.тип, аргументы)?
+                            };
+                            for аргументы in аргументы_цепочки {
+                                тип = вывести_тип_элемента(&аргументы.ключ.лок, &тип, &аргументы.выражения)?;
+                            }
+                            Ok(тип)
+                        }
+                        _ => {
+                            диагностика!(&правое.лок(), "ОШИБКА", "Данное выражение не может быть использовано как поле");
+                            Err(())
+                        }
+                    }
+                }
+                ВидБинопа::Равно | ВидБинопа::НеРавно |
+                ВидБинопа::Меньше | ВидБинопа::МеньшеРавно |
+                ВидБинопа::Больше | ВидБинопа::БольшеРавно => Ok(Тип::Лог),
+                ВидБинопа::Как => скомпилировать_тип(пп, правое, имена, Some(локальные_имена)),
+                _ => вывести_тип_выражения(пп, имена, текущая_процедура, локальные_имена, левое),
+            }
+        }
+        Выражение::УнарныйМинус{выражение, ..} => вывести_тип_выражения(пп, имена, текущая_процедура, локальные_имена, выражение),
+        Выражение::Отрицание{..} => Ok(Тип::Лог),
+        Выражение::Если{то, ..} => вывести_тип_выражения(пп, имена, текущая_процедура, локальные_имена, то),
+        Выражение::ЦепочкаВызовов{имя, цепочка_аргументов} => {
+            let mut аргументы_цепочки = цепочка_аргументов.iter();
+            let аргументы = аргументы_цепочки.next().expect("Цепочка аргументов вызова не может быть пустой");
+            let mut тип = match имя.текст.as_str() {
+                "ввод" | "сисвызов" | "адрес" => Тип::Нат64,
+                "размер" => Тип::Цел64,
+                "срез" => {
+                    let Some(массив) = аргументы.выражения.first() else {
+                        return Ok(Тип::Срез { тип_элемента: Box::new(Тип::Нат8) })
+                    };
+                    match вывести_тип_выражения(пп, имена, текущая_процедура, локальные_имена, массив)? {
+                        Тип::Массив{тип_элемента, ..} | Тип::Срез{тип_элемента} => Тип::Срез{тип_элемента},
+                        тип => {
+                            диагностика!(массив.лок(), "ОШИБКА",
+                                         "Значение типа «{тип}» не является массивом. Срезы возможны только для массивов либо других срезов.",
+                                         тип = тип.текст());
+                            return Err(())
+                        }
+                    }
+                }
+                _ => {
+                    if имена.найти_процедуру(&имя.текст).is_some() {
+                        тип_результата_процедуры(имена, имя)?
+                    } else if let Some((тип, ..)) = найти_значение_в_памяти(&имя.текст, имена, текущая_процедура, локальные_имена) {
+                        if тип.отравлен() {
+                            return Err(())
+                        }
+                        вывести_тип_элемента(&имя.лок, &тип, &аргументы.выражения)?
+                    } else {
+                        диагностика!(&имя.лок, "ОШИБКА", "Неизвестное имя «{имя}»", имя = имя.текст);
+                        return Err(())
+                    }
+                }
+            };
+            for аргументы in аргументы_цепочки {
+                тип = вывести_тип_элемента(&аргументы.ключ.лок, &тип, &аргументы.выражения)?;
+            }
+            Ok(тип)
+        }
+        Выражение::Кортеж{элементы, ..} => {
+            let mut типы = Vec::new();
+            for элемент in элементы.iter() {
+                типы.push(вывести_тип_выражения(пп, имена, текущая_процедура, локальные_имена, элемент)?);
+            }
+            Ok(Тип::Кортеж(типы))
+        }
+        Выражение::ЛитералСтруктуры{имя, ..} => {
+            let Some(структура) = имена.найти_структуру(&имя.текст) else {
+                диагностика!(&имя.лок, "ОШИБКА", "Неизвестная структура «{имя}»", имя = имя.текст);
+                return Err(());
+            };
+            Ok(Тип::Структура(структура.имя.текст.clone()))
+        }
+        Выражение::ЛитералМассива{ключ, элементы} => {
+            let Some(первый) = элементы.first() else {
+                диагностика!(&ключ.лок, "ОШИБКА", "Невозможно определить тип элементов пустого литерала массива");
+                return Err(());
+            };
+            let тип_элемента = вывести_тип_выражения(пп, имена, текущая_процедура, локальные_имена, первый)?;
+            Ok(Тип::Массив{размер: элементы.len(), тип_элемента: Box::new(тип_элемента)})
+        }
+    }
+}
+
+/// Тип элемента, получаемого индексированием значения типа «тип».
+fn вывести_тип_элемента(лок: &Лок, тип: &Тип, аргументы: &[Выражение]) -> Результат<Тип> {
+    match тип {
+        Тип::Массив{тип_элемента, ..} => {
+            if аргументы.len() != 1 {
+                диагностика!(лок, "ОШИБКА", "Некорректная размерность массива. Ожидался 1 индекс, но повстречалось {ранк}", ранк = аргументы.len());
+                return Err(());
+            }
+            Ok(*тип_элемента.clone())
+        }
+        Тип::Срез{тип_элемента} => {
+            if аргументы.len() != 1 {
+                диагностика!(лок, "ОШИБКА", "Некорректная размерность среза. Ожидался 1 индекс, но повстречалось {ранк}", ранк = аргументы.len());
+                return Err(());
+            }
+            Ok(*тип_элемента.clone())
+        }
+        _ => {
+            диагностика!(лок, "ОШИБКА", "Невозможно индексировать тип «{тип}»", тип = тип.текст());
+            Err(())
+        }
+    }
+}
+
+fn тип_результата_процедуры(имена: &Имена, имя: &Лексема) -> Результат<Тип> {
+    let процедура = имена.найти_процедуру(&имя.текст).expect("Существование процедуры должно быть уже проверено");
+    if let Some(результат) = &процедура.результат {
+        Ok(результат.тип.clone())
+    } else {
+        диагностика!(&имя.лок, "ОШИБКА", "Данная процедура ничего не возвращает. Её нельзя использовать как выражение. Только как утверждение.");
+        Err(())
+    }
+}
+
+fn найти_поле<'ы>(имена: &'ы Имена, ключ: &Лексема, тип: &Тип, имя_поля: &Лексема) -> Результат<&'ы Поле> {
+    let Тип::Структура(имя_структуры) = тип else {
+        диагностика!(&ключ.лок, "ОШИБКА", "У типа «{тип}» отстутствует поле «{поле}»", тип = тип.текст(), поле = имя_поля.текст);
+        return Err(())
+    };
+    let структура = имена
+        .структуры
+        .get(имя_структуры)
+        .expect("Существование структуры должно быть уже проверено на этапе компиляции типа");
+    let Some(поле) = структура.поля.get(&имя_поля.текст) else {
+        диагностика!(&ключ.лок, "ОШИБКА", "У структуры «{тип}» отстутствует поле «{поле}»", тип = тип.текст(), поле = имя_поля.текст);
+        return Err(())
+    };
+    Ok(поле)
+}
+
+/// Ожидает на стеке значения частей составного значения в порядке их
+/// перечисления. Выделяет под значение временную память, записывает в неё
+/// части по их смещениям и оставляет на стеке адрес значения.
+///
+/// Память выделяется только после вычисления всех частей, так как они
+/// сами могут занимать временную память.
+fn разместить_составное_значение(пп: &mut ПП, лок: &Лок, структуры: &HashMap<String, Структура>, части: &[(Тип, usize)], размер: usize, временный_размер: &mut usize) -> Результат<()> {
+    пп.код.push(Инструкция{ вид: ВидИнструкции::ВыделитьНаСтеке(размер as u64), лок: лок.clone() });
+    *временный_размер += размер;
+    for (тип, смещение) in части.iter().rev() {
+        пп.код.push(Инструкция{ вид: ВидИнструкции::ВершинаСтека(*смещение as i32), лок: лок.clone() });
+        записать_тип(лок, &mut пп.код, структуры, тип)?;
+    }
+    пп.код.push(Инструкция{ вид: ВидИнструкции::ВершинаСтека(0), лок: лок.clone() });
+    Ok(())
+}
+
+/// Логические «и» и «или» вычисляют правый операнд, только если левого,
+/// уже лежащего на стеке, недостаточно для результата.
+fn скомпилировать_сокращённую_операцию(пп: &mut ПП, окружение: Окружение, ключ: &Лексема, вид: &ВидБинопа, правое: &Выражение, временный_размер: &mut usize) -> Результат<Тип> {
+    let Окружение{имена, текущая_процедура, локальные_имена} = окружение;
+    пп.код.push(Инструкция{ вид: ВидИнструкции::Продублировать, лок: ключ.лок.clone() });
+    if let ВидБинопа::И = вид {
+        пп.код.push(Инструкция{ вид: ВидИнструкции::ЛогОтрицание, лок: ключ.лок.clone() });
+    }
+    let точка_условного_прыжка = пп.код.len();
+    пп.код.push(Инструкция{ вид: ВидИнструкции::Ноп, лок: ключ.лок.clone() });
+
+    пп.код.push(Инструкция{ вид: ВидИнструкции::Вытолкнуть, лок: ключ.лок.clone() });
+    let mut временный_размер_правого = 0;
+    let правый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, правое, &mut временный_размер_правого)?;
+    проверить_типы(правое.лок(), &Тип::Лог, &правый_тип)?;
+
+    if временный_размер_правого > 0 {
+        // Временная память правого операнда освобождается утверждением,
+        // поэтому при сокращении её нужно выделить, чтобы размер совпал.
+        let точка_прыжка_в_конец = пп.код.len();
+        пп.код.push(Инструкция{ вид: ВидИнструкции::Ноп, лок: ключ.лок.clone() });
+        let точка_сокращения = пп.код.len();
+        пп.код[точка_условного_прыжка].вид = ВидИнструкции::УсловныйПрыжок(точка_сокращения);
+        пп.код.push(Инструкция{ вид: ВидИнструкции::ВыделитьНаСтеке(временный_размер_правого as u64), лок: ключ.лок.clone() });
+        let точка_выхода = пп.код.len();
+        пп.код[точка_прыжка_в_конец].вид = ВидИнструкции::Прыжок(точка_выхода);
+        *временный_размер += временный_размер_правого;
+    } else {
+        let точка_выхода = пп.код.len();
+        пп.код[точка_условного_прыжка].вид = ВидИнструкции::УсловныйПрыжок(точка_выхода);
+    }
+    Ok(Тип::Лог)
+}
+
+/// Проверяет типы операндов арифметических и битовых операций.
+fn проверить_типы_операндов(вид: &ВидБинопа, лок: &Лок, левый_тип: &Тип, правый_тип: &Тип) -> Результат<()> {
+    match вид {
+        ВидБинопа::ЛевоеБитовоеСмещение | ВидБинопа::ПравоеБитовоеСмещение => проверить_типы(лок, &Тип::Нат64, правый_тип),
+        _ => проверить_типы(лок, левый_тип, правый_тип),
+    }
+}
+
+/// Ожидает на стеке оба операнда арифметической либо битовой операции,
+/// типы которых уже проверены.
+fn скомпилировать_операцию(пп: &mut ПП, ключ: &Лексема, вид: &ВидБинопа, левый_лок: &Лок, левый_тип: Тип) -> Результат<Тип> {
+    if let Тип::Отдельный{основа, ..} = &левый_тип {
+        // Операция выполняется над основой, но результат остаётся
+        // отдельного типа.
+        скомпилировать_операцию(пп, ключ, вид, левый_лок, (**основа).clone())?;
+        return Ok(левый_тип)
+    }
+    match вид {
+        ВидБинопа::Или => {
+            match левый_тип {
+                Тип::Лог | Тип::Нат8 | Тип::Нат16 | Тип::Нат32 | Тип::Нат64 |
+                Тип::Цел8 | Тип::Цел16 | Тип::Цел32 | Тип::Цел64 => {
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::БитИли, лок: ключ.лок.clone() });
+                }
+                _ => {
+                    диагностика!(левый_лок, "ОШИБКА", "Операция «или» не поддерживается для типов «{тип}»", тип = левый_тип.текст());
+                    return Err(())
+                }
+            }
+            Ok(левый_тип.clone())
+        }
+        ВидБинопа::И => {
+            match левый_тип {
+                Тип::Лог | Тип::Нат8 | Тип::Нат16 | Тип::Нат32 | Тип::Нат64 |
+                Тип::Цел8 | Тип::Цел16 | Тип::Цел32 | Тип::Цел64 => {
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::БитИ, лок: ключ.лок.clone() });
+                }
+                _ => {
+                    диагностика!(левый_лок, "ОШИБКА", "Операция «и» не поддерживается для типов «{тип}»", тип = левый_тип.текст());
+                    return Err(())
+                }
+            }
+            Ok(левый_тип.clone())
+        }
+        ВидБинопа::Либо => {
+            match левый_тип {
+                Тип::Лог | Тип::Нат8 | Тип::Нат16 | Тип::Нат32 | Тип::Нат64 |
+                Тип::Цел8 | Тип::Цел16 | Тип::Цел32 | Тип::Цел64 => {
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::БитЛибо, лок: ключ.лок.clone() });
+                }
+                _ => {
+                    диагностика!(левый_лок, "ОШИБКА", "Операция «либо» не поддерживается для типов «{тип}»", тип = левый_тип.текст());
+                    return Err(())
+                }
+            }
+            Ok(левый_тип.clone())
+        }
+        ВидБинопа::Сложение => {
+            match левый_тип {
+                Тип::Нат8 | Тип::Нат16 | Тип::Нат32 | Тип::Нат64 |
+                Тип::Цел8 | Тип::Цел16 | Тип::Цел32 | Тип::Цел64 => {
+                    проверить_арифметику(пп, Арифметика::Сложение, &левый_тип, &ключ.лок);
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: ключ.лок.clone() });
+                    обрезать_без_проверок(пп, &левый_тип, &ключ.лок);
+                }
+                Тип::Вещ32 => {
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Вещ32Сложение, лок: ключ.лок.clone() });
+                }
+                Тип::Вещ64 => {
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Вещ64Сложение, лок: ключ.лок.clone() });
+                }
+                _ => {
+                    диагностика!(&ключ.лок, "ОШИБКА", "Сложение для типов «{тип}» не поддерживается",
+                                 тип = левый_тип.текст());
+                    return Err(());
+                }
+            }
+            Ok(левый_тип)
+        }
+        ВидБинопа::Вычитание => {
+            match левый_тип {
+                Тип::Нат8 | Тип::Нат16 | Тип::Нат32 | Тип::Нат64 |
+                Тип::Цел8 | Тип::Цел16 | Тип::Цел32 | Тип::Цел64 => {
+                    проверить_арифметику(пп, Арифметика::Вычитание, &левый_тип, &ключ.лок);
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::НатВычитание, лок: ключ.лок.clone() });
+                    обрезать_без_проверок(пп, &левый_тип, &ключ.лок);
+                }
+                Тип::Вещ32 => {
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Вещ32Вычитание, лок: ключ.лок.clone() });
+                }
+                Тип::Вещ64 => {
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Вещ64Вычитание, лок: ключ.лок.clone() });
+                }
+                _ => {
+                    диагностика!(&ключ.лок, "ОШИБКА", "Вычитание для типов «{тип}» не поддерживается",
+                                 тип = левый_тип.текст());
+                    return Err(());
+                }
+            }
+            Ok(левый_тип)
+        }
+        ВидБинопа::Умножение => {
+            match левый_тип {
+                Тип::Цел8 | Тип::Цел16 | Тип::Цел32 | Тип::Цел64 => {
+                    проверить_арифметику(пп, Арифметика::Умножение, &левый_тип, &ключ.лок);
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::ЦелУмножение, лок: ключ.лок.clone() });
+                    обрезать_без_проверок(пп, &левый_тип, &ключ.лок);
+                }
+                Тип::Нат8 | Тип::Нат16 | Тип::Нат32 | Тип::Нат64 => {
+                    проверить_арифметику(пп, Арифметика::Умножение, &левый_тип, &ключ.лок);
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::НатУмножение, лок: ключ.лок.clone() });
+                    обрезать_без_проверок(пп, &левый_тип, &ключ.лок);
+                }
+                Тип::Вещ32 => {
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Вещ32Умножение, лок: ключ.лок.clone() });
+                }
+                Тип::Вещ64 => {
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Вещ64Умножение, лок: ключ.лок.clone() });
+                }
+                _ => {
+                    диагностика!(&ключ.лок, "ОШИБКА", "Перемножение для типов «{тип}» не поддерживается",
+                                 тип = левый_тип.текст());
+                    return Err(());
+                }
+            }
+            Ok(левый_тип)
+        }
+        ВидБинопа::СложениеПоМодулю | ВидБинопа::ВычитаниеПоМодулю | ВидБинопа::УмножениеПоМодулю => {
+            if левый_тип.целое().is_none() {
+                диагностика!(&ключ.лок, "ОШИБКА", "Арифметика по модулю для типов «{тип}» не поддерживается",
+                             тип = левый_тип.текст());
+                return Err(());
+            }
+            // Младшие биты результата не зависят от знаковости операндов.
+            let вид_инструкции = match вид {
+                ВидБинопа::СложениеПоМодулю => ВидИнструкции::НатСложение,
+                ВидБинопа::ВычитаниеПоМодулю => ВидИнструкции::НатВычитание,
+                _ => ВидИнструкции::НатУмножение,
+            };
+            пп.код.push(Инструкция{ вид: вид_инструкции, лок: ключ.лок.clone() });
+            обрезать_до_разрядности(пп, &левый_тип, &ключ.лок);
+            Ok(левый_тип)
+        }
+        ВидБинопа::Деление => {
+            match левый_тип {
+                Тип::Цел8 | Тип::Цел16 | Тип::Цел32 | Тип::Цел64 => {
+                    проверить_арифметику(пп, Арифметика::Деление, &левый_тип, &ключ.лок);
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::ЦелДеление, лок: ключ.лок.clone() });
+                    обрезать_без_проверок(пп, &левый_тип, &ключ.лок);
+                }
+                Тип::Нат8 | Тип::Нат16 | Тип::Нат32 | Тип::Нат64 => {
+                    проверить_арифметику(пп, Арифметика::Деление, &левый_тип, &ключ.лок);
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::НатДеление, лок: ключ.лок.clone() });
+                    обрезать_без_проверок(пп, &левый_тип, &ключ.лок);
+                }
+                Тип::Вещ32 => {
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Вещ32Деление, лок: ключ.лок.clone() });
+                }
+                Тип::Вещ64 => {
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Вещ64Деление, лок: ключ.лок.clone() });
+                }
+                _ => {
+                    диагностика!(&ключ.лок, "ОШИБКА", "Деление для типов «{тип}» не поддерживается",
+                                 тип = левый_тип.текст());
+                    return Err(());
+                }
+            }
+            Ok(левый_тип)
+        }
+        ВидБинопа::Остаток => {
+            match левый_тип {
+                Тип::Цел8 | Тип::Цел16 | Тип::Цел32 | Тип::Цел64 => {
+                    проверить_арифметику(пп, Арифметика::Деление, &левый_тип, &ключ.лок);
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::ЦелОстаток, лок: ключ.лок.clone() });
+                    обрезать_без_проверок(пп, &левый_тип, &ключ.лок);
+                }
+                Тип::Нат8 | Тип::Нат16 | Тип::Нат32 | Тип::Нат64 => {
+                    проверить_арифметику(пп, Арифметика::Деление, &левый_тип, &ключ.лок);
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::НатОстаток, лок: ключ.лок.clone() });
+                    обрезать_без_проверок(пп, &левый_тип, &ключ.лок);
+                }
+                Тип::Вещ32 => {
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Вещ32Остаток, лок: ключ.лок.clone() });
+                }
+                Тип::Вещ64 => {
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Вещ64Остаток, лок: ключ.лок.clone() });
+                }
+                _ => {
+                    диагностика!(&ключ.лок, "ОШИБКА", "Остаток для типов «{тип}» не поддерживается",
+                                 тип = левый_тип.текст());
+                    return Err(());
+                }
+            }
+            Ok(левый_тип)
+        }
+        ВидБинопа::ЛевоеБитовоеСмещение => {
+            match левый_тип {
+                // Биты, выдвинутые за разрядность, отбрасываются. У целых
+                // знак берётся из нового старшего бита.
+                Тип::Нат8 | Тип::Нат16 | Тип::Нат32 | Тип::Нат64 |
+                Тип::Цел8 | Тип::Цел16 | Тип::Цел32 | Тип::Цел64 => {
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::БитСмещениеВлево, лок: ключ.лок.clone() });
+                    обрезать_до_разрядности(пп, &левый_тип, &ключ.лок);
+                }
+                _ => {
+                    диагностика!(левый_лок, "ОШИБКА", "Левое битовое смещение для типа «{тип}» не поддерживается", тип = левый_тип.текст());
+                    return Err(())
+                }
+            }
+            Ok(левый_тип.clone())
+        }
+        ВидБинопа::ПравоеБитовоеСмещение => {
+            match левый_тип {
+                // Узкие натуральные лежат на стеке без старших битов, поэтому
+                // смещение вправо не требует обрезания.
+                Тип::Нат8 | Тип::Нат16 | Тип::Нат32 | Тип::Нат64 => {
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::БитСмещениеВправо, лок: ключ.лок.clone() });
+                }
+                // Узкие целые лежат на стеке расширенными до 64 бит, поэтому
+                // арифметическое смещение сразу даёт верный результат.
+                Тип::Цел8 | Тип::Цел16 | Тип::Цел32 | Тип::Цел64 => {
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::ЦелСмещениеВправо, лок: ключ.лок.clone() });
+                }
+                _ => {
+                    диагностика!(левый_лок, "ОШИБКА", "Правое битовое смещение для типа «{тип}» не поддерживается", тип = левый_тип.текст());
+                    return Err(())
+                }
+            }
+            Ok(левый_тип.clone())
+        }
+        _ => unreachable!("Операция «{:?}» не является арифметической либо битовой", вид),
     }
 }
 
 fn достать_и_проверить_процедуру_печати<'ы>(имена: &'ы Имена, имя_принтера: &str, тип: &Тип, лок: &Лок) -> Результат<&'ы СкомпПроцедура> {
-    if let Some(процедура) = имена.процедуры.get(имя_принтера) {
+    if let Some(процедура) = имена.найти_процедуру(имя_принтера) {
         let арность = 1;
         let [параметр] = процедура.параметры.as_slice() else {
             диагностика!(лок, "ОШИБКА", "Процедура «{имя_принтера}» ответственная за печать данного аргумента типа «{тип}» имеет некорректную сигнатуру. Она должна принимать {арность} {параметров} типа «{тип}»", тип = тип.текст(), параметров = ЧИСУЩ_ПАРАМЕТР.текст(арность));
@@ -1326,7 +2058,79 @@ fn достать_и_проверить_процедуру_печати<'ы>(и
     }
 }
 
-fn вызвать_процедуру(лок: Лок, имена: &Имена, процедура: &СкомпПроцедура, код: &mut Vec<Инструкция>, временный_размер: &mut usize) -> Результат<()> {
+/// Проверяет количество и типы аргументов вызова и компилирует их в
+/// обратном порядке. Возвращает типы вариативных аргументов, если
+/// процедура вариативная.
+fn скомпилировать_аргументы_вызова(пп: &mut ПП, окружение: Окружение, имя: &Лексема, процедура: &СкомпПроцедура, аргументы: &[Выражение], временный_размер: &mut usize) -> Результат<Vec<Тип>> {
+    let Окружение{имена, текущая_процедура, локальные_имена} = окружение;
+    let количество_аргументов = аргументы.len();
+    let количество_параметров = процедура.параметры.len();
+    if процедура.вариативная {
+        if количество_аргументов < количество_параметров {
+            диагностика!(&имя.лок, "ОШИБКА",
+                         "Неверное количество аргументов вызова процедуры. Вариативная процедура принимает как минимум {количество_параметров} {параметров}, но в данном вызове предоставлено лишь {количество_аргументов} {аргументов}.",
+                         параметров = ЧИСУЩ_ПАРАМЕТР.текст(количество_параметров),
+                         аргументов = ЧИСУЩ_АРГУМЕНТ.текст(количество_аргументов));
+            return Err(());
+        }
+    } else if количество_аргументов != количество_параметров {
+        диагностика!(&имя.лок, "ОШИБКА",
+                     "Неверное количество аргументов вызова процедуры. Процедура принимает {количество_параметров} {параметров}, но в данном вызове предоставлено лишь {количество_аргументов} {аргументов}.",
+                     параметров = ЧИСУЩ_ПАРАМЕТР.текст(количество_параметров),
+                     аргументов = ЧИСУЩ_АРГУМЕНТ.текст(количество_аргументов));
+        return Err(());
+    }
+
+    let mut вариативные_аргументы = Vec::new();
+    for аргумент in аргументы[количество_параметров..].iter().rev() {
+        let тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, аргумент, временный_размер)?;
+        if !тип.примитивный() {
+            диагностика!(&аргумент.лок(), "ОШИБКА", "Вариативными аргументами могут быть только значения примитивных типов, но повстречался тип «{тип}».",
+                         тип = тип.текст());
+            return Err(())
+        }
+        вариативные_аргументы.push(тип);
+    }
+    вариативные_аргументы.reverse();
+
+    for (параметр, аргумент) in процедура.параметры.iter().zip(аргументы.iter()).rev() {
+        let тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, аргумент, временный_размер)?;
+        проверить_типы(&аргумент.лок(), &параметр.тип, &тип)?;
+        if параметр.ссылка && значение_в_данных(имена, текущая_процедура, локальные_имена, аргумент) {
+            // «сам» не должен указывать в неизменяемые данные, поэтому
+            // процедура получает копию константы.
+            разместить_составное_значение(пп, аргумент.лок(), &имена.структуры, &[(тип.clone(), 0)], тип.размер(&имена.структуры), временный_размер)?;
+        }
+    }
+    Ok(вариативные_аргументы)
+}
+
+/// Лежит ли значение выражения в инициализированных данных, то есть
+/// является ли оно составной константой либо её частью.
+fn значение_в_данных(имена: &Имена, текущая_процедура: &СкомпПроцедура, локальные_имена: &ЛокальныеИмена, выражение: &Выражение) -> bool {
+    match выражение {
+        Выражение::Идент(имя) => найти_константу_по_имени(&имя.текст, имена, Some(локальные_имена)).is_some(),
+        Выражение::ЦепочкаВызовов{имя, ..} => {
+            имена.найти_процедуру(&имя.текст).is_none()
+                && matches!(найти_значение_в_памяти(&имя.текст, имена, текущая_процедура, локальные_имена), Some((_, Локация::Данные, _)))
+        }
+        Выражение::Биноп{вид: ВидБинопа::Поле, левое, правое, ..} => {
+            if let Ok(Some(выражение)) = квалифицировать_имя(имена, Some((текущая_процедура, локальные_имена)), левое, правое) {
+                return значение_в_данных(имена, текущая_процедура, локальные_имена, &выражение)
+            }
+            значение_в_данных(имена, текущая_процедура, локальные_имена, левое)
+        }
+        _ => false,
+    }
+}
+
+/// Рекурсивный вызов не делает процедуру использованной: иначе
+/// процедура, которую вызывает только она сама, осталась бы без
+/// предупреждения.
+fn вызвать_процедуру(лок: Лок, имена: &Имена, текущая_процедура: &СкомпПроцедура, процедура: &СкомпПроцедура, вариативные_аргументы: &[Тип], код: &mut Vec<Инструкция>, временный_размер: &mut usize) -> Результат<()> {
+    if процедура.имя.лок != текущая_процедура.имя.лок {
+        процедура.использована.set(true);
+    }
     match &процедура.точка_входа {
         &ТочкаВхода::Внутреняя{адрес} => {
             код.push(Инструкция {
@@ -1335,13 +2139,14 @@ fn вызвать_процедуру(лок: Лок, имена: &Имена, п
             });
             if let Some(результат) = &процедура.результат {
                 if !результат.тип.примитивный() {
-                    *временный_размер += результат.тип.размер(&имена.структуры);
+                    // Результат остаётся на стеке вместе с выравниванием.
+                    *временный_размер += выравнять_размер_по_слову(результат.тип.размер(&имена.структуры));
                 }
             }
         },
         &ТочкаВхода::Внешняя{индекс} => {
             for параметр in &процедура.параметры {
-                if параметр.тип != Тип::Нат64 && параметр.тип != Тип::Цел64 {
+                if !параметр.тип.примитивный() {
                     сделать!(&лок, "Вызов внешней процедуры с параметрами типа «{тип}»", тип = параметр.тип.текст());
                     return Err(())
                 }
@@ -1350,8 +2155,14 @@ fn вызвать_процедуру(лок: Лок, имена: &Имена, п
             код.push(Инструкция {
                 вид: ВидИнструкции::ВнешнийВызов{
                     индекс,
-                    арность: процедура.параметры.len(),
-                    результат: процедура.результат.clone().map(|результат| результат.тип),
+                    // Внешний мир ничего не знает об отдельных типах.
+                    параметры: процедура.параметры.iter().map(|параметр| параметр.тип.основа().clone()).collect(),
+                    вариативные_аргументы: if процедура.вариативная {
+                        Some(вариативные_аргументы.iter().map(|тип| тип.основа().clone()).collect())
+                    } else {
+                        None
+                    },
+                    результат: процедура.результат.as_ref().map(|результат| результат.тип.основа().clone()),
                 },
                 лок,
             });
@@ -1360,17 +2171,189 @@ fn вызвать_процедуру(лок: Лок, имена: &Имена, п
     Ok(())
 }
 
-fn индексировать_массив(тип_элемента: &Тип, пп: &mut ПП, имена: &Имена, текущая_процедура: &СкомпПроцедура, локальные_имена: &ЛокальныеИмена, индекс: &Выражение, временный_размер: &mut usize) -> Результат<()> {
-    // СДЕЛАТЬ: проверку входа за границы массива/среза. Включая отрицательные числа.
+/// Ожидает адрес начала массива на стеке.
+fn индексировать_массив(тип_элемента: &Тип, размер_массива: usize, пп: &mut ПП, окружение: Окружение, индекс: &Выражение, временный_размер: &mut usize) -> Результат<()> {
+    if !пп.без_проверок {
+        пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(размер_массива), лок: индекс.лок().clone() });
+    }
+    проиндексировать(тип_элемента, пп, окружение, индекс, временный_размер)
+}
+
+/// Переписывает обращение «модуль.имя» к включённому модулю в полное
+/// имя. Возвращает None, если слева от точки не имя модуля. Переменные
+/// и параметры затеняют имена модулей.
+fn квалифицировать_имя(имена: &Имена, область: Option<(&СкомпПроцедура, &ЛокальныеИмена)>, левое: &Выражение, правое: &Выражение) -> Результат<Option<Выражение>> {
+    let Выражение::Идент(модуль) = левое else {
+        return Ok(None)
+    };
+    if !имена.включён_модуль(&модуль.текст) {
+        return Ok(None)
+    }
+    if let Some((текущая_процедура, локальные_имена)) = область {
+        if найти_значение_в_памяти(&модуль.текст, имена, текущая_процедура, локальные_имена).is_some() {
+            return Ok(None)
+        }
+    }
+    let полное_имя = |имя: &Лексема| -> Результат<Лексема> {
+        if !имена.модули[&модуль.текст].открывает(&имя.текст) {
+            диагностика!(&имя.лок, "ОШИБКА", "Модуль «{модуль}» не открывает имя «{имя}»", модуль = модуль.текст, имя = имя.текст);
+            return Err(())
+        }
+        Ok(Лексема {
+            вид: ВидЛексемы::Идент,
+            текст: format!("{}.{}", модуль.текст, имя.текст),
+            лок: имя.лок.clone(),
+        })
+    };
+    match правое {
+        Выражение::Идент(имя) => Ok(Some(Выражение::Идент(полное_имя(имя)?))),
+        Выражение::ЦепочкаВызовов{имя, цепочка_аргументов} => Ok(Some(Выражение::ЦепочкаВызовов {
+            имя: полное_имя(имя)?,
+            цепочка_аргументов: цепочка_аргументов.clone(),
+        })),
+        Выражение::ЛитералСтруктуры{имя, поля} => Ok(Some(Выражение::ЛитералСтруктуры {
+            имя: полное_имя(имя)?,
+            поля: поля.clone(),
+        })),
+        _ => {
+            диагностика!(правое.лок(), "ОШИБКА", "После имени модуля «{модуль}» ожидалось имя", модуль = модуль.текст);
+            Err(())
+        }
+    }
+}
+
+/// Разрешает вызов процедуры структуры через точку: «в.длина()» либо
+/// «Вектор2.новый(1.0, 2.0)». Возвращает полное имя процедуры и аргументы
+/// вызова, в которых получатель идёт первым, так как он передаётся как
+/// «сам». Если слева от точки не структура, у неё есть поле с таким
+/// именем, либо нет такой процедуры, возвращает None и выражение
+/// компилируется как обращение к полю.
+fn разрешить_вызов_процедуры_структуры(пп: &ПП, имена: &Имена, текущая_процедура: &СкомпПроцедура, локальные_имена: &ЛокальныеИмена, левое: &Выражение, имя: &Лексема, аргументы: &[Выражение]) -> Результат<Option<(Лексема, Vec<Выражение>)>> {
+    let полное_имя = |имя_структуры: &str| Лексема {
+        вид: ВидЛексемы::Идент,
+        текст: format!("{}.{}", имя_структуры, имя.текст),
+        лок: имя.лок.clone(),
+    };
+
+    // «модуль.Структура.процедура()»
+    let квалифицированное = match левое {
+        Выражение::Биноп{вид: ВидБинопа::Поле, левое: модуль, правое: имя_в_модуле, ..} => {
+            квалифицировать_имя(имена, Some((текущая_процедура, локальные_имена)), модуль, имя_в_модуле)?
+        }
+        _ => None,
+    };
+    let левое = квалифицированное.as_ref().unwrap_or(левое);
+    if let Выражение::Идент(имя_структуры) = левое {
+        let это_значение = найти_значение_в_памяти(&имя_структуры.текст, имена, текущая_процедура, локальные_имена).is_some()
+            || найти_константу_по_имени(&имя_структуры.текст, имена, Some(локальные_имена)).is_some();
+        if let (false, Some(структура)) = (это_значение, имена.найти_структуру(&имя_структуры.текст)) {
+            let полное_имя = полное_имя(&структура.имя.текст);
+            if !имена.процедуры.contains_key(&полное_имя.текст) {
+                диагностика!(&имя.лок, "ОШИБКА", "У структуры «{структура}» нет процедуры «{имя}»",
+                             структура = имя_структуры.текст,
+                             имя = имя.текст);
+                return Err(())
+            }
+            проверить_доступность_процедуры_структуры(имена, &структура.имя.текст, имя)?;
+            return Ok(Some((полное_имя, аргументы.to_vec())))
+        }
+    }
+
+    // Код получателя должен идти после кода остальных аргументов, поэтому
+    // здесь его тип только выводится.
+    let тип = вывести_тип_выражения(пп, имена, текущая_процедура, локальные_имена, левое)?;
+    if let Тип::Структура(имя_структуры) = &тип {
+        if имена.структуры[имя_структуры].поля.contains_key(&имя.текст) {
+            return Ok(None)
+        }
+        let полное_имя = полное_имя(имя_структуры);
+        if имена.процедуры.contains_key(&полное_имя.текст) {
+            проверить_доступность_процедуры_структуры(имена, имя_структуры, имя)?;
+            let mut аргументы_вызова = vec![левое.clone()];
+            аргументы_вызова.extend(аргументы.iter().cloned());
+            return Ok(Some((полное_имя, аргументы_вызова)))
+        }
+    }
+    Ok(None)
+}
+
+fn проверить_доступность_процедуры_структуры(имена: &Имена, структура: &str, имя: &Лексема) -> Результат<()> {
+    if имена.процедура_структуры_закрыта(структура, &имя.текст) {
+        диагностика!(&имя.лок, "ОШИБКА", "Процедура «{имя}» структуры «{структура}» закрыта", имя = имя.текст, структура = структура);
+        return Err(())
+    }
+    Ok(())
+}
+
+/// Ожидает на стеке адрес индексируемого массива либо среза.
+fn проиндексировать_значение(пп: &mut ПП, окружение: Окружение, лок: &Лок, тип: &Тип, аргументы: &[Выражение], временный_размер: &mut usize) -> Результат<Тип> {
+    match тип {
+        Тип::Массив{размер, тип_элемента} => {
+            let [индекс] = аргументы else {
+                диагностика!(лок, "ОШИБКА", "Некорректная размерность массива. Ожидался 1 индекс, но повстречалось {ранк}", ранк = аргументы.len());
+                return Err(());
+            };
+            индексировать_массив(тип_элемента, *размер, пп, окружение, индекс, временный_размер)?;
+            if тип_элемента.примитивный() {
+                прочитать_примитивный_тип(лок, &mut пп.код, &окружение.имена.структуры, &тип_элемента)?;
+            }
+            Ok(*тип_элемента.clone())
+        }
+        Тип::Срез{тип_элемента} => {
+            let [индекс] = аргументы else {
+                диагностика!(лок, "ОШИБКА", "Некорректная размерность среза. Ожидался 1 индекс, но повстречалось {ранк}", ранк = аргументы.len());
+                return Err(());
+            };
+            индексировать_срез(тип_элемента, лок, пп, окружение, индекс, временный_размер)?;
+            if тип_элемента.примитивный() {
+                прочитать_примитивный_тип(лок, &mut пп.код, &окружение.имена.структуры, &тип_элемента)?;
+            }
+            Ok(*тип_элемента.clone())
+        }
+        _ => {
+            диагностика!(лок, "ОШИБКА", "Невозможно индексировать тип «{тип}»", тип = тип.текст());
+            Err(())
+        }
+    }
+}
+
+/// Ожидает адрес среза на стеке.
+fn индексировать_срез(тип_элемента: &Тип, лок: &Лок, пп: &mut ПП, окружение: Окружение, индекс: &Выражение, временный_размер: &mut usize) -> Результат<()> {
+    if !пп.без_проверок {
+        пп.код.push(Инструкция{ вид: ВидИнструкции::Продублировать, лок: лок.clone()});
+        пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(СРЕЗ_РАЗМЕР_СМЕЩЕНИЕ), лок: лок.clone()});
+        пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: лок.clone()});
+        пп.код.push(Инструкция{ вид: ВидИнструкции::Прочитать64, лок: лок.clone()});
+        пп.код.push(Инструкция{ вид: ВидИнструкции::Поменять, лок: лок.clone()});
+    }
+    пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(СРЕЗ_АДРЕС_СМЕЩЕНИЕ), лок: лок.clone()});
+    пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: лок.clone()});
+    пп.код.push(Инструкция{ вид: ВидИнструкции::Прочитать64, лок: лок.clone()});
+    if !пп.без_проверок {
+        пп.код.push(Инструкция{ вид: ВидИнструкции::Поменять, лок: лок.clone()});
+    }
+    проиндексировать(тип_элемента, пп, окружение, индекс, временный_размер)
+}
+
+/// Ожидает на стеке адрес начала элементов, а если проверки включены,
+/// то еще и количество элементов поверх него.
+fn проиндексировать(тип_элемента: &Тип, пп: &mut ПП, окружение: Окружение, индекс: &Выражение, временный_размер: &mut usize) -> Результат<()> {
+    let Окружение{имена, текущая_процедура, локальные_имена} = окружение;
     let тип_индекса = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, индекс, временный_размер)?;
     match тип_индекса {
         Тип::Нат64 => {
+            if !пп.без_проверок {
+                пп.код.push(Инструкция{ вид: ВидИнструкции::ПроверитьГраницы{знаковый: false}, лок: индекс.лок().clone() });
+            }
             пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(тип_элемента.размер(&имена.структуры)), лок: индекс.лок().clone() });
             пп.код.push(Инструкция{ вид: ВидИнструкции::НатУмножение, лок: индекс.лок().clone() });
             пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: индекс.лок().clone() });
             Ok(())
         }
         Тип::Цел64 => {
+            if !пп.без_проверок {
+                пп.код.push(Инструкция{ вид: ВидИнструкции::ПроверитьГраницы{знаковый: true}, лок: индекс.лок().clone() });
+            }
             пп.код.push(Инструкция{ вид: ВидИнструкции::Целое(тип_элемента.размер(&имена.структуры) as i64), лок: индекс.лок().clone() });
             пп.код.push(Инструкция{ вид: ВидИнструкции::ЦелУмножение, лок: индекс.лок().clone() });
             пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: индекс.лок().clone() });
@@ -1383,9 +2366,123 @@ fn индексировать_массив(тип_элемента: &Тип, п
     }
 }
 
+/// Пределы целого заданной разрядности.
+pub fn пределы_целого(знаковый: bool, разрядность: u32) -> (i64, u64) {
+    if знаковый {
+        (i64::MIN >> (64 - разрядность), (i64::MAX >> (64 - разрядность)) as u64)
+    } else {
+        (0, u64::MAX >> (64 - разрядность))
+    }
+}
+
+/// Пределы вещ32, которые после отбрасывания дробной части попадают в
+/// [мин..макс]: (нижний предел, включается ли нижний предел, верхний
+/// предел). Верхний предел никогда не включается.
+pub fn пределы_вещественных(мин: i64, макс: u64) -> (f64, bool, f64) {
+    // Для больших по модулю пределов единица теряется при округлении,
+    // но сами пределы при этом представимы точно.
+    let нижний = мин as f64 - 1.0;
+    (нижний, нижний == мин as f64, макс as f64 + 1.0)
+}
+
+/// Ожидает операнды операции на стеке.
+fn проверить_арифметику(пп: &mut ПП, операция: Арифметика, тип: &Тип, лок: &Лок) {
+    if пп.без_проверок {
+        return
+    }
+    if let Some((знаковый, разрядность)) = тип.целое() {
+        пп.код.push(Инструкция{ вид: ВидИнструкции::ПроверитьАрифметику{операция, знаковый, разрядность}, лок: лок.clone() });
+    }
+}
+
+/// Ожидает конвертируемое значение на стеке.
+fn проверить_диапазон(пп: &mut ПП, тип_значения: &Тип, тип_цели: &Тип, лок: &Лок) {
+    if пп.без_проверок {
+        return
+    }
+    let Some((знаковый_цели, разрядность_цели)) = тип_цели.целое() else {
+        return
+    };
+    let (мин, макс) = if *тип_цели == Тип::Символ {
+        (0, char::MAX as u64)
+    } else {
+        пределы_целого(знаковый_цели, разрядность_цели)
+    };
+    if let Some((знаковый, разрядность)) = тип_значения.целое() {
+        let (мин_значения, макс_значения) = пределы_целого(знаковый, разрядность);
+        if мин_значения < мин || макс_значения > макс {
+            пп.код.push(Инструкция{ вид: ВидИнструкции::ПроверитьДиапазон{знаковый, мин, макс}, лок: лок.clone() });
+        }
+    } else if let Тип::Вещ32 = тип_значения {
+        пп.код.push(Инструкция{ вид: ВидИнструкции::ПроверитьДиапазонВещ32{мин, макс}, лок: лок.clone() });
+    } else if let Тип::Вещ64 = тип_значения {
+        пп.код.push(Инструкция{ вид: ВидИнструкции::ПроверитьДиапазонВещ64{мин, макс}, лок: лок.clone() });
+    }
+}
+
+/// Ожидает на стеке значение, сконвертированное в символ и уже
+/// проверенное на вхождение в пределы Юникода.
+fn проверить_суррогат(пп: &mut ПП, тип_значения: &Тип, лок: &Лок) {
+    if пп.без_проверок {
+        return
+    }
+    if let Some((знаковый, разрядность)) = тип_значения.целое() {
+        let (_, макс) = пределы_целого(знаковый, разрядность);
+        if макс < *СУРРОГАТЫ.start() as u64 {
+            return
+        }
+    }
+    пп.код.push(Инструкция{ вид: ВидИнструкции::ПроверитьСимвол, лок: лок.clone() });
+}
+
+/// Отбрасывает старшие биты целого на вершине стека, которые не
+/// помещаются в тип. Знаковые целые затем расширяются до 64 бит, так
+/// как именно в таком виде они хранятся на стеке.
+fn обрезать_до_разрядности(пп: &mut ПП, тип: &Тип, лок: &Лок) {
+    let Some((знаковый, разрядность)) = тип.целое() else {
+        return
+    };
+    if разрядность >= 64 {
+        return
+    }
+    let (_, маска) = пределы_целого(false, разрядность);
+    пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(маска as usize), лок: лок.clone() });
+    пп.код.push(Инструкция{ вид: ВидИнструкции::БитИ, лок: лок.clone() });
+    if знаковый {
+        // (х либо знак) - знак переносит знаковый бит во все старшие.
+        let знак = 1usize << (разрядность - 1);
+        пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(знак), лок: лок.clone() });
+        пп.код.push(Инструкция{ вид: ВидИнструкции::БитЛибо, лок: лок.clone() });
+        пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(знак), лок: лок.clone() });
+        пп.код.push(Инструкция{ вид: ВидИнструкции::НатВычитание, лок: лок.clone() });
+    }
+}
+
+/// С проверками результат арифметики и так помещается в тип, иначе
+/// его нужно обрезать.
+fn обрезать_без_проверок(пп: &mut ПП, тип: &Тип, лок: &Лок) {
+    if пп.без_проверок {
+        обрезать_до_разрядности(пп, тип, лок);
+    }
+}
+
+/// Примитивные значения равны, когда совпадают их биты. Вещественные
+/// сравниваются по IEEE 754.
+fn инструкция_равенства(тип: &Тип) -> ВидИнструкции {
+    match тип.основа() {
+        Тип::Вещ32 => ВидИнструкции::Вещ32Равно,
+        Тип::Вещ64 => ВидИнструкции::Вещ64Равно,
+        _ => ВидИнструкции::НатРавно,
+    }
+}
+
 enum Локация {
     Глобальная,
     Стековая,
+    /// На стеке лежит адрес значения. Так передаётся «сам».
+    Ссылка,
+    /// Инициализированные данные: составные константы и строковые литералы.
+    Данные,
 }
 
 impl Локация {
@@ -1398,10 +2495,33 @@ impl Локация {
             Локация::Стековая => {
                 пп.код.push(Инструкция{ вид: ВидИнструкции::Кадр(смещение), лок: лок.clone() });
             }
+            Локация::Ссылка => {
+                пп.код.push(Инструкция{ вид: ВидИнструкции::Кадр(смещение), лок: лок.clone() });
+                пп.код.push(Инструкция{ вид: ВидИнструкции::Прочитать64, лок: лок.clone() });
+            }
+            Локация::Данные => {
+                пп.код.push(Инструкция{ вид: ВидИнструкции::ГлобальныеДанные(смещение), лок: лок.clone() });
+            }
         }
     }
 }
 
+/// Находит значение, расположенное в памяти: переменную, параметр
+/// либо составную константу.
+fn найти_значение_в_памяти(имя: &str, имена: &Имена, текущая_процедура: &СкомпПроцедура, локальные_имена: &ЛокальныеИмена) -> Option<(Тип, Локация, i32)> {
+    if let Some((переменная, локация)) = найти_переменную_по_имени(имя, имена, текущая_процедура, локальные_имена) {
+        return Some((переменная.тип.clone(), локация, переменная.смещение));
+    }
+
+    if let Some(константа) = найти_константу_по_имени(имя, имена, Some(локальные_имена)) {
+        if !константа.тип.примитивный() {
+            return Some((константа.тип.clone(), Локация::Данные, константа.значение_или_адрес as i32));
+        }
+    }
+
+    None
+}
+
 fn найти_константу_по_имени<'ы>(имя: &str, имена: &'ы Имена, локальные_имена: Option<&'ы ЛокальныеИмена>) -> Option<&'ы СкомпКонстанта> {
     if let Some(локальные_имена) = локальные_имена {
         if let Some(константа) = локальные_имена.найти_константу_по_имени(имя) {
@@ -1409,23 +2529,36 @@ fn найти_константу_по_имени<'ы>(имя: &str, имена:
         }
     }
 
-    if let Some(константа) = имена.константы.get(имя) {
+    if let Some(константа) = имена.найти_константу(имя) {
         return Some(константа);
     }
 
     None
 }
 
+/// Находит переменную для чтения и отмечает её использованной.
 fn найти_переменную_по_имени<'ы>(имя: &str, имена: &'ы Имена, текущая_процедура: &'ы СкомпПроцедура, локальные_имена: &'ы ЛокальныеИмена) -> Option<(&'ы СкомпПеременная, Локация)> {
+    let (переменная, локация) = найти_переменную_для_записи(имя, имена, текущая_процедура, локальные_имена)?;
+    переменная.использована.set(true);
+    Some((переменная, локация))
+}
+
+/// Присваивание переменной целиком не делает её использованной:
+/// переменная, в которую только пишут, так же бесполезна, как и
+/// неиспользованная.
+fn найти_переменную_для_записи<'ы>(имя: &str, имена: &'ы Имена, текущая_процедура: &'ы СкомпПроцедура, локальные_имена: &'ы ЛокальныеИмена) -> Option<(&'ы СкомпПеременная, Локация)> {
     if let Some(переменная) = локальные_имена.найти_переменную_по_имени(имя) {
         return Some((переменная, Локация::Стековая));
     }
 
     if let Some(параметр) = текущая_процедура.параметры.iter().find(|параметр| параметр.имя.текст == имя) {
+        if параметр.ссылка {
+            return Some((параметр, Локация::Ссылка));
+        }
         return Some((параметр, Локация::Стековая));
     }
 
-    if let Some(переменная) = имена.переменные.get(имя) {
+    if let Some(переменная) = имена.найти_переменную(имя) {
         return Some((переменная, Локация::Глобальная));
     }
 
@@ -1438,35 +2571,48 @@ fn найти_переменную_по_имени<'ы>(имя: &str, имена
 ///
 /// Особенность левых выражений в том, что они являются ссылками.
 fn скомпилировать_левое_выражение(пп: &mut ПП, имена: &Имена, текущая_процедура: &СкомпПроцедура, локальные_имена: &ЛокальныеИмена, выражение: &Выражение, временный_размер: &mut usize) -> Результат<Тип> {
+    let тип = скомпилировать_левое_выражение_как_есть(пп, имена, текущая_процедура, локальные_имена, выражение, временный_размер)?;
+    if тип.отравлен() {
+        return Err(())
+    }
+    Ok(тип)
+}
+
+fn скомпилировать_левое_выражение_как_есть(пп: &mut ПП, имена: &Имена, текущая_процедура: &СкомпПроцедура, локальные_имена: &ЛокальныеИмена, выражение: &Выражение, временный_размер: &mut usize) -> Результат<Тип> {
     match выражение {
         Выражение::Лог(лексема, _) => {
             диагностика!(&лексема.лок, "ОШИБКА", "Логическое выражение не может находиться в левой части присваивания");
             Err(())
         }
-        Выражение::ЦелЧисло(лексема, _) => {
+        Выражение::ЦелЧисло(лексема, ..) => {
             диагностика!(&лексема.лок, "ОШИБКА", "Целое число не может находиться в левой части присваивания");
             Err(())
         }
-        Выражение::НатЧисло(лексема, _) => {
+        Выражение::НатЧисло(лексема, ..) => {
             диагностика!(&лексема.лок, "ОШИБКА", "Натуральное число не может находиться в левой части присваивания");
             Err(())
         }
-        Выражение::ВещЧисло(лексема, _) => {
+        Выражение::ВещЧисло(лексема, ..) => {
             диагностика!(&лексема.лок, "ОШИБКА", "Вещественное число не может находиться в левой части присваивания");
             Err(())
         }
-        Выражение::Строка(лексема) => {
-            // А мы точно хотим разрешать подобные вещи?
-            сделать!(&лексема.лок, "Присваивание строковых литералов");
+        Выражение::Символ(лексема, _) => {
+            диагностика!(&лексема.лок, "ОШИБКА", "Символ не может находиться в левой части присваивания");
+            Err(())
+        }
+        Выражение::Строка(лексема) | Выражение::ИндексСтроки{строка: лексема, ..} => {
+            // Одинаковые литералы размещаются в памяти лишь однажды, поэтому
+            // изменение одного из них изменило бы и все остальные.
+            диагностика!(&лексема.лок, "ОШИБКА", "Строковый литерал не может находиться в левой части присваивания");
             Err(())
         }
         Выражение::Идент(имя) => {
-            if let Some((переменная, локация)) = найти_переменную_по_имени(&имя.текст, имена, текущая_процедура, локальные_имена) {
+            if let Some((переменная, локация)) = найти_переменную_для_записи(&имя.текст, имена, текущая_процедура, локальные_имена) {
                 локация.протолкнуть_смещение(пп, &имя.лок, переменная.смещение);
                 return Ok(переменная.тип.clone());
             }
 
-            if let Some(_) = найти_константу_по_имени(&имя.текст, имена, Some(локальные_имена)) {
+            if найти_константу_по_имени(&имя.текст, имена, Some(локальные_имена)).is_some() {
                 диагностика!(&имя.лок, "ОШИБКА", "Константы не могут находиться в данном месте");
                 return Err(());
             }
@@ -1476,9 +2622,12 @@ fn скомпилировать_левое_выражение(пп: &mut ПП, 
         }
         Выражение::ЦепочкаВызовов{имя, цепочка_аргументов} => {
             let mut тип = if let Some((переменная, локация)) = найти_переменную_по_имени(&имя.текст, имена, текущая_процедура, локальные_имена) {
+                if переменная.тип.отравлен() {
+                    return Err(())
+                }
                 локация.протолкнуть_смещение(пп, &имя.лок, переменная.смещение);
                 переменная.тип.clone()
-            } else if let Some(_) = найти_константу_по_имени(&имя.текст, имена, Some(локальные_имена)) {
+            } else if найти_константу_по_имени(&имя.текст, имена, Some(локальные_имена)).is_some() {
                 диагностика!(&имя.лок, "ОШИБКА", "Константы не могут находиться в данном месте.");
                 return Err(());
             } else {
@@ -1489,13 +2638,13 @@ fn скомпилировать_левое_выражение(пп: &mut ПП, 
 
             for аргументы in цепочка_аргументов.iter() {
                 match &тип {
-                    Тип::Массив{тип_элемента, ..} => {
+                    Тип::Массив{размер, тип_элемента} => {
                         if аргументы.выражения.len() != 1 {
                             диагностика!(&имя.лок, "ОШИБКА", "Некорректная размерность массива. Ожидался 1 индекс, но повстречалось {ранк}", ранк = аргументы.выражения.len());
                             return Err(());
                         };
                         let индекс = &аргументы.выражения[0];
-                        индексировать_массив(&*тип_элемента, пп, имена, текущая_процедура, локальные_имена, индекс, временный_размер)?;
+                        индексировать_массив(&*тип_элемента, *размер, пп, Окружение{имена, текущая_процедура, локальные_имена}, индекс, временный_размер)?;
                         тип = *тип_элемента.clone();
                     }
                     Тип::Срез{тип_элемента} => {
@@ -1504,10 +2653,7 @@ fn скомпилировать_левое_выражение(пп: &mut ПП, 
                             return Err(());
                         };
                         let индекс = &аргументы.выражения[0];
-                        пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(СРЕЗ_АДРЕС_СМЕЩЕНИЕ), лок: имя.лок.clone()});
-                        пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: имя.лок.clone()});
-                        пп.код.push(Инструкция{ вид: ВидИнструкции::Прочитать64, лок: имя.лок.clone()});
-                        индексировать_массив(&*тип_элемента, пп, имена, текущая_процедура, локальные_имена, индекс, временный_размер)?;
+                        индексировать_срез(&*тип_элемента, &имя.лок, пп, Окружение{имена, текущая_процедура, локальные_имена}, индекс, временный_размер)?;
                         тип = *тип_элемента.clone();
                     }
                     _ => {
@@ -1524,7 +2670,15 @@ fn скомпилировать_левое_выражение(пп: &mut ПП, 
         }
         Выражение::Биноп{ключ, вид, левое, правое} => {
             if let ВидБинопа::Поле = вид {
+                if let Some(выражение) = квалифицировать_имя(имена, Some((текущая_процедура, локальные_имена)), &левое, &правое)? {
+                    return скомпилировать_левое_выражение(пп, имена, текущая_процедура, локальные_имена, &выражение, временный_размер);
+                }
                 let левый_тип = скомпилировать_левое_выражение(пп, имена, текущая_процедура, локальные_имена, &левое, временный_размер)?;
+                if let Выражение::Идент(имя) = &**левое {
+                    // Запись в поле, как и в элемент, может идти через срез
+                    // в чужую память, поэтому считается использованием.
+                    найти_переменную_по_имени(&имя.текст, имена, текущая_процедура, локальные_имена);
+                }
                 match &**правое {
                     Выражение::Идент(имя_поля) => {
                         match &левый_тип {
@@ -1572,13 +2726,13 @@ fn скомпилировать_левое_выражение(пп: &mut ПП, 
 
                         for аргументы in цепочка_аргументов.iter() {
                             match &тип {
-                                Тип::Массив{тип_элемента, ..} => {
+                                Тип::Массив{размер, тип_элемента} => {
                                     if аргументы.выражения.len() != 1 {
                                         диагностика!(&имя_поля.лок, "ОШИБКА", "Некорректная размерность массива. Ожидался 1 индекс, но повстречалось {ранк}", ранк = аргументы.выражения.len());
                                         return Err(());
                                     };
                                     let индекс = &аргументы.выражения[0];
-                                    индексировать_массив(&*тип_элемента, пп, имена, текущая_процедура, локальные_имена, индекс, временный_размер)?;
+                                    индексировать_массив(&*тип_элемента, *размер, пп, Окружение{имена, текущая_процедура, локальные_имена}, индекс, временный_размер)?;
                                     тип = *тип_элемента.clone();
                                 }
                                 Тип::Срез{тип_элемента} => {
@@ -1588,10 +2742,7 @@ fn скомпилировать_левое_выражение(пп: &mut ПП, 
                                     };
 
                                     let индекс = &аргументы.выражения[0];
-                                    пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(СРЕЗ_АДРЕС_СМЕЩЕНИЕ), лок: имя_поля.лок.clone()});
-                                    пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: имя_поля.лок.clone()});
-                                    пп.код.push(Инструкция{ вид: ВидИнструкции::Прочитать64, лок: имя_поля.лок.clone()});
-                                    индексировать_массив(&*тип_элемента, пп, имена, текущая_процедура, локальные_имена, индекс, временный_размер)?;
+                                    индексировать_срез(&*тип_элемента, &имя_поля.лок, пп, Окружение{имена, текущая_процедура, локальные_имена}, индекс, временный_размер)?;
                                     тип = *тип_элемента.clone();
                                 }
                                 _ => {
@@ -1617,6 +2768,19 @@ fn скомпилировать_левое_выражение(пп: &mut ПП, 
             диагностика!(&ключ.лок, "ОШИБКА", "Отрицание не может находится в левой части присваивания");
             Err(())
         }
+        Выражение::Если{ключ, ..} => {
+            диагностика!(&ключ.лок, "ОШИБКА", "Условное выражение не может находиться в левой части присваивания");
+            Err(())
+        }
+        Выражение::ЛитералСтруктуры{имя: ключ, ..} | Выражение::ЛитералМассива{ключ, ..} => {
+            диагностика!(&ключ.лок, "ОШИБКА", "Литерал не может находиться в левой части присваивания");
+            Err(())
+        }
+        Выражение::Кортеж{ключ, ..} => {
+            диагностика!(&ключ.лок, "ОШИБКА", "Кортеж не может находиться в левой части присваивания");
+            диагностика!(&ключ.лок, "ИНФО", "Чтобы разобрать кортеж на части, объявите переменные: пер (а, б) := ...;");
+            Err(())
+        }
     }
 }
 
@@ -1643,6 +2807,28 @@ fn скомпилировать_утверждение(пп: &mut ПП, имен
             }
             Ok(())
         }
+        Утверждение::СоставноеПрисваивание{ключ, вид, левое, правое} => {
+            let mut временный_размер = 0;
+            let левый_тип = скомпилировать_левое_выражение(пп, имена, текущая_процедура, локальные_имена, левое, &mut временный_размер)?;
+            if !левый_тип.примитивный() {
+                диагностика!(&левое.лок(), "ОШИБКА", "Составное присваивание не применимо к значениям типа «{}»", левый_тип.текст());
+                return Err(());
+            }
+            // Адрес левой части вычисляется один раз: одна копия нужна для
+            // чтения текущего значения, а вторая для записи результата.
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Продублировать, лок: ключ.лок.clone() });
+            прочитать_примитивный_тип(&ключ.лок, &mut пп.код, &имена.структуры, &левый_тип)?;
+            let правый_тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, правое, &mut временный_размер)?;
+            проверить_типы_операндов(вид, правое.лок(), &левый_тип, &правый_тип)?;
+            let тип = скомпилировать_операцию(пп, ключ, вид, левое.лок(), левый_тип.clone())?;
+            проверить_типы(&ключ.лок, &левый_тип, &тип)?;
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Поменять, лок: ключ.лок.clone() });
+            записать_тип(&ключ.лок, &mut пп.код, &имена.структуры, &левый_тип)?;
+            if временный_размер > 0 {
+                пп.код.push(Инструкция{ вид: ВидИнструкции::ОсвободитьСоСтека(временный_размер as u64), лок: ключ.лок.clone() })
+            }
+            Ok(())
+        }
         Утверждение::Выражение{выражение} => {
             match выражение {
                 Выражение::ЦепочкаВызовов{имя, цепочка_аргументов} => {
@@ -1658,6 +2844,20 @@ fn скомпилировать_утверждение(пп: &mut ПП, имен
                         }
                     }
                 }
+                Выражение::Биноп{вид: ВидБинопа::Поле, левое, правое, ..} => {
+                    if let Some(выражение) = квалифицировать_имя(имена, Some((текущая_процедура, локальные_имена)), левое, правое)? {
+                        return скомпилировать_утверждение(пп, имена, текущая_процедура, локальные_имена, &Утверждение::Выражение{выражение}, отложенные_возвраты);
+                    }
+                    if let Выражение::ЦепочкаВызовов{имя, цепочка_аргументов} = &**правое {
+                        if let [аргументы] = цепочка_аргументов.as_slice() {
+                            if let Some((имя_процедуры, аргументы)) = разрешить_вызов_процедуры_структуры(пп, имена, текущая_процедура, локальные_имена, левое, имя, &аргументы.выражения)? {
+                                return скомпилировать_утверждение_вызова_функции_по_имени(пп, имена, текущая_процедура, локальные_имена, &имя_процедуры, &аргументы);
+                            }
+                        }
+                    }
+                    диагностика!(выражение.лок(), "ОШИБКА", "Подобные выражения в контексте утверждений не возможны. Можно только вызывать функции, которые не возвращают аргументов");
+                    Err(())
+                }
                 _ => {
                     диагностика!(выражение.лок(), "ОШИБКА", "Подобные выражения в контексте утверждений не возможны. Можно только вызывать функции, которые не возвращают аргументов");
                     Err(())
@@ -1666,8 +2866,10 @@ fn скомпилировать_утверждение(пп: &mut ПП, имен
         }
         Утверждение::Если{ключ, условие, тело, иначе} => {
             let mut временный_размер = 0;
-            let тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &условие, &mut временный_размер)?;
-            проверить_типы(&условие.лок(), &Тип::Лог, &тип)?;
+            // Тело компилируется, даже если условие не удалось, чтобы
+            // сообщить и об ошибках в теле.
+            let mut результат = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &условие, &mut временный_размер)
+                .and_then(|тип| проверить_типы(&условие.лок(), &Тип::Лог, &тип));
             пп.код.push(Инструкция{ вид: ВидИнструкции::ЛогОтрицание, лок: ключ.лок.clone() });
             if временный_размер > 0 {
                 пп.код.push(Инструкция{ вид: ВидИнструкции::ОсвободитьСоСтека(временный_размер as u64), лок: ключ.лок.clone() });
@@ -1676,17 +2878,8 @@ fn скомпилировать_утверждение(пп: &mut ПП, имен
             пп.код.push(Инструкция{ вид: ВидИнструкции::Ноп, лок: ключ.лок.clone() });
 
             локальные_имена.стек_областей.push(Default::default());
-            for утверждение in тело.iter() {
-                скомпилировать_утверждение(пп, имена, текущая_процедура, локальные_имена, утверждение, отложенные_возвраты)?;
-            }
-            let размер_области = локальные_имена
-                .стек_областей
-                .pop()
-                .expect("Тело условия «если» не трогает область которую мы протолкнули")
-                .размер_переменных_на_стеке;
-            if размер_области > 0 {
-                пп.код.push(Инструкция{ вид: ВидИнструкции::ОсвободитьСоСтека(размер_области as u64), лок: ключ.лок.clone() });
-            }
+            результат = результат.and(скомпилировать_утверждения(пп, имена, текущая_процедура, локальные_имена, тело, отложенные_возвраты));
+            закрыть_область(пп, локальные_имена, &ключ.лок);
 
 
             if иначе.len() == 0 {
@@ -1700,22 +2893,13 @@ fn скомпилировать_утверждение(пп: &mut ПП, имен
                 пп.код[точка_условного_прыжка].вид = ВидИнструкции::УсловныйПрыжок(точка_иначе);
 
                 локальные_имена.стек_областей.push(Default::default());
-                for утверждение in иначе.iter() {
-                    скомпилировать_утверждение(пп, имена, текущая_процедура, локальные_имена, утверждение, отложенные_возвраты)?;
-                }
-                let размер_области = локальные_имена
-                    .стек_областей
-                    .pop()
-                    .expect("Тело условия «если» не трогает область которую мы протолкнули")
-                    .размер_переменных_на_стеке;
-                if размер_области > 0 {
-                    пп.код.push(Инструкция{ вид: ВидИнструкции::ОсвободитьСоСтека(размер_области as u64), лок: ключ.лок.clone() });
-                }
+                результат = результат.and(скомпилировать_утверждения(пп, имена, текущая_процедура, локальные_имена, иначе, отложенные_возвраты));
+                закрыть_область(пп, локальные_имена, &ключ.лок);
 
                 let точка_выхода = пп.код.len();
                 пп.код[точка_конца_тела].вид = ВидИнструкции::Прыжок(точка_выхода);
             }
-            Ok(())
+            результат
         }
         Утверждение::Вернуть{ключ, выражение} => {
             if let Some(выражение) = выражение {
@@ -1750,8 +2934,8 @@ fn скомпилировать_утверждение(пп: &mut ПП, имен
         Утверждение::Пока{ключ, условие, тело} => {
             let точка_условия = пп.код.len();
             let mut временный_размер = 0;
-            let тип = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &условие, &mut временный_размер)?;
-            проверить_типы(&условие.лок(), &Тип::Лог, &тип)?;
+            let mut результат = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &условие, &mut временный_размер)
+                .and_then(|тип| проверить_типы(&условие.лок(), &Тип::Лог, &тип));
             пп.код.push(Инструкция{ вид: ВидИнструкции::ЛогОтрицание, лок: ключ.лок.clone() });
             if временный_размер > 0 {
                 пп.код.push(Инструкция{ вид: ВидИнструкции::ОсвободитьСоСтека(временный_размер as u64), лок: ключ.лок.clone() });
@@ -1760,138 +2944,215 @@ fn скомпилировать_утверждение(пп: &mut ПП, имен
 
             пп.код.push(Инструкция{ вид: ВидИнструкции::Ноп, лок: ключ.лок.clone() });
             локальные_имена.стек_областей.push(Default::default());
-            for утверждение in тело.iter() {
-                скомпилировать_утверждение(пп, имена, текущая_процедура, локальные_имена, утверждение, отложенные_возвраты)?;
-            }
-            let размер_области = локальные_имена
-                .стек_областей
-                .pop()
-                .expect("Тело цикла «пока» не трогает область которую мы протолкнули")
-                .размер_переменных_на_стеке;
-            if размер_области > 0 {
-                пп.код.push(Инструкция{ вид: ВидИнструкции::ОсвободитьСоСтека(размер_области as u64), лок: ключ.лок.clone() });
-            }
+            результат = результат.and(скомпилировать_утверждения(пп, имена, текущая_процедура, локальные_имена, тело, отложенные_возвраты));
+            закрыть_область(пп, локальные_имена, &ключ.лок);
 
             пп.код.push(Инструкция{ вид: ВидИнструкции::Прыжок(точка_условия), лок: ключ.лок.clone() });
             let точка_выхода = пп.код.len();
             пп.код[точка_условного_прыжка].вид = ВидИнструкции::УсловныйПрыжок(точка_выхода);
-            Ok(())
+            результат
         }
-        Утверждение::Для{ключ, индекс, нижняя_граница, верхняя_граница, тело} => {
+        Утверждение::Для{ключ, индекс, нижняя_граница, верхняя_граница, включая_конец, шаг, тело} => {
+            let шаг = match шаг {
+                Some(шаг) => {
+                    if let Some(имя) = найти_переменную_в_выражении(шаг, имена, текущая_процедура, локальные_имена) {
+                        диагностика!(&имя.лок, "ОШИБКА", "Шаг цикла «для» должен быть известен во время компиляции, но зависит от переменной «{имя}»",
+                                     имя = имя.текст);
+                        return Err(());
+                    }
+                    let значение = скомпилировать_константное_выражение(пп, имена, Some(локальные_имена), шаг)?;
+                    проверить_типы(шаг.лок(), &Тип::Цел64, &значение.тип)?;
+                    let значение = i64::from_le_bytes(значение.байты[..8].try_into().unwrap());
+                    if значение == 0 {
+                        диагностика!(шаг.лок(), "ОШИБКА", "Шаг цикла «для» не может быть равен нулю");
+                        return Err(());
+                    }
+                    значение
+                }
+                None => 1,
+            };
+
             локальные_имена.стек_областей.push(Default::default());
+            let смещение_индекса = объявить_локальную_переменную(пп, имена, текущая_процедура, локальные_имена, индекс, Тип::Цел64, false)?;
+            // Верхняя граница вычисляется один раз перед началом цикла.
+            let смещение_конца = объявить_локальную_переменную(пп, имена, текущая_процедура, локальные_имена, &скрытое_имя(ключ, "конец"), Тип::Цел64, false)?;
+            инициализировать_переменную(пп, имена, текущая_процедура, локальные_имена, смещение_индекса, &Тип::Цел64, нижняя_граница)?;
+            инициализировать_переменную(пп, имена, текущая_процедура, локальные_имена, смещение_конца, &Тип::Цел64, верхняя_граница)?;
 
-            let размер_всех_локальных_переменных = локальные_имена
-                .стек_областей
-                .iter()
-                .map(|область| область.размер_переменных_на_стеке)
-                .sum::<usize>();
+            // Проверка условия выхода из цикла
+            let точка_условия = пп.код.len();
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Кадр(смещение_индекса), лок: индекс.лок.clone() });
+            прочитать_примитивный_тип(&индекс.лок, &mut пп.код, &имена.структуры, &Тип::Цел64)?;
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Кадр(смещение_конца), лок: ключ.лок.clone() });
+            прочитать_примитивный_тип(&ключ.лок, &mut пп.код, &имена.структуры, &Тип::Цел64)?;
+            let выход = match (шаг > 0, *включая_конец) {
+                (true, true)   => ВидИнструкции::ЦелБольше,
+                (true, false)  => ВидИнструкции::ЦелБольшеРавно,
+                (false, true)  => ВидИнструкции::ЦелМеньше,
+                (false, false) => ВидИнструкции::ЦелМеньшеРавно,
+            };
+            пп.код.push(Инструкция{ вид: выход, лок: ключ.лок.clone() });
+            let точка_условного_прыжка = пп.код.len();
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Ноп, лок: ключ.лок.clone() });
 
-            let тип_переменной = Тип::Цел64;
-            let размер = выравнять_размер_по_слову(тип_переменной.размер(&имена.структуры));
-            пп.код.push(Инструкция{ вид: ВидИнструкции::ВыделитьНаСтеке(размер as u64), лок: ключ.лок.clone() });
-            let смещение =
-                -(2*(РАЗМЕР_СЛОВА as i32))                  // пропускаем кадр и адрес возврата
-                -(размер as i32)                            // сама переменная
-                -(размер_всех_локальных_переменных as i32); // пропускаем все предыдущие локальные переменные
+            скомпилировать_тело_цикла(пп, имена, текущая_процедура, локальные_имена, ключ, тело, отложенные_возвраты)?;
 
-            let текущая_область_видимости = локальные_имена
+            // Выход, если следующий индекс окажется за концом. Проверка идёт
+            // до инкремента, потому что индекс вблизи границ цел64 мог бы
+            // переполниться и никогда не дойти до конца. Внутри цикла индекс
+            // не заходит за конец, поэтому расстояние до конца неотрицательно.
+            let (уменьшаемое, вычитаемое) = if шаг > 0 {
+                ((смещение_конца, &ключ.лок), (смещение_индекса, &индекс.лок))
+            } else {
+                ((смещение_индекса, &индекс.лок), (смещение_конца, &ключ.лок))
+            };
+            for (смещение, лок) in [уменьшаемое, вычитаемое] {
+                пп.код.push(Инструкция{ вид: ВидИнструкции::Кадр(смещение), лок: лок.clone() });
+                прочитать_примитивный_тип(лок, &mut пп.код, &имена.структуры, &Тип::Цел64)?;
+            }
+            пп.код.push(Инструкция{ вид: ВидИнструкции::НатВычитание, лок: ключ.лок.clone() });
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(шаг.unsigned_abs() as usize), лок: ключ.лок.clone() });
+            let последний_шаг = if *включая_конец { ВидИнструкции::НатМеньше } else { ВидИнструкции::НатМеньшеРавно };
+            пп.код.push(Инструкция{ вид: последний_шаг, лок: ключ.лок.clone() });
+            let точка_последнего_шага = пп.код.len();
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Ноп, лок: ключ.лок.clone() });
+
+            // Инкремент
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Кадр(смещение_индекса), лок: индекс.лок.clone() });
+            прочитать_примитивный_тип(&индекс.лок, &mut пп.код, &имена.структуры, &Тип::Цел64)?;
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Целое(шаг), лок: ключ.лок.clone() });
+            пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: ключ.лок.clone() });
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Кадр(смещение_индекса), лок: индекс.лок.clone() });
+            записать_тип(&индекс.лок, &mut пп.код, &имена.структуры, &Тип::Цел64)?;
+
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Прыжок(точка_условия), лок: ключ.лок.clone() });
+            let точка_выхода = пп.код.len();
+            пп.код[точка_условного_прыжка].вид = ВидИнструкции::УсловныйПрыжок(точка_выхода);
+            пп.код[точка_последнего_шага].вид = ВидИнструкции::УсловныйПрыжок(точка_выхода);
+
+            закрыть_область(пп, локальные_имена, &ключ.лок);
+            Ok(())
+        }
+        Утверждение::ДляЭлементов{ключ, индекс, элемент, коллекция, тело} => {
+            локальные_имена.стек_областей.push(Default::default());
+
+            // Коллекция вычисляется один раз, а её временная память должна
+            // жить до конца цикла, поэтому она становится частью области цикла.
+            let mut временный_размер = 0;
+            let тип_коллекции = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, коллекция, &mut временный_размер)?;
+            локальные_имена
                 .стек_областей
                 .last_mut()
-                .expect("Компиляция процедуры обязана создать хотя бы одну область видимости");
+                .expect("Область цикла была только что протолкнута")
+                .размер_переменных_на_стеке += временный_размер;
 
-            // СДЕЛАТЬ: Проверка затенения имен локальными переменными.
-            if let Some(существующая_переменная) = текущая_область_видимости.переменные.get(&индекс.текст) {
-                диагностика!(&индекс.лок, "ОШИБКА", "Переопределение локальной переменной «{имя}»", имя = индекс.текст);
-                диагностика!(&существующая_переменная.имя.лок, "ИНФО", "Первое определение находится здесь");
-                return Err(());
-            }
-            текущая_область_видимости.переменные.insert(индекс.текст.clone(), СкомпПеременная {
-                имя: индекс.clone(),
-                тип: тип_переменной,
-                смещение
-            });
-            текущая_область_видимости.размер_переменных_на_стеке += размер;
+            let тип_элемента = match &тип_коллекции {
+                Тип::Массив{тип_элемента, ..} | Тип::Срез{тип_элемента} => (**тип_элемента).clone(),
+                _ => {
+                    диагностика!(коллекция.лок(), "ОШИБКА", "Перебирать можно только массивы и срезы, но повстречался тип «{тип}»", тип = тип_коллекции.текст());
+                    return Err(());
+                }
+            };
 
-            if let Some((переменная, локация)) = найти_переменную_по_имени(&индекс.текст, имена, текущая_процедура, локальные_имена) {
-                проверить_типы(&индекс.лок, &Тип::Цел64, &переменная.тип)?;
+            let смещение_данных = объявить_локальную_переменную(пп, имена, текущая_процедура, локальные_имена, &скрытое_имя(ключ, "данные"), Тип::Нат64, false)?;
+            let смещение_размера = объявить_локальную_переменную(пп, имена, текущая_процедура, локальные_имена, &скрытое_имя(ключ, "размер"), Тип::Цел64, false)?;
+            let смещение_индекса = match индекс {
+                Some(индекс) => объявить_локальную_переменную(пп, имена, текущая_процедура, локальные_имена, индекс, Тип::Цел64, false)?,
+                None => объявить_локальную_переменную(пп, имена, текущая_процедура, локальные_имена, &скрытое_имя(ключ, "индекс"), Тип::Цел64, false)?,
+            };
+            let смещение_элемента = объявить_локальную_переменную(пп, имена, текущая_процедура, локальные_имена, элемент, тип_элемента.clone(), false)?;
 
-                // Инициализация индексной переменной
-                let mut временный_размер = 0;
-                let тип_нижней_границы = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &нижняя_граница, &mut временный_размер)?;
-                проверить_типы(&нижняя_граница.лок(), &переменная.тип, &тип_нижней_границы)?;
-                локация.протолкнуть_смещение(пп, &индекс.лок, переменная.смещение);
-                записать_тип(&индекс.лок, &mut пп.код, &имена.структуры, &переменная.тип)?;
-                if временный_размер > 0 {
-                    пп.код.push(Инструкция{ вид: ВидИнструкции::ОсвободитьСоСтека(временный_размер as u64), лок: ключ.лок.clone() });
-                    временный_размер = 0;
+            match &тип_коллекции {
+                Тип::Массив{размер, ..} => {
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Кадр(смещение_данных), лок: ключ.лок.clone() });
+                    записать_тип(&ключ.лок, &mut пп.код, &имена.структуры, &Тип::Нат64)?;
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(*размер), лок: ключ.лок.clone() });
                 }
-
-                // Проверка условия выхода из цикла
-                let точка_условия = пп.код.len();
-                локация.протолкнуть_смещение(пп, &индекс.лок, переменная.смещение);
-                прочитать_примитивный_тип(&индекс.лок, &mut пп.код, &имена.структуры, &переменная.тип)?;
-                let тип_верхней_границы = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &верхняя_граница, &mut временный_размер)?;
-                проверить_типы(&верхняя_граница.лок(), &переменная.тип, &тип_верхней_границы)?;
-                пп.код.push(Инструкция{ вид: ВидИнструкции::ЦелБольше, лок: ключ.лок.clone() });
-                let точка_условного_прыжка = пп.код.len();
-                пп.код.push(Инструкция{ вид: ВидИнструкции::Ноп, лок: ключ.лок.clone() });
-                if временный_размер > 0 {
-                    пп.код.push(Инструкция{ вид: ВидИнструкции::ОсвободитьСоСтека(временный_размер as u64), лок: ключ.лок.clone() });
+                Тип::Срез{..} => {
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Продублировать, лок: ключ.лок.clone() });
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(СРЕЗ_АДРЕС_СМЕЩЕНИЕ), лок: ключ.лок.clone() });
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: ключ.лок.clone() });
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Прочитать64, лок: ключ.лок.clone() });
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Кадр(смещение_данных), лок: ключ.лок.clone() });
+                    записать_тип(&ключ.лок, &mut пп.код, &имена.структуры, &Тип::Нат64)?;
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(СРЕЗ_РАЗМЕР_СМЕЩЕНИЕ), лок: ключ.лок.clone() });
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: ключ.лок.clone() });
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Прочитать64, лок: ключ.лок.clone() });
                 }
+                _ => unreachable!(),
+            }
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Кадр(смещение_размера), лок: ключ.лок.clone() });
+            записать_тип(&ключ.лок, &mut пп.код, &имена.структуры, &Тип::Цел64)?;
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Целое(0), лок: ключ.лок.clone() });
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Кадр(смещение_индекса), лок: ключ.лок.clone() });
+            записать_тип(&ключ.лок, &mut пп.код, &имена.структуры, &Тип::Цел64)?;
 
-                let переменная_тип = переменная.тип.clone();
-                let переменная_смещение = переменная.смещение;
-
-                // Тело
-                локальные_имена.стек_областей.push(Default::default());
-                for утверждение in тело.iter() {
-                    скомпилировать_утверждение(пп, имена, текущая_процедура, локальные_имена, утверждение, отложенные_возвраты)?;
-                }
-                let размер_области = локальные_имена
-                    .стек_областей
-                    .pop()
-                    .expect("Тело цикла «для» не трогает область которую мы протолкнули")
-                    .размер_переменных_на_стеке;
-                if размер_области > 0 {
-                    пп.код.push(Инструкция{ вид: ВидИнструкции::ОсвободитьСоСтека(размер_области as u64), лок: ключ.лок.clone() });
-                }
-
-                // Инкремент
-                локация.протолкнуть_смещение(пп, &индекс.лок, переменная_смещение);
-                прочитать_примитивный_тип(&индекс.лок, &mut пп.код, &имена.структуры, &переменная_тип)?;
-                пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(1), лок: ключ.лок.clone() });
-                пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: ключ.лок.clone() });
-                локация.протолкнуть_смещение(пп, &индекс.лок, переменная_смещение);
-                записать_тип(&индекс.лок, &mut пп.код, &имена.структуры, &переменная_тип)?;
+            // Проверка условия выхода из цикла
+            let точка_условия = пп.код.len();
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Кадр(смещение_индекса), лок: ключ.лок.clone() });
+            прочитать_примитивный_тип(&ключ.лок, &mut пп.код, &имена.структуры, &Тип::Цел64)?;
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Кадр(смещение_размера), лок: ключ.лок.clone() });
+            прочитать_примитивный_тип(&ключ.лок, &mut пп.код, &имена.структуры, &Тип::Цел64)?;
+            пп.код.push(Инструкция{ вид: ВидИнструкции::ЦелБольшеРавно, лок: ключ.лок.clone() });
+            let точка_условного_прыжка = пп.код.len();
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Ноп, лок: ключ.лок.clone() });
 
+            // Копирование текущего элемента в переменную
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Кадр(смещение_данных), лок: элемент.лок.clone() });
+            прочитать_примитивный_тип(&элемент.лок, &mut пп.код, &имена.структуры, &Тип::Нат64)?;
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Кадр(смещение_индекса), лок: элемент.лок.clone() });
+            прочитать_примитивный_тип(&элемент.лок, &mut пп.код, &имена.структуры, &Тип::Цел64)?;
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(тип_элемента.размер(&имена.структуры)), лок: элемент.лок.clone() });
+            пп.код.push(Инструкция{ вид: ВидИнструкции::НатУмножение, лок: элемент.лок.clone() });
+            пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: элемент.лок.clone() });
+            if тип_элемента.примитивный() {
+                прочитать_примитивный_тип(&элемент.лок, &mut пп.код, &имена.структуры, &тип_элемента)?;
+            }
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Кадр(смещение_элемента), лок: элемент.лок.clone() });
+            записать_тип(&элемент.лок, &mut пп.код, &имена.структуры, &тип_элемента)?;
 
-                пп.код.push(Инструкция{ вид: ВидИнструкции::Прыжок(точка_условия), лок: ключ.лок.clone() });
-                let точка_выхода = пп.код.len();
-                пп.код[точка_условного_прыжка].вид = ВидИнструкции::УсловныйПрыжок(точка_выхода);
+            скомпилировать_тело_цикла(пп, имена, текущая_процедура, локальные_имена, ключ, тело, отложенные_возвраты)?;
 
-                let размер_области = локальные_имена
-                    .стек_областей
-                    .pop()
-                    .expect("Тело цикла «для» не трогает область которую мы протолкнули")
-                    .размер_переменных_на_стеке;
-                if размер_области > 0 {
-                    пп.код.push(Инструкция{ вид: ВидИнструкции::ОсвободитьСоСтека(размер_области as u64), лок: ключ.лок.clone() });
-                }
+            // Инкремент
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Кадр(смещение_индекса), лок: ключ.лок.clone() });
+            прочитать_примитивный_тип(&ключ.лок, &mut пп.код, &имена.структуры, &Тип::Цел64)?;
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Целое(1), лок: ключ.лок.clone() });
+            пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: ключ.лок.clone() });
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Кадр(смещение_индекса), лок: ключ.лок.clone() });
+            записать_тип(&ключ.лок, &mut пп.код, &имена.структуры, &Тип::Цел64)?;
 
-                return Ok(())
-            }
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Прыжок(точка_условия), лок: ключ.лок.clone() });
+            let точка_выхода = пп.код.len();
+            пп.код[точка_условного_прыжка].вид = ВидИнструкции::УсловныйПрыжок(точка_выхода);
 
-            диагностика!(&индекс.лок, "ОШИБКА", "Неизвестная переменная «{имя}»", имя = индекс.текст);
-            Err(())
+            закрыть_область(пп, локальные_имена, &ключ.лок);
+            Ok(())
         }
         Утверждение::ДекларацияПеременной{ключ, имя, тип, значение} => {
+            // Одинаковые строковые литералы разделяют одни данные. Поэтому
+            // переменная, инициализированная литералом, получает собственную
+            // копию его байт, иначе запись в неё изменила бы все такие литералы.
+            let копия_литерала = match значение {
+                Some(Выражение::Строка(строка)) if !строка.текст.is_empty() => {
+                    let тип_копии = Тип::Массив{размер: строка.текст.len(), тип_элемента: Box::new(Тип::Нат8)};
+                    let смещение_копии = объявить_локальную_переменную(пп, имена, текущая_процедура, локальные_имена, &скрытое_имя(имя, "копия"), тип_копии, false)?;
+                    Some((смещение_копии, строка.текст.len()))
+                }
+                _ => None,
+            };
+
             let размер_всех_локальных_переменных = локальные_имена
                 .стек_областей
                 .iter()
                 .map(|область| область.размер_переменных_на_стеке)
                 .sum::<usize>();
 
-            let тип_переменной = скомпилировать_тип(пп, тип, имена, Some(локальные_имена))?;
+            // Переменная объявляется, даже если не удалось скомпилировать её
+            // тип или значение, иначе ошибкой стало бы и каждое её упоминание.
+            let (тип_переменной, mut результат) = match скомпилировать_тип(пп, тип, имена, Some(локальные_имена)) {
+                Ok(тип) => (тип, Ok(())),
+                Err(()) => (Тип::Ошибка, Err(())),
+            };
             let размер = выравнять_размер_по_слову(тип_переменной.размер(&имена.структуры));
             пп.код.push(Инструкция{ вид: ВидИнструкции::ВыделитьНаСтеке(размер as u64), лок: ключ.лок.clone() });
             let смещение =
@@ -1899,67 +3160,129 @@ fn скомпилировать_утверждение(пп: &mut ПП, имен
                 -(размер as i32)                            // сама переменная
                 -(размер_всех_локальных_переменных as i32); // пропускаем все предыдущие локальные переменные
 
-            if let Some(значение) = значение {
+            if let (Some(значение), Ok(())) = (значение, &результат) {
                 let mut временный_размер = 0;
-                let тип_значения = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, значение, &mut временный_размер)?;
-                проверить_типы(&значение.лок(), &тип_переменной, &тип_значения)?;
-                пп.код.push(Инструкция{ вид: ВидИнструкции::Кадр(смещение), лок: ключ.лок.clone() });
-                записать_тип(&ключ.лок, &mut пп.код, &имена.структуры, &тип_переменной)?;
+                результат = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, значение, &mut временный_размер)
+                    .and_then(|тип_значения| проверить_типы(&значение.лок(), &тип_переменной, &тип_значения));
+                if результат.is_ok() {
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::Кадр(смещение), лок: ключ.лок.clone() });
+                    записать_тип(&ключ.лок, &mut пп.код, &имена.структуры, &тип_переменной)?;
+                    if let Some((смещение_копии, длина)) = копия_литерала {
+                        // Копируем байты литерала, на которые пока указывает
+                        // срез переменной, и перенаправляем срез на копию.
+                        пп.код.push(Инструкция{ вид: ВидИнструкции::Кадр(смещение), лок: ключ.лок.clone() });
+                        пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(СРЕЗ_АДРЕС_СМЕЩЕНИЕ), лок: ключ.лок.clone() });
+                        пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: ключ.лок.clone() });
+                        пп.код.push(Инструкция{ вид: ВидИнструкции::Прочитать64, лок: ключ.лок.clone() });
+                        пп.код.push(Инструкция{ вид: ВидИнструкции::Кадр(смещение_копии), лок: ключ.лок.clone() });
+                        пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(длина), лок: ключ.лок.clone() });
+                        пп.код.push(Инструкция{ вид: ВидИнструкции::СкопироватьПамять, лок: ключ.лок.clone() });
+                        пп.код.push(Инструкция{ вид: ВидИнструкции::Кадр(смещение_копии), лок: ключ.лок.clone() });
+                        пп.код.push(Инструкция{ вид: ВидИнструкции::Кадр(смещение), лок: ключ.лок.clone() });
+                        пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(СРЕЗ_АДРЕС_СМЕЩЕНИЕ), лок: ключ.лок.clone() });
+                        пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: ключ.лок.clone() });
+                        пп.код.push(Инструкция{ вид: ВидИнструкции::Записать64, лок: ключ.лок.clone() });
+                    }
+                }
                 if временный_размер > 0 {
                     пп.код.push(Инструкция{ вид: ВидИнструкции::ОсвободитьСоСтека(временный_размер as u64), лок: ключ.лок.clone() });
                 }
             }
 
-            let текущая_область_видимости = локальные_имена
-                .стек_областей
-                .last_mut()
-                .expect("Компиляция процедуры обязана создать хотя бы одну область видимости");
-            // СДЕЛАТЬ: Проверка затенения имен локальными переменными.
-            if let Some(существующая_переменная) = текущая_область_видимости.переменные.get(&имя.текст) {
+            if let Some(существующая_переменная) = локальные_имена.найти_переменную_в_текущей_области(&имя.текст) {
                 диагностика!(&имя.лок, "ОШИБКА", "Переопределение локальной переменной «{имя}»", имя = имя.текст);
                 диагностика!(&существующая_переменная.имя.лок, "ИНФО", "Первое определение находится здесь");
                 return Err(());
             }
+            проверить_затенение(имена, текущая_процедура, локальные_имена, имя);
+            let текущая_область_видимости = локальные_имена
+                .стек_областей
+                .last_mut()
+                .expect("Компиляция процедуры обязана создать хотя бы одну область видимости");
             текущая_область_видимости.переменные.insert(имя.текст.clone(), СкомпПеременная {
                 имя: имя.clone(),
                 тип: тип_переменной,
-                смещение
+                смещение,
+                ссылка: false,
+                использована: Cell::new(false),
             });
             текущая_область_видимости.размер_переменных_на_стеке += размер;
 
+            результат
+        }
+        Утверждение::ДекларацияКортежа{ключ, имена: имена_переменных, значение} => {
+            // Типы переменных выводятся из значения, а место под переменные
+            // должно быть выделено до временной памяти значения. Поэтому
+            // тип значения выводится до его компиляции.
+            let тип = вывести_тип_выражения(пп, имена, текущая_процедура, локальные_имена, значение);
+            let Ok(тип) = тип else {
+                отравить_переменные(пп, имена, текущая_процедура, локальные_имена, имена_переменных);
+                return Err(());
+            };
+            let Тип::Кортеж(типы) = &тип else {
+                диагностика!(значение.лок(), "ОШИБКА", "Разобрать на части можно только кортеж, но повстречался тип «{тип}»", тип = тип.текст());
+                отравить_переменные(пп, имена, текущая_процедура, локальные_имена, имена_переменных);
+                return Err(());
+            };
+            if типы.len() != имена_переменных.len() {
+                диагностика!(&ключ.лок, "ОШИБКА", "Кортеж типа «{тип}» содержит {количество} {элементов}, но объявлено {переменных_количество} {переменных}",
+                             тип = тип.текст(),
+                             количество = типы.len(),
+                             элементов = ЧИСУЩ_ЭЛЕМЕНТ.текст(типы.len()),
+                             переменных_количество = имена_переменных.len(),
+                             переменных = ЧИСУЩ_ПЕРЕМЕННАЯ.текст(имена_переменных.len()));
+                отравить_переменные(пп, имена, текущая_процедура, локальные_имена, имена_переменных);
+                return Err(());
+            }
+
+            let mut смещения_переменных = Vec::new();
+            for (имя, тип_элемента) in имена_переменных.iter().zip(типы.iter()) {
+                смещения_переменных.push(объявить_локальную_переменную(пп, имена, текущая_процедура, локальные_имена, имя, тип_элемента.clone(), true)?);
+            }
+
+            let mut временный_размер = 0;
+            let _ = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, значение, &mut временный_размер)?;
+            let смещения_элементов = Тип::смещения_элементов(типы, &имена.структуры);
+            for ((тип_элемента, смещение_элемента), смещение_переменной) in типы.iter().zip(смещения_элементов).zip(смещения_переменных) {
+                пп.код.push(Инструкция{ вид: ВидИнструкции::Продублировать, лок: ключ.лок.clone() });
+                пп.код.push(Инструкция{ вид: ВидИнструкции::Натуральное(смещение_элемента), лок: ключ.лок.clone() });
+                пп.код.push(Инструкция{ вид: ВидИнструкции::НатСложение, лок: ключ.лок.clone() });
+                if тип_элемента.примитивный() {
+                    прочитать_примитивный_тип(&ключ.лок, &mut пп.код, &имена.структуры, тип_элемента)?;
+                }
+                пп.код.push(Инструкция{ вид: ВидИнструкции::Кадр(смещение_переменной), лок: ключ.лок.clone() });
+                записать_тип(&ключ.лок, &mut пп.код, &имена.структуры, тип_элемента)?;
+            }
+            пп.код.push(Инструкция{ вид: ВидИнструкции::Вытолкнуть, лок: ключ.лок.clone() });
+            if временный_размер > 0 {
+                пп.код.push(Инструкция{ вид: ВидИнструкции::ОсвободитьСоСтека(временный_размер as u64), лок: ключ.лок.clone() });
+            }
             Ok(())
         }
         Утверждение::ДекларацияКонстанты{имя, значение, ..} => {
-            let (байты_значения, тип) = скомпилировать_константное_выражение(пп, имена, Some(локальные_имена), значение)?;
+            let значение = скомпилировать_константное_выражение(пп, имена, Some(локальные_имена), значение);
 
-            let текущая_область_видимости = локальные_имена
-                .стек_областей
-                .last_mut()
-                .expect("Компиляция процедуры обязана создать хотя бы одну область видимости");
-            // СДЕЛАТЬ: Проверка затенения имен локальными константами.
-            if let Some(существующая_константа) = текущая_область_видимости.константы.get(&имя.текст) {
+            if let Some(существующая_константа) = локальные_имена.найти_константу_в_текущей_области(&имя.текст) {
                 диагностика!(&имя.лок, "ОШИБКА", "Переопределение локальной константы «{имя}»", имя = имя.текст);
                 диагностика!(&существующая_константа.имя.лок, "ИНФО", "Первое определение находится здесь");
                 return Err(());
             }
+            проверить_затенение(имена, текущая_процедура, локальные_имена, имя);
+            let текущая_область_видимости = локальные_имена
+                .стек_областей
+                .last_mut()
+                .expect("Компиляция процедуры обязана создать хотя бы одну область видимости");
 
-            if тип.примитивный() {
-                let значение = u64::from_le_bytes(байты_значения.as_slice().try_into().unwrap());
-                текущая_область_видимости.константы.insert(имя.текст.clone(), СкомпКонстанта {
-                    имя: имя.clone(),
-                    тип,
-                    значение_или_адрес: значение,
-                });
-            } else {
-                let адрес = пп.иниц_данные.len();
-                пп.иниц_данные.extend(байты_значения);
-
-                текущая_область_видимости.константы.insert(имя.текст.clone(), СкомпКонстанта {
-                    имя: имя.clone(),
-                    тип,
-                    значение_или_адрес: адрес as u64,
-                });
-            }
+            let Ok(значение) = значение else {
+                текущая_область_видимости.константы.insert(имя.текст.clone(), СкомпКонстанта::отравленная(имя));
+                return Err(());
+            };
+            let тип = значение.тип.clone();
+            текущая_область_видимости.константы.insert(имя.текст.clone(), СкомпКонстанта {
+                имя: имя.clone(),
+                тип,
+                значение_или_адрес: пп.разместить_константу(значение),
+            });
 
             Ok(())
         }
@@ -1971,13 +3294,15 @@ fn скомпилировать_утверждение(пп: &mut ПП, имен
                 диагностика!(выражение.лок(), "ОШИБКА", "Использовать вилку пока что можно только для примитивных типов");
                 return Err(());
             }
+            let mut результат = Ok(());
             let mut прыжки_в_конец = Vec::new();
             for ветка in ветки {
                 пп.код.push(Инструкция{ вид: ВидИнструкции::Продублировать, лок: выражение.лок().clone() });
                 let mut временный_размер_ветки: usize = 0;
-                let тип_ветки = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &ветка.выражение, &mut временный_размер_ветки)?;
-                проверить_типы(ветка.выражение.лок(), &тип_корня, &тип_ветки)?;
-                пп.код.push(Инструкция{ вид: ВидИнструкции::НатРавно, лок: ветка.ключ.лок.clone() });
+                результат = результат.and(
+                    скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, &ветка.выражение, &mut временный_размер_ветки)
+                        .and_then(|тип_ветки| проверить_типы(ветка.выражение.лок(), &тип_корня, &тип_ветки)));
+                пп.код.push(Инструкция{ вид: инструкция_равенства(&тип_корня), лок: ветка.ключ.лок.clone() });
                 пп.код.push(Инструкция{ вид: ВидИнструкции::ЛогОтрицание, лок: ветка.ключ.лок.clone() });
                 пп.код.push(Инструкция{ вид: ВидИнструкции::ОсвободитьСоСтека(временный_размер_ветки as u64), лок: ветка.ключ.лок.clone() });
                 let прыжок_на_следующую_ветку = пп.код.len();
@@ -1987,17 +3312,8 @@ fn скомпилировать_утверждение(пп: &mut ПП, имен
                 пп.код.push(Инструкция{ вид: ВидИнструкции::Вытолкнуть, лок: ветка.ключ.лок.clone() });
 
                 локальные_имена.стек_областей.push(Default::default());
-                for утверждение in ветка.тело.iter() {
-                    скомпилировать_утверждение(пп, имена, текущая_процедура, локальные_имена, утверждение, отложенные_возвраты)?;
-                }
-                let размер_области = локальные_имена
-                    .стек_областей
-                    .pop()
-                    .expect("Тело ветки вилки не трогает область которую мы протолкнули")
-                    .размер_переменных_на_стеке;
-                if размер_области > 0 {
-                    пп.код.push(Инструкция{ вид: ВидИнструкции::ОсвободитьСоСтека(размер_области as u64), лок: ветка.ключ.лок.clone() });
-                }
+                результат = результат.and(скомпилировать_утверждения(пп, имена, текущая_процедура, локальные_имена, &ветка.тело, отложенные_возвраты));
+                закрыть_область(пп, локальные_имена, &ветка.ключ.лок);
 
                 прыжки_в_конец.push(пп.код.len());
                 пп.код.push(Инструкция{ вид: ВидИнструкции::Прыжок(0), лок: ветка.ключ.лок.clone() });
@@ -2014,35 +3330,183 @@ fn скомпилировать_утверждение(пп: &mut ПП, имен
 
             if let Some((любое_ключ, любое_тело)) = любое {
                 локальные_имена.стек_областей.push(Default::default());
-                for утверждение in любое_тело.iter() {
-                    скомпилировать_утверждение(пп, имена, текущая_процедура, локальные_имена, утверждение, отложенные_возвраты)?;
-                }
-                let размер_области = локальные_имена
-                    .стек_областей
-                    .pop()
-                    .expect("Тело ветки вилки не трогает область которую мы протолкнули")
-                    .размер_переменных_на_стеке;
-                if размер_области > 0 {
-                    пп.код.push(Инструкция{ вид: ВидИнструкции::ОсвободитьСоСтека(размер_области as u64), лок: любое_ключ.лок.clone() });
+                результат = результат.and(скомпилировать_утверждения(пп, имена, текущая_процедура, локальные_имена, любое_тело, отложенные_возвраты));
+                закрыть_область(пп, локальные_имена, &любое_ключ.лок);
+            }
+
+            let конец_вилки = пп.код.len();
+            for прыжок in прыжки_в_конец {
+                if let ВидИнструкции::Прыжок(адрес) = &mut пп.код[прыжок].вид {
+                    *адрес = конец_вилки;
+                } else {
+                    unreachable!();
                 }
             }
 
-            let конец_вилки = пп.код.len();
-            for прыжок in прыжки_в_конец {
-                if let ВидИнструкции::Прыжок(адрес) = &mut пп.код[прыжок].вид {
-                    *адрес = конец_вилки;
-                } else {
-                    unreachable!();
-                }
+            результат
+        }
+    }
+}
+
+/// Объявляет переменные отравленного типа вместо тех, которые не удалось
+/// объявить из-за ошибки.
+fn отравить_переменные(пп: &mut ПП, имена: &Имена, текущая_процедура: &СкомпПроцедура, локальные_имена: &mut ЛокальныеИмена, имена_переменных: &[Лексема]) {
+    for имя in имена_переменных {
+        let _ = объявить_локальную_переменную(пп, имена, текущая_процедура, локальные_имена, имя, Тип::Ошибка, false);
+    }
+}
+
+/// Имя служебной переменной, к которой нельзя обратиться из программы,
+/// так как идентификаторы не могут содержать пробелов.
+fn скрытое_имя(ключ: &Лексема, имя: &str) -> Лексема {
+    Лексема {
+        вид: ВидЛексемы::Идент,
+        текст: format!("<{имя} {ключ}>", имя = имя, ключ = ключ.текст),
+        лок: ключ.лок.clone(),
+    }
+}
+
+/// Выделяет место под переменную в текущей области видимости и
+/// возвращает её смещение относительно кадра. О неиспользовании
+/// переменной предупреждают, только если она объявлена через «пер».
+fn объявить_локальную_переменную(пп: &mut ПП, имена: &Имена, текущая_процедура: &СкомпПроцедура, локальные_имена: &mut ЛокальныеИмена, имя: &Лексема, тип: Тип, объявлена_через_пер: bool) -> Результат<i32> {
+    let размер_всех_локальных_переменных = локальные_имена
+        .стек_областей
+        .iter()
+        .map(|область| область.размер_переменных_на_стеке)
+        .sum::<usize>();
+
+    let размер = выравнять_размер_по_слову(тип.размер(&имена.структуры));
+    let смещение =
+        -(2*(РАЗМЕР_СЛОВА as i32))                  // пропускаем кадр и адрес возврата
+        -(размер as i32)                            // сама переменная
+        -(размер_всех_локальных_переменных as i32); // пропускаем все предыдущие локальные переменные
+
+    if let Some(существующая_переменная) = локальные_имена.найти_переменную_в_текущей_области(&имя.текст) {
+        диагностика!(&имя.лок, "ОШИБКА", "Переопределение локальной переменной «{имя}»", имя = имя.текст);
+        диагностика!(&существующая_переменная.имя.лок, "ИНФО", "Первое определение находится здесь");
+        return Err(());
+    }
+    проверить_затенение(имена, текущая_процедура, локальные_имена, имя);
+    let текущая_область_видимости = локальные_имена
+        .стек_областей
+        .last_mut()
+        .expect("Компиляция процедуры обязана создать хотя бы одну область видимости");
+    пп.код.push(Инструкция{ вид: ВидИнструкции::ВыделитьНаСтеке(размер as u64), лок: имя.лок.clone() });
+    текущая_область_видимости.переменные.insert(имя.текст.clone(), СкомпПеременная {
+        имя: имя.clone(),
+        тип,
+        смещение,
+        ссылка: false,
+        использована: Cell::new(!объявлена_через_пер),
+    });
+    текущая_область_видимости.размер_переменных_на_стеке += размер;
+    Ok(смещение)
+}
+
+fn инициализировать_переменную(пп: &mut ПП, имена: &Имена, текущая_процедура: &СкомпПроцедура, локальные_имена: &ЛокальныеИмена, смещение: i32, тип: &Тип, значение: &Выражение) -> Результат<()> {
+    let mut временный_размер = 0;
+    let тип_значения = скомпилировать_выражение(пп, имена, текущая_процедура, локальные_имена, значение, &mut временный_размер)?;
+    проверить_типы(значение.лок(), тип, &тип_значения)?;
+    пп.код.push(Инструкция{ вид: ВидИнструкции::Кадр(смещение), лок: значение.лок().clone() });
+    записать_тип(значение.лок(), &mut пп.код, &имена.структуры, тип)?;
+    if временный_размер > 0 {
+        пп.код.push(Инструкция{ вид: ВидИнструкции::ОсвободитьСоСтека(временный_размер as u64), лок: значение.лок().clone() });
+    }
+    Ok(())
+}
+
+fn скомпилировать_тело_цикла(пп: &mut ПП, имена: &Имена, текущая_процедура: &СкомпПроцедура, локальные_имена: &mut ЛокальныеИмена, ключ: &Лексема, тело: &[Утверждение], отложенные_возвраты: &mut Vec<usize>) -> Результат<()> {
+    локальные_имена.стек_областей.push(Default::default());
+    let результат = скомпилировать_утверждения(пп, имена, текущая_процедура, локальные_имена, тело, отложенные_возвраты);
+    закрыть_область(пп, локальные_имена, &ключ.лок);
+    результат
+}
+
+/// Ошибка в одном утверждении не мешает компилировать следующие, чтобы
+/// сообщить обо всех ошибках сразу. Области видимости, которые не закрыло
+/// неудачное утверждение, закрываются здесь.
+fn скомпилировать_утверждения(пп: &mut ПП, имена: &Имена, текущая_процедура: &СкомпПроцедура, локальные_имена: &mut ЛокальныеИмена, утверждения: &[Утверждение], отложенные_возвраты: &mut Vec<usize>) -> Результат<()> {
+    let последнее_исполнимое = утверждения
+        .iter()
+        .position(|утверждение| блок_завершается_возвратом(std::slice::from_ref(утверждение)));
+    if let Some(индекс) = последнее_исполнимое {
+        if let Some(недостижимое) = утверждения.get(индекс + 1) {
+            if предупреждение!(недостижимое.лок(), Предупреждение::НедостижимыйКод, "Недостижимый код") {
+                диагностика!(утверждения[индекс].лок(), "ИНФО", "Исполнение не идёт дальше этого утверждения");
+            }
+        }
+    }
+
+    let mut результат = Ok(());
+    for утверждение in утверждения {
+        let глубина = локальные_имена.стек_областей.len();
+        if скомпилировать_утверждение(пп, имена, текущая_процедура, локальные_имена, утверждение, отложенные_возвраты).is_err() {
+            локальные_имена.стек_областей.truncate(глубина);
+            результат = Err(());
+            if предел_ошибок_достигнут() {
+                break
             }
+        }
+    }
+    результат
+}
 
-            Ok(())
+/// Закрывает область, протолкнутую для блока, и освобождает её переменные.
+fn закрыть_область(пп: &mut ПП, локальные_имена: &mut ЛокальныеИмена, лок: &Лок) {
+    let область = локальные_имена
+        .стек_областей
+        .pop()
+        .expect("Тело блока не трогает область которую мы протолкнули");
+    локальные_имена.неиспользованные.extend(
+        область.переменные
+            .into_values()
+            .filter(|переменная| !переменная.использована.get())
+            .map(|переменная| переменная.имя));
+    if область.размер_переменных_на_стеке > 0 {
+        пп.код.push(Инструкция{ вид: ВидИнструкции::ОсвободитьСоСтека(область.размер_переменных_на_стеке as u64), лок: лок.clone() });
+    }
+}
+
+/// Имена, начинающиеся с «_», как и «сам», могут оставаться
+/// неиспользованными без предупреждений.
+fn предупредить_о_неиспользуемых(процедура: &СкомпПроцедура, локальные_имена: &mut ЛокальныеИмена) {
+    let mut неиспользованные = std::mem::take(&mut локальные_имена.неиспользованные);
+    неиспользованные.sort_by_key(|имя| (имя.лок.строка, имя.лок.столбец));
+    for имя in неиспользованные.iter().filter(|имя| !имя.текст.starts_with('_')) {
+        предупреждение!(&имя.лок, Предупреждение::НеиспользуемаяПеременная, "Переменная «{имя}» нигде не используется", имя = имя.текст);
+    }
+    for параметр in &процедура.параметры {
+        if !параметр.использована.get() && !параметр.ссылка && !параметр.имя.текст.starts_with('_') {
+            предупреждение!(&параметр.имя.лок, Предупреждение::НеиспользуемыйПараметр, "Параметр «{имя}» процедуры «{процедура}» нигде не используется",
+                            имя = параметр.имя.текст,
+                            процедура = процедура.имя.текст);
         }
     }
 }
 
-fn выравнять_размер_по_слову(размер: usize) -> usize {
-    (размер + РАЗМЕР_СЛОВА - 1)/РАЗМЕР_СЛОВА*РАЗМЕР_СЛОВА
+/// Сообщает о затенении, если имя объявляемой локальной переменной или
+/// константы уже означает что-то в этом месте процедуры.
+fn проверить_затенение(имена: &Имена, текущая_процедура: &СкомпПроцедура, локальные_имена: &ЛокальныеИмена, имя: &Лексема) {
+    // Служебные переменные из программы не видны.
+    if имя.текст.starts_with('<') {
+        return
+    }
+    let затенённое = локальные_имена.стек_областей
+        .iter()
+        .rev()
+        .find_map(|область| {
+            область.переменные.get(&имя.текст).map(|переменная| &переменная.имя)
+                .or_else(|| область.константы.get(&имя.текст).map(|константа| &константа.имя))
+        })
+        .or_else(|| текущая_процедура.параметры.iter().find(|параметр| параметр.имя.текст == имя.текст).map(|параметр| &параметр.имя))
+        .or_else(|| имена.найти_переменную(&имя.текст).map(|переменная| &переменная.имя))
+        .or_else(|| имена.найти_константу(&имя.текст).map(|константа| &константа.имя));
+    if let Some(затенённое) = затенённое {
+        if предупреждение!(&имя.лок, Предупреждение::Затенение, "Имя «{имя}» затеняет другое определение", имя = имя.текст) {
+            диагностика!(&затенённое.лок, "ИНФО", "Затенённое имя определено здесь");
+        }
+    }
 }
 
 fn записать_примитивный_тип(лок: &Лок, код: &mut Vec<Инструкция>, размер: usize) -> Результат<()> {
@@ -2083,23 +3547,86 @@ fn прочитать_примитивный_тип(лок: &Лок, код: &mu
 }
 
 
+/// Истина, если исполнение блока не может дойти до его конца: на каждом
+/// пути встречается «вернуть» либо бесконечный цикл «пока истина».
+fn блок_завершается_возвратом(блок: &[Утверждение]) -> bool {
+    блок.iter().any(|утверждение| match утверждение {
+        Утверждение::Вернуть{..} => true,
+        Утверждение::Если{тело, иначе, ..} => блок_завершается_возвратом(тело) && блок_завершается_возвратом(иначе),
+        Утверждение::Вилка{ветки, любое, ..} => {
+            // Без ветки «любое» значение может не совпасть ни с одной веткой.
+            любое.as_ref().is_some_and(|(_, тело)| блок_завершается_возвратом(тело)) &&
+                ветки.iter().all(|ветка| блок_завершается_возвратом(&ветка.тело))
+        }
+        // Выйти из цикла можно только через «вернуть».
+        Утверждение::Пока{условие: Выражение::Лог(_, true), ..} => true,
+        Утверждение::Пока{..} |
+        Утверждение::Для{..} |
+        Утверждение::ДляЭлементов{..} |
+        Утверждение::Присваивание{..} |
+        Утверждение::СоставноеПрисваивание{..} |
+        Утверждение::Выражение{..} |
+        Утверждение::ДекларацияПеременной{..} |
+        Утверждение::ДекларацияКонстанты{..} |
+        Утверждение::ДекларацияКортежа{..} => false,
+    })
+}
+
 fn скомпилировать_процедуру(пп: &mut ПП, имена: &mut Имена, процедура: Процедура) -> Результат<()> {
+    let ключ = if let Some(владелец) = &процедура.владелец {
+        let Some(структура) = имена.структуры.get(&имена.полное_имя(&владелец.текст)) else {
+            диагностика!(&владелец.лок, "ОШИБКА", "Процедуры можно объявлять только для структур, определённых в том же модуле, но «{имя}» не является такой структурой", имя = владелец.текст);
+            return Err(())
+        };
+        let имя_процедуры = &процедура.имя.текст[владелец.текст.len() + '.'.len_utf8()..];
+        if let Some(поле) = структура.поля.get(имя_процедуры) {
+            диагностика!(&процедура.имя.лок, "ОШИБКА", "У структуры «{структура}» уже есть поле «{имя}»",
+                         структура = владелец.текст,
+                         имя = имя_процедуры);
+            диагностика!(&поле.имя.лок, "ИНФО", "Поле определено тут");
+            return Err(())
+        }
+        let ключ = format!("{}.{}", структура.имя.текст, имя_процедуры);
+        if let Some(существующая_процедура) = имена.процедуры.get(&ключ) {
+            диагностика!(&процедура.имя.лок, "ОШИБКА", "уже существует процедура с именем «{имя}»", имя = процедура.имя.текст);
+            диагностика!(&существующая_процедура.имя.лок, "ИНФО", "она определена здесь здесь. Выберите другое имя.");
+            return Err(())
+        }
+        ключ
+    } else {
+        имена.полное_имя(&процедура.имя.текст)
+    };
     let mut параметры = Vec::new();
     let mut размер_параметров_на_стеке: usize = 0;
+    // Процедура с неизвестными типами в сигнатуре всё равно объявляется,
+    // чтобы её вызовы не порождали новых ошибок.
+    let mut результат_компиляции = Ok(());
 
     for параметр in &процедура.параметры {
-        let тип = скомпилировать_тип(пп, &параметр.тип, имена, None)?;
+        let тип = скомпилировать_тип(пп, &параметр.тип, имена, None).unwrap_or_else(|()| {
+            результат_компиляции = Err(());
+            Тип::Ошибка
+        });
         let скомп_параметр = СкомпПеременная {
             имя: параметр.имя.clone(),
             тип,
             смещение: размер_параметров_на_стеке as i32,
+            ссылка: параметр.ссылка,
+            использована: Cell::new(false),
         };
-        размер_параметров_на_стеке += выравнять_размер_по_слову(скомп_параметр.тип.размер(&имена.структуры));
+        if скомп_параметр.ссылка {
+            размер_параметров_на_стеке += Тип::Нат64.размер(&имена.структуры);
+        } else {
+            размер_параметров_на_стеке += выравнять_размер_по_слову(скомп_параметр.тип.размер(&имена.структуры));
+        }
         параметры.push(скомп_параметр);
     }
     let результат = if let Some(тип) = процедура.тип_результата {
         let результат = РезультатСкомпПроцедуры {
-            тип: скомпилировать_тип(пп, &тип, имена, None)?,
+            тип: скомпилировать_тип(пп, &тип, имена, None).unwrap_or_else(|()| {
+                результат_компиляции = Err(());
+                Тип::Ошибка
+            }),
             смещение_от_кадра: размер_параметров_на_стеке as i32,
         };
         размер_параметров_на_стеке += выравнять_размер_по_слову(результат.тип.размер(&имена.структуры));
@@ -2108,20 +3635,26 @@ fn скомпилировать_процедуру(пп: &mut ПП, имена:
         None
     };
     match процедура.тело {
-        ТелоПроцедуры::Внутренее{блок} => {
+        ТелоПроцедуры::Внутренее{блок, конец} => {
+            if let Some(многоточие) = &процедура.многоточие {
+                диагностика!(&многоточие.лок, "ОШИБКА", "Вариативными могут быть только внешние процедуры.");
+                return Err(())
+            }
             let адрес = пп.код.len();
             let скомп_процедура = СкомпПроцедура{
                 имя: процедура.имя.clone(),
                 параметры,
                 точка_входа: ТочкаВхода::Внутреняя{адрес},
                 результат,
+                вариативная: false,
+                использована: Cell::new(false),
             };
 
-            if let Some(_) = имена.процедуры.insert(скомп_процедура.имя.текст.clone(), скомп_процедура) {
+            if имена.процедуры.insert(ключ.clone(), скомп_процедура).is_some() {
                 unreachable!("Проверка переопределения процедур должна происходить на этапе разбора")
             }
 
-            let скомп_процедура = имена.процедуры.get(&процедура.имя.текст).unwrap();
+            let скомп_процедура = имена.процедуры.get(&ключ).unwrap();
 
             // СДЕЛАТЬ: Если размер_параметров_на_стеке равен 0, то, наверное, не имеет никакого смысла вообще включать данную инструкцию
             // Тоже самое можно сказать и про противоположную инструкцию ОсвободитьСоВторогоСтека.
@@ -2130,21 +3663,28 @@ fn скомпилировать_процедуру(пп: &mut ПП, имена:
             пп.код.push(Инструкция{ вид: ВидИнструкции::АргументНаСтек, лок: процедура.имя.лок.clone() }); // сохраняем адрес возврата на второй стек
             for параметр in скомп_процедура.параметры.iter() {
                 пп.код.push(Инструкция{ вид: ВидИнструкции::Кадр(параметр.смещение), лок: параметр.имя.лок.clone() });
-                записать_тип(&параметр.имя.лок, &mut пп.код, &имена.структуры, &параметр.тип)?;
+                if параметр.ссылка {
+                    записать_тип(&параметр.имя.лок, &mut пп.код, &имена.структуры, &Тип::Нат64)?;
+                } else {
+                    записать_тип(&параметр.имя.лок, &mut пп.код, &имена.структуры, &параметр.тип)?;
+                }
             }
             let mut отложенные_возвраты = Vec::new();
             let mut локальные_имена = ЛокальныеИмена::default();
             локальные_имена.стек_областей.push(Default::default());
-            for утверждение in &блок {
-                скомпилировать_утверждение(пп, имена, &скомп_процедура, &mut локальные_имена, утверждение, &mut отложенные_возвраты)?;
+            результат_компиляции = результат_компиляции.and(скомпилировать_утверждения(пп, имена, &скомп_процедура, &mut локальные_имена, &блок, &mut отложенные_возвраты));
+            if let Some(результат) = &скомп_процедура.результат {
+                if !блок_завершается_возвратом(&блок) {
+                    диагностика!(&конец.лок, "ОШИБКА", "Исполнение процедуры «{имя}» может дойти до конца, не вернув результат типа «{тип}»",
+                                 имя = процедура.имя.текст,
+                                 тип = результат.тип.текст());
+                    диагностика!(&процедура.имя.лок, "ИНФО", "Процедура определена тут.");
+                    return Err(())
+                }
             }
-            let размер_области = локальные_имена
-                .стек_областей
-                .pop()
-                .expect("Тело процедуры не трогает область которую мы протолкнули")
-                .размер_переменных_на_стеке;
-            if размер_области > 0 {
-                пп.код.push(Инструкция{ вид: ВидИнструкции::ОсвободитьСоСтека(размер_области as u64), лок: процедура.имя.лок.clone() });
+            закрыть_область(пп, &mut локальные_имена, &процедура.имя.лок);
+            if результат_компиляции.is_ok() {
+                предупредить_о_неиспользуемых(скомп_процедура, &mut локальные_имена);
             }
 
             let конец_процедуры = пп.код.len();
@@ -2163,7 +3703,7 @@ fn скомпилировать_процедуру(пп: &mut ПП, имена:
                 } else {
                     пп.код.push(Инструкция{ вид: ВидИнструкции::АргументСоСтека, лок: процедура.имя.лок.clone() });
                     пп.код.push(Инструкция{ вид: ВидИнструкции::ВосстановитьКадр, лок: процедура.имя.лок.clone() });
-                    пп.код.push(Инструкция{ вид: ВидИнструкции::ОсвободитьСоСтека(размер_параметров_на_стеке as u64 - результат.тип.размер(&имена.структуры) as u64), лок: процедура.имя.лок.clone() });
+                    пп.код.push(Инструкция{ вид: ВидИнструкции::ОсвободитьСоСтека((размер_параметров_на_стеке - выравнять_размер_по_слову(результат.тип.размер(&имена.структуры))) as u64), лок: процедура.имя.лок.clone() });
                 }
             } else {
                 пп.код.push(Инструкция{ вид: ВидИнструкции::АргументСоСтека, лок: процедура.имя.лок.clone() });
@@ -2171,7 +3711,21 @@ fn скомпилировать_процедуру(пп: &mut ПП, имена:
                 пп.код.push(Инструкция{ вид: ВидИнструкции::ОсвободитьСоСтека(размер_параметров_на_стеке as u64), лок: процедура.имя.лок.clone() });
             }
             пп.код.push(Инструкция{ вид: ВидИнструкции::Возврат, лок: процедура.имя.лок.clone() });
-            Ok(())
+            результат_компиляции
+        }
+        ТелоПроцедуры::Испорченное => {
+            let скомп_процедура = СкомпПроцедура{
+                имя: процедура.имя,
+                параметры,
+                точка_входа: ТочкаВхода::Внутреняя{адрес: пп.код.len()},
+                результат,
+                вариативная: false,
+                использована: Cell::new(false),
+            };
+            if имена.процедуры.insert(ключ, скомп_процедура).is_some() {
+                unreachable!("Проверка переопределения процедур должна происходить на этапе разбора")
+            }
+            Err(())
         }
         ТелоПроцедуры::Внешнее{символ} => {
             let индекс = пп.внешние_символы.len();
@@ -2181,33 +3735,44 @@ fn скомпилировать_процедуру(пп: &mut ПП, имена:
                 параметры,
                 точка_входа: ТочкаВхода::Внешняя{индекс},
                 результат,
+                вариативная: процедура.многоточие.is_some(),
+                использована: Cell::new(false),
             };
-            if let Some(_) = имена.процедуры.insert(скомп_процедура.имя.текст.clone(), скомп_процедура) {
+            if имена.процедуры.insert(ключ, скомп_процедура).is_some() {
                 unreachable!("Проверка переопределения процедур должна происходить на этапе разбора")
             }
-            Ok(())
+            результат_компиляции
         }
     }
 }
 
-fn скомпилировать_константное_выражение(пп: &ПП, имена: &Имена, локальные_имена: Option<&ЛокальныеИмена>, выражение: &Выражение) -> Результат<(Vec<u8>, Тип)> {
+/// Максимальное количество инструкций, которое может исполнить
+/// вычисление одной константы.
+const ЛИМИТ_ШАГОВ_КОНСТАНТЫ: usize = 10_000_000;
+
+fn скомпилировать_константное_выражение(пп: &ПП, имена: &Имена, локальные_имена: Option<&ЛокальныеИмена>, выражение: &Выражение) -> Результат<ЗначениеКонстанты> {
+    if let Some((тип, значение)) = свернуть_константное_выражение(имена, локальные_имена, выражение) {
+        return Ok(ЗначениеКонстанты {
+            байты: значение.to_le_bytes().to_vec(),
+            смещение: 0,
+            указатели: vec![],
+            тип,
+        })
+    }
+    if !обращается_к_процедурам(имена, локальные_имена, выражение) {
+        return вычислить_выражение_без_процедур(пп, имена, локальные_имена, выражение)
+    }
+
     let mut временный_размер = 0;
+    // Выражение компилируется в конец уже скомпилированного кода, чтобы
+    // из него можно было вызывать уже определённые процедуры.
     let mut пп = (*пп).clone();
-    пп.код.clear();
     пп.внешние_символы.clear();
     пп.библиотеки.clear();
-    let текущая_процедура = СкомпПроцедура {
-        имя: Лексема {
-            вид: ВидЛексемы::Идент,
-            текст: "<Констатное Выражение>".to_string(),
-            лок: выражение.лок().clone(),
-        },
-        параметры: vec![],
-        результат: None,
-        точка_входа: ТочкаВхода::Внутреняя{адрес: 0},
-    };
+    let точка_входа = пп.код.len();
+    let текущая_процедура = процедура_константного_выражения(выражение, точка_входа);
+    let исходные_имена = имена;
     let mut имена = имена.clone();
-    имена.процедуры.clear();
     имена.переменные.clear();
     let mut локальные_имена = локальные_имена.cloned().unwrap_or_else(|| ЛокальныеИмена::default());
     for область in локальные_имена.стек_областей.iter_mut() {
@@ -2215,35 +3780,274 @@ fn скомпилировать_константное_выражение(пп:
         область.размер_переменных_на_стеке = 0;
     }
     let тип = скомпилировать_выражение(&mut пп, &имена, &текущая_процедура, &локальные_имена, выражение, &mut временный_размер)?;
-    if !тип.примитивный() {
-        // СДЕЛАТЬ: Компиляция константных выражений сложных типов
-        // Главная проблема здесь в том, что любое выделение
-        // инициализированных данных при компиляции константного
-        // выражения уходит во временную машину. Это особенно
-        // проблемно для строковых литералов:
-        //
-        //     конст СООБЩЕНИЕ := «Привет, «Мир»!»;
-        //
-        //     про главная() нч
-        //         печать(СООБЩЕНИЕ, «\н»);12
-        //     кц
-        //
-        // Данная программа будет сегфолтиться, т.к. сообщения
-        // «Привет, «Мир»!» в памяти главное машины не
-        // существует. Адрес константы СООБЩЕНИЯ ссылается в никуда.
-
-        диагностика!(выражение.лок(), "ОШИБКА", "На данный момент разрешена компиляция константных выражений только примитивных типов");
-        return Err(());
+    пп.код.push(Инструкция{ вид: ВидИнструкции::Возврат, лок: выражение.лок().clone() });
+    // Процедуры, вызванные из константы, используются программой.
+    for (ключ, процедура) in &имена.процедуры {
+        if процедура.использована.get() {
+            if let Some(исходная) = исходные_имена.процедуры.get(ключ) {
+                исходная.использована.set(true);
+            }
+        }
     }
+
+    // Глобальные переменные, к которым обращаются процедуры, размещаются
+    // так же, как и при завершении компиляции, чтобы машина могла
+    // распознать обращение к ним.
+    for индекс in &пп.заплатки_неиниц_указателей {
+        if let Some(Инструкция{ вид: ВидИнструкции::ГлобальныеДанные(указатель), .. }) = пп.код.get_mut(*индекс) {
+            *указатель += пп.иниц_данные.len() as i32;
+        }
+    }
+
+    вычислить_на_машине(&пп, &имена, выражение, точка_входа, тип, 1_000_000)
+}
+
+/// Выражение без вызовов процедур и обращений к переменным не нуждается
+/// в уже скомпилированном коде. Поэтому машина получает только код
+/// самого выражения и второй стек размером с его временную память.
+fn вычислить_выражение_без_процедур(пп: &ПП, имена: &Имена, локальные_имена: Option<&ЛокальныеИмена>, выражение: &Выражение) -> Результат<ЗначениеКонстанты> {
+    let mut пп = ПП {
+        код: Vec::new(),
+        иниц_данные: пп.иниц_данные.clone(),
+        размер_неиниц_данных: 0,
+        заплатки_неиниц_указателей: Vec::new(),
+        заплатки_иниц_указателей: пп.заплатки_иниц_указателей.clone(),
+        строки: пп.строки.clone(),
+        внешние_символы: HashMap::new(),
+        библиотеки: HashMap::new(),
+        без_проверок: пп.без_проверок,
+    };
+    let текущая_процедура = процедура_константного_выражения(выражение, 0);
+    let пустые_локальные_имена = ЛокальныеИмена::default();
+    let локальные_имена = локальные_имена.unwrap_or(&пустые_локальные_имена);
+    let mut временный_размер = 0;
+    let тип = скомпилировать_выражение(&mut пп, имена, &текущая_процедура, локальные_имена, выражение, &mut временный_размер)?;
     пп.код.push(Инструкция{ вид: ВидИнструкции::Возврат, лок: выражение.лок().clone() });
-    let mut машина = Машина::новая(&пп, 1024);
-    let _ = машина.интерпретировать(&имена, 0, false)?;
+    вычислить_на_машине(&пп, имена, выражение, 0, тип, временный_размер)
+}
+
+fn процедура_константного_выражения(выражение: &Выражение, точка_входа: usize) -> СкомпПроцедура {
+    СкомпПроцедура {
+        имя: Лексема {
+            вид: ВидЛексемы::Идент,
+            текст: "<Констатное Выражение>".to_string(),
+            лок: выражение.лок().clone(),
+        },
+        параметры: vec![],
+        результат: None,
+        точка_входа: ТочкаВхода::Внутреняя{адрес: точка_входа},
+        вариативная: false,
+        использована: Cell::new(false),
+    }
+}
+
+fn вычислить_на_машине(пп: &ПП, имена: &Имена, выражение: &Выражение, точка_входа: usize, тип: Тип, объём_второго_стека: usize) -> Результат<ЗначениеКонстанты> {
+    let mut машина = Машина::новая(пп, объём_второго_стека);
+    машина.вычисление_константы = true;
+    машина.лимит_шагов = Some(ЛИМИТ_ШАГОВ_КОНСТАНТЫ);
+    // Машина уже сообщила об ошибке, но не посчитала её.
+    let ошибка_вычисления = || {
+        диагностика!(выражение.лок(), "ИНФО", "Ошибка возникла при вычислении значения этой константы");
+        учесть_ошибку();
+    };
+    if let Err(()) = машина.интерпретировать(имена, точка_входа, false) {
+        ошибка_вычисления();
+        return Err(());
+    }
     let значение_или_адрес = машина.стек.pop().unwrap();
     assert!(машина.стек.len() == 1); // Содержит только адрес возврата
     if тип.примитивный() {
-        return Ok((значение_или_адрес.to_le_bytes().to_vec(), тип));
+        Ok(ЗначениеКонстанты {
+            байты: значение_или_адрес.to_le_bytes().to_vec(),
+            смещение: 0,
+            указатели: vec![],
+            тип,
+        })
     } else {
-        return Ok((машина.срез_памяти(значение_или_адрес, тип.размер(&имена.структуры))?.to_vec(), тип));
+        let mut значение = ЗначениеКонстанты {
+            байты: vec![],
+            смещение: 0,
+            указатели: vec![],
+            тип: тип.clone(),
+        };
+        match выгрузить_значение(&mut машина, &имена.структуры, значение_или_адрес, &тип, &mut значение) {
+            Ok(смещение) => значение.смещение = смещение,
+            Err(()) => {
+                ошибка_вычисления();
+                return Err(());
+            }
+        }
+        Ok(значение)
+    }
+}
+
+/// Сворачивает литералы, примитивные константы и целочисленную
+/// арифметику над ними без запуска машины. Возвращает None, если
+/// выражение сложнее, либо при его вычислении произошла бы ошибка:
+/// о ней сообщит машина.
+fn свернуть_константное_выражение(имена: &Имена, локальные_имена: Option<&ЛокальныеИмена>, выражение: &Выражение) -> Option<(Тип, u64)> {
+    match выражение {
+        Выражение::Лог(_, значение) => Some((Тип::Лог, *значение as u64)),
+        Выражение::ЦелЧисло(_, число, разрядность) => Some((Тип::целое_разрядности(true, *разрядность), *число as u64)),
+        Выражение::НатЧисло(_, число, разрядность) => Some((Тип::целое_разрядности(false, *разрядность), *число)),
+        Выражение::Идент(имя) => {
+            let константа = найти_константу_по_имени(&имя.текст, имена, локальные_имена)?;
+            if константа.тип.примитивный() && !константа.тип.отравлен() {
+                Some((константа.тип.clone(), константа.значение_или_адрес))
+            } else {
+                None
+            }
+        }
+        Выражение::УнарныйМинус{выражение, ..} => {
+            let (тип, значение) = свернуть_константное_выражение(имена, локальные_имена, выражение)?;
+            let (true, _) = тип.целое()? else {
+                return None
+            };
+            уложить_в_тип(тип, -(значение as i64 as i128))
+        }
+        Выражение::Биноп{вид, левое, правое, ..} => {
+            let (тип, левое) = свернуть_константное_выражение(имена, локальные_имена, левое)?;
+            let (правый_тип, правое) = свернуть_константное_выражение(имена, локальные_имена, правое)?;
+            let (знаковый, _) = тип.целое()?;
+            if тип != правый_тип || *тип.основа() == Тип::Символ {
+                return None
+            }
+            let (левое, правое) = if знаковый {
+                (левое as i64 as i128, правое as i64 as i128)
+            } else {
+                (левое as i128, правое as i128)
+            };
+            let значение = match вид {
+                ВидБинопа::Сложение => левое + правое,
+                ВидБинопа::Вычитание => левое - правое,
+                ВидБинопа::Умножение => левое.checked_mul(правое)?,
+                ВидБинопа::Деление => левое.checked_div(правое)?,
+                ВидБинопа::Остаток => левое.checked_rem(правое)?,
+                _ => return None,
+            };
+            уложить_в_тип(тип, значение)
+        }
+        _ => None,
+    }
+}
+
+fn уложить_в_тип(тип: Тип, значение: i128) -> Option<(Тип, u64)> {
+    let (знаковый, разрядность) = тип.целое()?;
+    let (мин, макс) = пределы_целого(знаковый, разрядность);
+    if значение < мин as i128 || значение > макс as i128 {
+        return None
+    }
+    Some((тип, значение as u64))
+}
+
+/// Может ли выражение вызвать процедуру либо обратиться к переменной.
+/// Имена, не являющиеся константами, считаются такими обращениями.
+fn обращается_к_процедурам(имена: &Имена, локальные_имена: Option<&ЛокальныеИмена>, выражение: &Выражение) -> bool {
+    let обращается = |выражение| обращается_к_процедурам(имена, локальные_имена, выражение);
+    match выражение {
+        Выражение::Лог(..) | Выражение::ЦелЧисло(..) | Выражение::НатЧисло(..) |
+        Выражение::ВещЧисло(..) | Выражение::Символ(..) | Выражение::Строка(..) => false,
+        Выражение::ИндексСтроки{аргументы, ..} => аргументы.выражения.iter().any(обращается),
+        Выражение::Идент(имя) => найти_константу_по_имени(&имя.текст, имена, локальные_имена).is_none(),
+        Выражение::ЦепочкаВызовов{..} => true,
+        Выражение::Биноп{вид: ВидБинопа::Поле, левое, правое, ..} => {
+            !matches!(&**правое, Выражение::Идент(_)) || обращается(левое)
+        }
+        Выражение::Биноп{вид: ВидБинопа::Как, левое, ..} => обращается(левое),
+        Выражение::Биноп{левое, правое, ..} => обращается(левое) || обращается(правое),
+        Выражение::УнарныйМинус{выражение, ..} | Выражение::Отрицание{выражение, ..} => обращается(выражение),
+        Выражение::Если{условие, то, иначе, ..} => обращается(условие) || обращается(то) || обращается(иначе),
+        Выражение::Кортеж{элементы, ..} | Выражение::ЛитералМассива{элементы, ..} => элементы.iter().any(обращается),
+        Выражение::ЛитералСтруктуры{поля, ..} => поля.iter().any(|(_, значение)| обращается(значение)),
+    }
+}
+
+/// Находит первую переменную, к которой обращается выражение. Значение
+/// переменной становится известно только во время исполнения.
+fn найти_переменную_в_выражении<'ы>(выражение: &'ы Выражение, имена: &Имена, текущая_процедура: &СкомпПроцедура, локальные_имена: &ЛокальныеИмена) -> Option<&'ы Лексема> {
+    let найти = |выражение: &'ы Выражение| найти_переменную_в_выражении(выражение, имена, текущая_процедура, локальные_имена);
+    match выражение {
+        Выражение::Идент(имя) => {
+            if найти_константу_по_имени(&имя.текст, имена, Some(локальные_имена)).is_some() {
+                return None;
+            }
+            найти_переменную_для_записи(&имя.текст, имена, текущая_процедура, локальные_имена).map(|_| имя)
+        }
+        Выражение::Биноп{вид: ВидБинопа::Поле | ВидБинопа::Как, левое, ..} => найти(левое),
+        Выражение::Биноп{левое, правое, ..} => найти(левое).or_else(|| найти(правое)),
+        Выражение::УнарныйМинус{выражение, ..} | Выражение::Отрицание{выражение, ..} => найти(выражение),
+        Выражение::Если{условие, то, иначе, ..} => найти(условие).or_else(|| найти(то)).or_else(|| найти(иначе)),
+        _ => None,
+    }
+}
+
+/// Копирует значение из памяти машины в байты константы вместе со всеми
+/// данными, на которые ссылаются его срезы. Возвращает смещение
+/// значения внутри байтов.
+fn выгрузить_значение(машина: &mut Машина, структуры: &HashMap<String, Структура>, адрес: usize, тип: &Тип, значение: &mut ЗначениеКонстанты) -> Результат<usize> {
+    let mut байты = машина.срез_памяти(адрес, тип.размер(структуры))?.to_vec();
+    let mut срезы = Vec::new();
+    найти_срезы(тип, 0, структуры, &mut срезы);
+    for (смещение, тип_элемента) in &срезы {
+        let размер = u64::from_le_bytes(байты[смещение+СРЕЗ_РАЗМЕР_СМЕЩЕНИЕ..смещение+СРЕЗ_РАЗМЕР_СМЕЩЕНИЕ+8].try_into().unwrap()) as usize;
+        let адрес_данных = u64::from_le_bytes(байты[смещение+СРЕЗ_АДРЕС_СМЕЩЕНИЕ..смещение+СРЕЗ_АДРЕС_СМЕЩЕНИЕ+8].try_into().unwrap()) as usize;
+        let смещение_данных = if размер == 0 {
+            значение.байты.len()
+        } else {
+            let тип_данных = Тип::Массив{размер, тип_элемента: Box::new(тип_элемента.clone())};
+            выгрузить_значение(машина, структуры, адрес_данных, &тип_данных, значение)?
+        };
+        байты[смещение+СРЕЗ_АДРЕС_СМЕЩЕНИЕ..смещение+СРЕЗ_АДРЕС_СМЕЩЕНИЕ+8].copy_from_slice(&(смещение_данных as u64).to_le_bytes());
+    }
+    let начало = значение.байты.len();
+    значение.байты.extend(байты);
+    for (смещение, _) in срезы {
+        значение.указатели.push(начало + смещение + СРЕЗ_АДРЕС_СМЕЩЕНИЕ);
+    }
+    Ok(начало)
+}
+
+/// Собирает смещения и типы элементов всех срезов внутри значения типа.
+fn найти_срезы(тип: &Тип, смещение: usize, структуры: &HashMap<String, Структура>, срезы: &mut Vec<(usize, Тип)>) {
+    match тип {
+        Тип::Срез{тип_элемента} => срезы.push((смещение, (**тип_элемента).clone())),
+        Тип::Массив{размер, тип_элемента} => {
+            let размер_элемента = тип_элемента.размер(структуры);
+            for индекс in 0..*размер {
+                найти_срезы(тип_элемента, смещение + индекс*размер_элемента, структуры, срезы);
+            }
+        }
+        Тип::Структура(имя) => {
+            let структура = структуры.get(имя).expect("Существование структуры должно быть уже проверено на этапе компиляции типа");
+            for поле in структура.поля.values() {
+                найти_срезы(&поле.тип, смещение + поле.смещение, структуры, срезы);
+            }
+        }
+        Тип::Кортеж(элементы) => {
+            for (тип_элемента, смещение_элемента) in элементы.iter().zip(Тип::смещения_элементов(элементы, структуры)) {
+                найти_срезы(тип_элемента, смещение + смещение_элемента, структуры, срезы);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn встроенный_тип(имя: &str) -> Option<Тип> {
+    match имя {
+        "цел" | "цел64" => Some(Тип::Цел64),
+        "цел8" => Some(Тип::Цел8),
+        "цел16" => Some(Тип::Цел16),
+        "цел32" => Some(Тип::Цел32),
+        "нат" | "нат64" => Some(Тип::Нат64),
+        "нат8" => Some(Тип::Нат8),
+        "нат16" => Some(Тип::Нат16),
+        "нат32" => Some(Тип::Нат32),
+        "символ" => Some(Тип::Символ),
+        "вещ" | "вещ32" => Some(Тип::Вещ32),
+        "вещ64" => Some(Тип::Вещ64),
+        "строка" => Some(Тип::Срез { тип_элемента: Box::new(Тип::Нат8) }),
+        "лог" => Some(Тип::Лог),
+        _ => None,
     }
 }
 
@@ -2253,36 +4057,36 @@ fn скомпилировать_тип(пп: &ПП, тип: &Выражение,
             диагностика!(&лексема.лок, "ОШИБКА", "Логические выражения не являться типами");
             Err(())
         }
-        Выражение::ЦелЧисло(лексема, _) => {
+        Выражение::ЦелЧисло(лексема, ..) => {
             диагностика!(&лексема.лок, "ОШИБКА", "Целое число не является являться типом");
             Err(())
         }
-        Выражение::НатЧисло(лексема, _) => {
+        Выражение::НатЧисло(лексема, ..) => {
             диагностика!(&лексема.лок, "ОШИБКА", "Натуральное число не является являться типом");
             Err(())
         }
-        Выражение::ВещЧисло(лексема, _) => {
+        Выражение::ВещЧисло(лексема, ..) => {
             диагностика!(&лексема.лок, "ОШИБКА", "Вещественное число не является являться типом");
             Err(())
         }
-        Выражение::Строка(лексема) => {
+        Выражение::Символ(лексема, _) => {
+            диагностика!(&лексема.лок, "ОШИБКА", "Символ не является являться типом");
+            Err(())
+        }
+        Выражение::Строка(лексема) | Выражение::ИндексСтроки{строка: лексема, ..} => {
             диагностика!(&лексема.лок, "ОШИБКА", "Строка не является являться типом");
             Err(())
         }
-        Выражение::Идент(лексема) => match лексема.текст.as_str() {
-            "цел" | "цел64" => Ok(Тип::Цел64),
-            "нат" | "нат64" => Ok(Тип::Нат64),
-            "нат8" => Ok(Тип::Нат8),
-            "вещ" | "вещ32" => Ok(Тип::Вещ32),
-            "строка" => Ok(Тип::Срез { тип_элемента: Box::new(Тип::Нат8) }),
-            "лог" => Ok(Тип::Лог),
-            _ => {
-                if имена.структуры.contains_key(&лексема.текст) {
-                    Ok(Тип::Структура(лексема.текст.clone()))
-                } else {
-                    диагностика!(&лексема.лок, "ОШИБКА", "Неизвестный тип «{тип}»", тип = лексема.текст);
-                    Err(())
-                }
+        Выражение::Идент(лексема) => {
+            if let Some(тип) = встроенный_тип(&лексема.текст) {
+                Ok(тип)
+            } else if let Some(структура) = имена.найти_структуру(&лексема.текст) {
+                Ok(Тип::Структура(структура.имя.текст.clone()))
+            } else if let Some(тип) = имена.найти_тип(&лексема.текст) {
+                Ok(тип.тип.clone())
+            } else {
+                диагностика!(&лексема.лок, "ОШИБКА", "Неизвестный тип «{тип}»", тип = лексема.текст);
+                Err(())
             }
         }
         Выражение::ЦепочкаВызовов{имя, цепочка_аргументов} => {
@@ -2301,9 +4105,9 @@ fn скомпилировать_тип(пп: &ПП, тип: &Выражение,
                                              аргументов = ЧИСУЩ_АРГУМЕНТ.текст(количество_аргументов));
                                 return Err(())
                             };
-                            let (байты_размера, тип_размера) = скомпилировать_константное_выражение(&пп, имена, локальные_имена, &выражение_размера)?;
-                            проверить_типы(выражение_размера.лок(), &Тип::Цел64, &тип_размера)?;
-                            let размер = usize::from_le_bytes(байты_размера.try_into().unwrap());
+                            let значение_размера = скомпилировать_константное_выражение(&пп, имена, локальные_имена, &выражение_размера)?;
+                            проверить_типы(выражение_размера.лок(), &Тип::Цел64, &значение_размера.тип)?;
+                            let размер = usize::from_le_bytes(значение_размера.байты[..8].try_into().unwrap());
                             let тип_элемента = Box::new(скомпилировать_тип(пп, &тип_элемента, имена, локальные_имена)?);
                             Ok(Тип::Массив{размер, тип_элемента})
                         }
@@ -2333,7 +4137,12 @@ fn скомпилировать_тип(пп: &ПП, тип: &Выражение,
                 }
             }
         }
-        Выражение::Биноп{ключ, ..} => {
+        Выражение::Биноп{ключ, вид, левое, правое} => {
+            if let ВидБинопа::Поле = вид {
+                if let Some(тип) = квалифицировать_имя(имена, None, левое, правое)? {
+                    return скомпилировать_тип(пп, &тип, имена, локальные_имена);
+                }
+            }
             диагностика!(&ключ.лок, "ОШИБКА", "Подобные бинарные операции не поддерживаются в типах");
             Err(())
         }
@@ -2346,130 +4155,416 @@ fn скомпилировать_тип(пп: &ПП, тип: &Выражение,
             // Хотя, это довольно таки интересна идея. Тип «не цел». Любой тип, кроме целого...
             Err(())
         }
+        Выражение::Если{ключ, ..} => {
+            диагностика!(&ключ.лок, "ОШИБКА", "Условные выражения не поддерживаются в типах");
+            Err(())
+        }
+        Выражение::ЛитералСтруктуры{имя: ключ, ..} | Выражение::ЛитералМассива{ключ, ..} => {
+            диагностика!(&ключ.лок, "ОШИБКА", "Литерал не является типом");
+            Err(())
+        }
+        Выражение::Кортеж{элементы, ..} => {
+            let mut типы = Vec::new();
+            for элемент in элементы.iter() {
+                типы.push(скомпилировать_тип(пп, элемент, имена, локальные_имена)?);
+            }
+            Ok(Тип::Кортеж(типы))
+        }
+    }
+}
+
+/// Ключевые слова, с которых начинаются объявления верхнего уровня.
+const КЛЮЧИ_ОБЪЯВЛЕНИЙ: &[ВидЛексемы] = &[
+    ВидЛексемы::КлючПер,
+    ВидЛексемы::КлючПро,
+    ВидЛексемы::КлючКонст,
+    ВидЛексемы::КлючВкл,
+    ВидЛексемы::КлючЭкспорт,
+    ВидЛексемы::КлючБибл,
+    ВидЛексемы::КлючСтрукт,
+    ВидЛексемы::КлючТип,
+    ВидЛексемы::КлючОткрытая,
+    ВидЛексемы::КлючЗакрытая,
+];
+
+/// После ошибки пропускает лексемы до начала следующего объявления
+/// верхнего уровня. Ключевые слова внутри блоков «нч ... кц» объявлений не
+/// начинают.
+fn пропустить_до_объявления(лекс: &mut Лексер) {
+    let mut вложенность: usize = 0;
+    loop {
+        // О неизвестной лексеме уже сообщили, и лексер её пропустил.
+        if let Ok(лексема) = лекс.подсмотреть_лексему() {
+            match лексема.вид {
+                ВидЛексемы::Конец => return,
+                вид if вложенность == 0 && КЛЮЧИ_ОБЪЯВЛЕНИЙ.contains(&вид) => return,
+                ВидЛексемы::КлючНч => вложенность += 1,
+                ВидЛексемы::КлючКц => вложенность = вложенность.saturating_sub(1),
+                _ => {}
+            }
+        }
+        let _ = лекс.вытащить_лексему();
     }
 }
 
 impl Программа {
+    /// Объявление с ошибкой не прерывает компиляцию: разбор продолжается со
+    /// следующего объявления, пока ошибок не станет слишком много.
     pub fn скомпилировать_лексемы(&mut self, лекс: &mut Лексер) -> Результат<()> {
+        let настоящих_ошибок_до = количество_настоящих_ошибок();
+        let mut результат = Ok(());
         loop {
-            let ключ = лекс.вытащить_лексему_вида(&[
+            let ошибок_до = количество_ошибок();
+            match self.скомпилировать_объявление(лекс) {
+                Ok(false) => break,
+                Ok(true) if количество_ошибок() == ошибок_до => continue,
+                // Объявление могло разобраться не до конца, даже если
+                // его ошибки исправлены на месте.
+                Ok(true) | Err(()) => {}
+            }
+            результат = Err(());
+            if предел_ошибок_достигнут() {
+                break
+            }
+            пропустить_до_объявления(лекс);
+        }
+        // Вызов процедуры мог быть в коде, который не скомпилировался из-за
+        // ошибки, но предупреждения, ставшие ошибками, проверке не мешают.
+        if количество_настоящих_ошибок() == настоящих_ошибок_до && !предел_ошибок_достигнут() {
+            let ошибок_до = количество_ошибок();
+            self.предупредить_о_неиспользуемых_процедурах(лекс.путь_к_файлу);
+            if количество_ошибок() > ошибок_до {
+                результат = Err(());
+            }
+        }
+        результат
+    }
+
+    /// Процедуры главного файла, кроме «главная», и закрытые процедуры
+    /// модуля нельзя вызвать извне файла, поэтому к концу файла уже
+    /// известно, используются ли они.
+    fn предупредить_о_неиспользуемых_процедурах(&self, путь_к_файлу: &Path) {
+        let модуль = &self.имена.текущий_модуль;
+        let mut неиспользуемые: Vec<&СкомпПроцедура> = self.имена.процедуры
+            .iter()
+            .filter(|(_, процедура)| !процедура.использована.get() && процедура.имя.лок.путь_к_файлу == путь_к_файлу)
+            .filter(|(ключ, _)| {
+                if модуль.is_empty() {
+                    return ключ.as_str() != "главная"
+                }
+                let имя = &ключ[модуль.len() + '.'.len_utf8()..];
+                let Some(модуль) = self.имена.модули.get(модуль) else {
+                    return false
+                };
+                match имя.split_once('.') {
+                    Some((структура, _)) => !модуль.открывает(структура) || модуль.видимость.get(имя) == Some(&Видимость::Закрытая),
+                    None => !модуль.открывает(имя),
+                }
+            })
+            .map(|(_, процедура)| процедура)
+            .collect();
+        неиспользуемые.sort_by_key(|процедура| (процедура.имя.лок.строка, процедура.имя.лок.столбец));
+        for процедура in неиспользуемые {
+            предупреждение!(&процедура.имя.лок, Предупреждение::НеиспользуемаяПроцедура, "Процедура «{имя}» нигде не вызывается", имя = процедура.имя.текст);
+        }
+    }
+
+    /// Возвращает «ложь», когда объявления закончились.
+    fn скомпилировать_объявление(&mut self, лекс: &mut Лексер) -> Результат<bool> {
+        let mut ключ = лекс.вытащить_лексему_вида(&[
+            ВидЛексемы::КлючПер,
+            ВидЛексемы::КлючПро,
+            ВидЛексемы::КлючКонст,
+            ВидЛексемы::КлючВкл,
+            ВидЛексемы::КлючЭкспорт,
+            ВидЛексемы::КлючБибл,
+            ВидЛексемы::КлючСтрукт,
+            ВидЛексемы::КлючТип,
+            ВидЛексемы::КлючОткрытая,
+            ВидЛексемы::КлючЗакрытая,
+            ВидЛексемы::Конец,
+        ])?;
+        let видимость = match ключ.вид {
+            ВидЛексемы::КлючОткрытая => Some(Видимость::Открытая),
+            ВидЛексемы::КлючЗакрытая => Some(Видимость::Закрытая),
+            _ => None,
+        };
+        if видимость.is_some() {
+            ключ = лекс.вытащить_лексему_вида(&[
                 ВидЛексемы::КлючПер,
                 ВидЛексемы::КлючПро,
                 ВидЛексемы::КлючКонст,
-                ВидЛексемы::КлючВкл,
-                ВидЛексемы::КлючБибл,
                 ВидЛексемы::КлючСтрукт,
-                ВидЛексемы::Конец,
+                ВидЛексемы::КлючТип,
             ])?;
-            match ключ.вид {
-                ВидЛексемы::КлючПер => {
-                    let синтаксис = Переменная::разобрать(лекс)?;
-                    let имя = синтаксис.имя;
-                    self.имена.верифицировать_переопределение_имени(&имя)?;
-                    let тип = скомпилировать_тип(&self.пп, &синтаксис.тип, &self.имена, None)?;
-                    let смещение = self.пп.размер_неиниц_данных as i32;
-                    self.пп.размер_неиниц_данных += тип.размер(&self.имена.структуры);
-                    if let Some(_) = self.имена.переменные.insert(имя.текст.clone(), СкомпПеременная {имя, тип, смещение}) {
-                        unreachable!("Проверка переопределения переменных должна происходить на этапе разбора")
-                    }
+        }
+        match ключ.вид {
+            ВидЛексемы::КлючПер => {
+                let синтаксис = Переменная::разобрать(лекс)?;
+                let имя = синтаксис.имя;
+                self.имена.верифицировать_переопределение_имени(&имя)?;
+                self.задать_видимость(&имя, видимость);
+                let (тип, результат) = match скомпилировать_тип(&self.пп, &синтаксис.тип, &self.имена, None) {
+                    Ok(тип) => (тип, Ok(())),
+                    Err(()) => (Тип::Ошибка, Err(())),
+                };
+                let смещение = self.пп.размер_неиниц_данных as i32;
+                self.пп.размер_неиниц_данных += тип.размер(&self.имена.структуры);
+                if self.имена.переменные.insert(self.имена.полное_имя(&имя.текст), СкомпПеременная {имя, тип, смещение, ссылка: false, использована: Cell::new(true)}).is_some() {
+                    unreachable!("Проверка переопределения переменных должна происходить на этапе разбора")
                 }
-                ВидЛексемы::КлючПро => {
-                    let процедура = Процедура::разобрать(лекс)?;
+                результат?;
+            }
+            ВидЛексемы::КлючПро => {
+                let процедура = Процедура::разобрать(лекс)?;
+                if процедура.владелец.is_none() {
                     self.имена.верифицировать_переопределение_имени(&процедура.имя)?;
-                    скомпилировать_процедуру(&mut self.пп, &mut self.имена, процедура)?;
-                }
-                ВидЛексемы::КлючКонст => {
-                    let константа = Константа::разобрать(лекс)?;
-                    self.имена.верифицировать_переопределение_имени(&константа.имя)?;
-                    let (байты_значения, тип) = скомпилировать_константное_выражение(&self.пп, &self.имена, None, &константа.выражение)?;
-                    if тип.примитивный() {
-                        let значение = u64::from_le_bytes(байты_значения.try_into().unwrap());
-                        if let Some(_) = self.имена.константы.insert(константа.имя.текст.clone(), СкомпКонстанта {
-                            имя: константа.имя.clone(),
-                            тип,
-                            значение_или_адрес: значение
-                        }) {
-                            unreachable!("Проверка переопределения констант должна происходить на этапе разбора")
-                        }
-                    } else {
-                        let адрес = self.пп.иниц_данные.len();
-                        self.пп.иниц_данные.extend(байты_значения);
-                        if let Some(_) = self.имена.константы.insert(константа.имя.текст.clone(), СкомпКонстанта {
-                            имя: константа.имя.clone(),
-                            тип,
-                            значение_или_адрес: адрес as u64,
-                        }) {
-                            unreachable!("Проверка переопределения констант должна происходить на этапе разбора")
-                        }
+                }
+                self.задать_видимость(&процедура.имя, видимость);
+                скомпилировать_процедуру(&mut self.пп, &mut self.имена, процедура)?;
+            }
+            ВидЛексемы::КлючКонст => {
+                let константа = Константа::разобрать(лекс)?;
+                self.имена.верифицировать_переопределение_имени(&константа.имя)?;
+                self.задать_видимость(&константа.имя, видимость);
+                let Ok(значение) = скомпилировать_константное_выражение(&self.пп, &self.имена, None, &константа.выражение) else {
+                    self.имена.константы.insert(self.имена.полное_имя(&константа.имя.текст), СкомпКонстанта::отравленная(&константа.имя));
+                    return Err(());
+                };
+                let тип = значение.тип.clone();
+                let старая = self.имена.константы.insert(self.имена.полное_имя(&константа.имя.текст), СкомпКонстанта {
+                    имя: константа.имя.clone(),
+                    тип,
+                    значение_или_адрес: self.пп.разместить_константу(значение),
+                });
+                if старая.is_some() {
+                    unreachable!("Проверка переопределения констант должна происходить на этапе разбора")
+                }
+            }
+            ВидЛексемы::КлючВкл => {
+                let имя = лекс.вытащить_лексему_вида(&[ВидЛексемы::Идент])?;
+                let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::ТочкаЗапятая])?;
+                self.включить_модуль(имя)?;
+            }
+            ВидЛексемы::КлючЭкспорт => {
+                let mut имена = Vec::new();
+                loop {
+                    имена.push(лекс.вытащить_лексему_вида(&[ВидЛексемы::Идент])?);
+                    let лексема = лекс.вытащить_лексему_вида(&[
+                        ВидЛексемы::Запятая,
+                        ВидЛексемы::ТочкаЗапятая,
+                    ])?;
+                    if лексема.вид == ВидЛексемы::ТочкаЗапятая {
+                        break
                     }
                 }
-                ВидЛексемы::КлючВкл => {
-                    // СДЕЛАТЬ: расхардкодить пути поиска модулей
-                    let пути_поиска_модулей = &[".", "./модули"];
-                    // СДЕЛАТЬ: отслеживать повторное включение модулей и игнорировать его.
+                let модуль = self.имена.модули.entry(self.имена.текущий_модуль.clone()).or_default();
+                модуль.экспорт.get_or_insert_with(Vec::new).extend(имена);
+            }
+            ВидЛексемы::КлючБибл => {
+                let имя = лекс.вытащить_лексему_вида(&[ВидЛексемы::Идент])?;
+                self.пп.библиотеки.insert(имя.текст.clone(), self.пп.библиотеки.len());
+                let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::ТочкаЗапятая])?;
+            }
+            ВидЛексемы::КлючСтрукт => {
+                let имя = лекс.вытащить_лексему_вида(&[ВидЛексемы::Идент])?;
+                let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::КлючНч])?;
+                let mut поля: HashMap<String, Поле> = HashMap::new();
+                let mut размер = 0;
+                let mut результат = Ok(());
+                // СДЕЛАТЬ: однополевые структуры через ключ «то»
+                while лекс.подсмотреть_лексему()?.вид != ВидЛексемы::КлючКц {
+                    let смещение = размер;
                     let имя = лекс.вытащить_лексему_вида(&[ВидЛексемы::Идент])?;
+                    let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::Двоеточие])?;
+                    let тип = скомпилировать_тип(&self.пп, &Выражение::разобрать(лекс)?, &self.имена, None).unwrap_or_else(|()| {
+                        результат = Err(());
+                        Тип::Ошибка
+                    });
                     let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::ТочкаЗапятая])?;
-                    let mut существует = false;
-                    for путь in пути_поиска_модулей {
-                        let mut путь_к_файлу = PathBuf::new();
-                        путь_к_файлу.push(путь);
-                        путь_к_файлу.push(&имя.текст);
-                        путь_к_файлу.set_extension("хуя");
-                        if путь_к_файлу.exists() {
-                            существует = true;
-                            let содержимое = ::прочитать_содержимое_файла(&путь_к_файлу, Some(имя.лок.clone()))?;
-                            let mut лекс = Лексер::новый(&путь_к_файлу, &содержимое);
-                            self.скомпилировать_лексемы(&mut лекс)?;
-                            break;
-                        }
-                    }
-                    if !существует {
-                        диагностика!(имя.лок, "ОШИБКА", "Не удалось найти модуль «{имя}».", имя = имя.текст);
+                    размер += выравнять_размер_по_слову(тип.размер(&self.имена.структуры));
+                    if let Some(дубль) = поля.get(&имя.текст) {
+                        диагностика!(&имя.лок, "ОШИБКА", "Поле «{имя}» уже существует", имя = имя.текст);
+                        диагностика!(&дубль.имя.лок, "ИНФО", "Оно определено здесь");
                         return Err(());
                     }
+                    if поля.insert(имя.текст.clone(), Поле {имя, тип, смещение}).is_some() {
+                        unreachable!();
+                    }
                 }
-                ВидЛексемы::КлючБибл => {
-                    let имя = лекс.вытащить_лексему_вида(&[ВидЛексемы::Идент])?;
-                    self.пп.библиотеки.insert(имя.текст.clone(), self.пп.библиотеки.len());
-                    let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::ТочкаЗапятая])?;
+                let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::КлючКц])?;
+                self.верифицировать_переопределение_типа(&имя)?;
+                self.задать_видимость(&имя, видимость);
+                // Структура хранится под полным именем, которое и
+                // попадает в её тип.
+                let имя = Лексема {
+                    текст: self.имена.полное_имя(&имя.текст),
+                    ..имя
+                };
+                if self.имена.структуры.insert(имя.текст.clone(), Структура {имя, поля, размер}).is_some() {
+                    unreachable!()
                 }
-                ВидЛексемы::КлючСтрукт => {
-                    let имя = лекс.вытащить_лексему_вида(&[ВидЛексемы::Идент])?;
-                    let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::КлючНч])?;
-                    let mut поля: HashMap<String, Поле> = HashMap::new();
-                    let mut размер = 0;
-                    // СДЕЛАТЬ: однополевые структуры через ключ «то»
-                    while лекс.подсмотреть_лексему()?.вид != ВидЛексемы::КлючКц {
-                        let смещение = размер;
-                        let имя = лекс.вытащить_лексему_вида(&[ВидЛексемы::Идент])?;
-                        let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::Двоеточие])?;
-                        let тип = скомпилировать_тип(&self.пп, &Выражение::разобрать(лекс)?, &self.имена, None)?;
-                        let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::ТочкаЗапятая])?;
-                        размер += выравнять_размер_по_слову(тип.размер(&self.имена.структуры));
-                        if let Some(дубль) = поля.get(&имя.текст) {
-                            диагностика!(&имя.лок, "ОШИБКА", "Поле «{имя}» уже существует", имя = имя.текст);
-                            диагностика!(&дубль.имя.лок, "ИНФО", "Оно определено здесь");
-                            return Err(());
-                        }
-                        if let Some(_) = поля.insert(имя.текст.clone(), Поле {имя, тип, смещение}) {
-                            unreachable!();
-                        }
-                    }
-                    let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::КлючКц])?;
-                    if let Some(дубль) = self.имена.структуры.get(&имя.текст) {
-                        диагностика!(&имя.лок, "ОШИБКА", "Структура «{имя}» уже существует", имя = имя.текст);
-                        диагностика!(&дубль.имя.лок, "ИНФО", "Она определено здесь");
+                результат?;
+            }
+            ВидЛексемы::КлючТип => {
+                let имя = лекс.вытащить_лексему_вида(&[ВидЛексемы::Идент])?;
+                let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::Присваивание])?;
+                let (основа, результат) = match скомпилировать_тип(&self.пп, &Выражение::разобрать(лекс)?, &self.имена, None) {
+                    Ok(тип) => (тип, Ok(())),
+                    Err(()) => (Тип::Ошибка, Err(())),
+                };
+                let лексема = лекс.вытащить_лексему_вида(&[
+                    ВидЛексемы::Идент,
+                    ВидЛексемы::ТочкаЗапятая,
+                ])?;
+                let отдельный = лексема.вид == ВидЛексемы::Идент;
+                if отдельный {
+                    if лексема.текст != "отдельный" {
+                        диагностика!(&лексема.лок, "ОШИБКА", "Ожидалось «отдельный» либо точка с запятой, но повстречалось «{текст}»", текст = лексема.текст);
                         return Err(());
                     }
-                    if let Some(_) = self.имена.структуры.insert(имя.текст.clone(), Структура {имя, поля, размер}) {
-                        unreachable!()
+                    let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::ТочкаЗапятая])?;
+                    if !основа.примитивный() && !основа.отравлен() {
+                        сделать!(&имя.лок, "Отдельные типы на основе непримитивного типа «{тип}»", тип = основа.текст());
+                        return Err(());
                     }
                 }
-                ВидЛексемы::Конец => break,
-                _ => unreachable!(),
+                self.верифицировать_переопределение_типа(&имя)?;
+                self.задать_видимость(&имя, видимость);
+                let полное_имя = self.имена.полное_имя(&имя.текст);
+                // Псевдоним ничем не отличается от своего типа.
+                let тип = if отдельный {
+                    Тип::Отдельный{имя: полное_имя.clone(), основа: Box::new(основа)}
+                } else {
+                    основа
+                };
+                if self.имена.типы.insert(полное_имя, СкомпТип {имя, тип}).is_some() {
+                    unreachable!()
+                }
+                результат?;
+            }
+            ВидЛексемы::Конец => return Ok(false),
+            _ => unreachable!(),
+        }
+        Ok(true)
+    }
+
+    /// Структуры и объявленные типы делят одно пространство имён.
+    fn верифицировать_переопределение_типа(&self, имя: &Лексема) -> Результат<()> {
+        if встроенный_тип(&имя.текст).is_some() {
+            диагностика!(&имя.лок, "ОШИБКА", "Тип «{имя}» является встроенным", имя = имя.текст);
+            return Err(());
+        }
+        if let Some(дубль) = self.имена.найти_структуру(&имя.текст) {
+            диагностика!(&имя.лок, "ОШИБКА", "Структура «{имя}» уже существует", имя = имя.текст);
+            диагностика!(&дубль.имя.лок, "ИНФО", "Она определено здесь");
+            return Err(());
+        }
+        if let Some(дубль) = self.имена.найти_тип(&имя.текст) {
+            диагностика!(&имя.лок, "ОШИБКА", "Тип «{имя}» уже существует", имя = имя.текст);
+            диагностика!(&дубль.имя.лок, "ИНФО", "Он определён здесь");
+            return Err(());
+        }
+        Ok(())
+    }
+
+    fn пути_поиска_модулей(&self) -> Vec<PathBuf> {
+        let mut пути = self.пути_поиска.clone();
+        if let Some(переменная) = env::var_os(ПЕРЕМЕННАЯ_ПУТЕЙ_ПОИСКА) {
+            пути.extend(env::split_paths(&переменная));
+        }
+        пути.push(PathBuf::from("."));
+        пути.push(PathBuf::from("./модули"));
+        пути
+    }
+
+    /// Компилирует модуль, если он ещё не был скомпилирован, и делает его
+    /// открытые имена видимыми из текущего модуля.
+    fn включить_модуль(&mut self, имя: Лексема) -> Результат<()> {
+        if let Some(начало) = self.стек_включений.iter().position(|включение| включение.текст == имя.текст) {
+            let цикл: Vec<&str> = self.стек_включений[начало..]
+                .iter()
+                .chain(std::iter::once(&имя))
+                .map(|включение| включение.текст.as_str())
+                .collect();
+            диагностика!(&имя.лок, "ОШИБКА", "Циклическое включение модулей: {цикл}", цикл = цикл.join(" → "));
+            for включение in &self.стек_включений[начало..] {
+                диагностика!(&включение.лок, "ИНФО", "Модуль «{имя}» включается здесь", имя = включение.текст);
+            }
+            return Err(())
+        }
+
+        if self.имена.включён_модуль(&имя.текст) {
+            return Ok(())
+        }
+
+        let mut результат = Ok(());
+        if !self.имена.модули.contains_key(&имя.текст) {
+            let Some(путь_к_файлу) = self.пути_поиска_модулей()
+                .into_iter()
+                .map(|путь| путь.join(&имя.текст).with_extension("хуя"))
+                .find(|путь_к_файлу| путь_к_файлу.exists()) else {
+                диагностика!(&имя.лок, "ОШИБКА", "Не удалось найти модуль «{имя}».", имя = имя.текст);
+                return Err(());
+            };
+            let содержимое = ::прочитать_содержимое_файла(&путь_к_файлу, Some(имя.лок.clone()))?;
+            let mut лекс = Лексер::новый(&путь_к_файлу, &содержимое);
+
+            self.имена.модули.insert(имя.текст.clone(), Модуль::default());
+            let внешний_модуль = std::mem::replace(&mut self.имена.текущий_модуль, имя.текст.clone());
+            self.стек_включений.push(имя.clone());
+            результат = self.скомпилировать_лексемы(&mut лекс).and_then(|()| self.проверить_экспорт());
+            self.стек_включений.pop();
+            self.имена.текущий_модуль = внешний_модуль;
+            // Даже модуль с ошибками включается, чтобы его имена не
+            // порождали новых ошибок.
+            if предел_ошибок_достигнут() {
+                return результат
+            }
+        }
+
+        for (открытое_имя, лок) in self.имена.открытые_имена(&имя.текст) {
+            if let Some(существующее) = self.имена.найти_определение(&открытое_имя) {
+                диагностика!(&имя.лок, "ОШИБКА", "Модуль «{модуль}» открывает имя «{имя}», которое уже определено",
+                             модуль = имя.текст,
+                             имя = открытое_имя);
+                диагностика!(существующее, "ИНФО", "Существующее определение находится здесь");
+                диагностика!(&лок, "ИНФО", "Определение в модуле находится здесь");
+                return Err(())
             }
         }
+        self.имена.модули
+            .entry(self.имена.текущий_модуль.clone())
+            .or_default()
+            .включённые
+            .push(имя.текст);
+        результат
+    }
+
+    fn задать_видимость(&mut self, имя: &Лексема, видимость: Option<Видимость>) {
+        if let Some(видимость) = видимость {
+            self.имена.модули
+                .entry(self.имена.текущий_модуль.clone())
+                .or_default()
+                .видимость
+                .insert(имя.текст.clone(), видимость);
+        }
+    }
 
+    fn проверить_экспорт(&self) -> Результат<()> {
+        let модуль = &self.имена.модули[&self.имена.текущий_модуль];
+        if let Some(экспорт) = &модуль.экспорт {
+            for имя in экспорт {
+                if self.имена.найти_определение(&self.имена.полное_имя(&имя.текст)).is_none() {
+                    диагностика!(&имя.лок, "ОШИБКА", "Модуль экспортирует неизвестное имя «{имя}»", имя = имя.текст);
+                    return Err(())
+                }
+                if модуль.видимость.get(&имя.текст) == Some(&Видимость::Закрытая) {
+                    диагностика!(&имя.лок, "ОШИБКА", "Закрытое имя «{имя}» не может быть экспортировано", имя = имя.текст);
+                    return Err(())
+                }
+            }
+        }
         Ok(())
     }
 
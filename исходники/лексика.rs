@@ -6,7 +6,12 @@ use std::path::Path;
 pub const ПРИСТАВКИ_ПРЕПИНАНИЙ: &[(&[char], ВидЛексемы)] = &[
     (&['('], ВидЛексемы::ОткрытаяСкобка),
     (&[')'], ВидЛексемы::ЗакрытаяСкобка),
+    (&['{'], ВидЛексемы::ОткрытаяФигурнаяСкобка),
+    (&['}'], ВидЛексемы::ЗакрытаяФигурнаяСкобка),
+    (&['['], ВидЛексемы::ОткрытаяКвадратнаяСкобка),
+    (&[']'], ВидЛексемы::ЗакрытаяКвадратнаяСкобка),
     (&[';'], ВидЛексемы::ТочкаЗапятая),
+    (&['.', '.', '.'], ВидЛексемы::Многоточие),
     (&['.', '.'], ВидЛексемы::ТочкаТочка),
     (&['.'], ВидЛексемы::Точка),
     (&[':', '='], ВидЛексемы::Присваивание),
@@ -14,17 +19,38 @@ pub const ПРИСТАВКИ_ПРЕПИНАНИЙ: &[(&[char], ВидЛексе
     (&[','], ВидЛексемы::Запятая),
     (&['+', '?', '='], ВидЛексемы::БольшеРавно),
     (&['+', '?'], ВидЛексемы::Больше),
+    (&['+', '%', '='], ВидЛексемы::ПлюсПроцентПрисваивание),
+    (&['+', '%'], ВидЛексемы::ПлюсПроцент),
+    (&['+', '='], ВидЛексемы::ПлюсПрисваивание),
     (&['+'], ВидЛексемы::Плюс),
     (&['-', '?', '='], ВидЛексемы::МеньшеРавно),
     (&['-', '?'], ВидЛексемы::Меньше),
+    (&['-', '%', '='], ВидЛексемы::МинусПроцентПрисваивание),
+    (&['-', '%'], ВидЛексемы::МинусПроцент),
+    (&['-', '='], ВидЛексемы::МинусПрисваивание),
     (&['-'], ВидЛексемы::Минус),
+    (&['*', '%', '='], ВидЛексемы::ЗвёздочкаПроцентПрисваивание),
+    (&['*', '%'], ВидЛексемы::ЗвёздочкаПроцент),
+    (&['*', '='], ВидЛексемы::ЗвёздочкаПрисваивание),
     (&['*'], ВидЛексемы::Звёздочка),
+    (&['/', '='], ВидЛексемы::ПрямаяНаклоннаяПрисваивание),
     (&['/'], ВидЛексемы::ПрямаяНаклонная),
     (&['='], ВидЛексемы::Равно),
     (&['!', '='], ВидЛексемы::НеРавно),
     (&['!'], ВидЛексемы::Не),
 ];
 
+/// Ключевые слова операций, за которыми может сразу следовать «=»,
+/// образуя составное присваивание (например «ост=»).
+pub const СОСТАВНЫЕ_ПРИСВАИВАНИЯ_КЛЮЧЕЙ: &[(ВидЛексемы, ВидЛексемы)] = &[
+    (ВидЛексемы::КлючОст, ВидЛексемы::ОстПрисваивание),
+    (ВидЛексемы::КлючИ, ВидЛексемы::ИПрисваивание),
+    (ВидЛексемы::КлючИли, ВидЛексемы::ИлиПрисваивание),
+    (ВидЛексемы::КлючЛибо, ВидЛексемы::ЛибоПрисваивание),
+    (ВидЛексемы::КлючЛбс, ВидЛексемы::ЛбсПрисваивание),
+    (ВидЛексемы::КлючПбс, ВидЛексемы::ПбсПрисваивание),
+];
+
 pub const КЛЮЧЕВЫЕ_СЛОВА: &[(&str, ВидЛексемы)] = &[
     ("пер", ВидЛексемы::КлючПер),
     ("про", ВидЛексемы::КлючПро),
@@ -42,9 +68,13 @@ pub const КЛЮЧЕВЫЕ_СЛОВА: &[(&str, ВидЛексемы)] = &[
     ("кц", ВидЛексемы::КлючКц),
     ("как", ВидЛексемы::КлючКак),
     ("вкл", ВидЛексемы::КлючВкл),
+    ("экспорт", ВидЛексемы::КлючЭкспорт),
+    ("открытая", ВидЛексемы::КлючОткрытая),
+    ("закрытая", ВидЛексемы::КлючЗакрытая),
     ("внешняя", ВидЛексемы::КлючВнешняя),
     ("библ", ВидЛексемы::КлючБибл),
     ("структ", ВидЛексемы::КлючСтрукт),
+    ("тип", ВидЛексемы::КлючТип),
     ("истина", ВидЛексемы::КлючИстина),
     ("ложь", ВидЛексемы::КлючЛожь),
     ("лбс", ВидЛексемы::КлючЛбс),
@@ -77,9 +107,13 @@ pub enum ВидЛексемы {
     КлючКц,
     КлючКак,
     КлючВкл,
+    КлючЭкспорт,
+    КлючОткрытая,
+    КлючЗакрытая,
     КлючВнешняя,
     КлючБибл,
     КлючСтрукт,
+    КлючТип,
     КлючИстина,
     КлючЛожь,
     КлючЛбс,
@@ -91,16 +125,37 @@ pub enum ВидЛексемы {
 
     ОткрытаяСкобка,
     ЗакрытаяСкобка,
+    ОткрытаяФигурнаяСкобка,
+    ЗакрытаяФигурнаяСкобка,
+    ОткрытаяКвадратнаяСкобка,
+    ЗакрытаяКвадратнаяСкобка,
     ТочкаЗапятая,
     Точка,
     ТочкаТочка,
+    Многоточие,
     Двоеточие,
     Запятая,
     Плюс,
+    ПлюсПроцент,
     Минус,
+    МинусПроцент,
     Звёздочка,
+    ЗвёздочкаПроцент,
     ПрямаяНаклонная,
     Присваивание,
+    ПлюсПрисваивание,
+    МинусПрисваивание,
+    ЗвёздочкаПрисваивание,
+    ПрямаяНаклоннаяПрисваивание,
+    ПлюсПроцентПрисваивание,
+    МинусПроцентПрисваивание,
+    ЗвёздочкаПроцентПрисваивание,
+    ОстПрисваивание,
+    ИПрисваивание,
+    ИлиПрисваивание,
+    ЛибоПрисваивание,
+    ЛбсПрисваивание,
+    ПбсПрисваивание,
     Равно,
     Меньше,
     МеньшеРавно,
@@ -113,6 +168,7 @@ pub enum ВидЛексемы {
     ЦелШестЧисло,
     ВещЧисло,
     Строка,
+    Символ,
 }
 
 impl ВидЛексемы {
@@ -138,9 +194,13 @@ impl ВидЛексемы {
             ВидЛексемы::КлючКц             => Сущ{текст: "«кц»",                       род: Род::Муж},
             ВидЛексемы::КлючКак            => Сущ{текст: "«как»",                      род: Род::Муж},
             ВидЛексемы::КлючВкл            => Сущ{текст: "«вкл»",                      род: Род::Муж},
+            ВидЛексемы::КлючЭкспорт        => Сущ{текст: "«экспорт»",                  род: Род::Муж},
+            ВидЛексемы::КлючОткрытая       => Сущ{текст: "«открытая»",                 род: Род::Жен},
+            ВидЛексемы::КлючЗакрытая       => Сущ{текст: "«закрытая»",                 род: Род::Жен},
             ВидЛексемы::КлючВнешняя        => Сущ{текст: "«внешняя»",                  род: Род::Жен},
             ВидЛексемы::КлючБибл           => Сущ{текст: "«библ»",                     род: Род::Муж},
             ВидЛексемы::КлючСтрукт         => Сущ{текст: "«структ»",                   род: Род::Муж},
+            ВидЛексемы::КлючТип            => Сущ{текст: "«тип»",                      род: Род::Муж},
             ВидЛексемы::КлючИстина         => Сущ{текст: "«истина»",                   род: Род::Жен},
             ВидЛексемы::КлючЛожь           => Сущ{текст: "«ложь»",                     род: Род::Жен},
             ВидЛексемы::КлючЛбс            => Сущ{текст: "«лбс»",                      род: Род::Муж},
@@ -153,16 +213,37 @@ impl ВидЛексемы {
             // Знаки препинания
             ВидЛексемы::ОткрытаяСкобка     => Сущ{текст: "открытая скобка",            род: Род::Жен},
             ВидЛексемы::ЗакрытаяСкобка     => Сущ{текст: "закрытая скобка",            род: Род::Жен},
+            ВидЛексемы::ОткрытаяФигурнаяСкобка => Сущ{текст: "открытая фигурная скобка", род: Род::Жен},
+            ВидЛексемы::ЗакрытаяФигурнаяСкобка => Сущ{текст: "закрытая фигурная скобка", род: Род::Жен},
+            ВидЛексемы::ОткрытаяКвадратнаяСкобка => Сущ{текст: "открытая квадратная скобка", род: Род::Жен},
+            ВидЛексемы::ЗакрытаяКвадратнаяСкобка => Сущ{текст: "закрытая квадратная скобка", род: Род::Жен},
             ВидЛексемы::ТочкаЗапятая       => Сущ{текст: "точка с запятой",            род: Род::Жен},
             ВидЛексемы::Точка              => Сущ{текст: "точка",                      род: Род::Жен},
             ВидЛексемы::ТочкаТочка         => Сущ{текст: "точка точка",                род: Род::Жен},
+            ВидЛексемы::Многоточие         => Сущ{текст: "многоточие",                 род: Род::Сред},
             ВидЛексемы::Двоеточие          => Сущ{текст: "двоеточие",                  род: Род::Сред},
             ВидЛексемы::Запятая            => Сущ{текст: "запятая",                    род: Род::Жен},
             ВидЛексемы::Плюс               => Сущ{текст: "плюс",                       род: Род::Муж},
+            ВидЛексемы::ПлюсПроцент        => Сущ{текст: "плюс с процентом",           род: Род::Муж},
             ВидЛексемы::Минус              => Сущ{текст: "минус",                      род: Род::Муж},
+            ВидЛексемы::МинусПроцент       => Сущ{текст: "минус с процентом",          род: Род::Муж},
             ВидЛексемы::Звёздочка          => Сущ{текст: "звёздочка",                  род: Род::Жен},
+            ВидЛексемы::ЗвёздочкаПроцент   => Сущ{текст: "звёздочка с процентом",      род: Род::Жен},
             ВидЛексемы::ПрямаяНаклонная    => Сущ{текст: "прямая наклонная черта",     род: Род::Жен},
             ВидЛексемы::Присваивание       => Сущ{текст: "присваивание",               род: Род::Сред},
+            ВидЛексемы::ПлюсПрисваивание   => Сущ{текст: "«+=»",                       род: Род::Сред},
+            ВидЛексемы::МинусПрисваивание  => Сущ{текст: "«-=»",                       род: Род::Сред},
+            ВидЛексемы::ЗвёздочкаПрисваивание => Сущ{текст: "«*=»",                    род: Род::Сред},
+            ВидЛексемы::ПрямаяНаклоннаяПрисваивание => Сущ{текст: "«/=»",              род: Род::Сред},
+            ВидЛексемы::ПлюсПроцентПрисваивание => Сущ{текст: "«+%=»",                 род: Род::Сред},
+            ВидЛексемы::МинусПроцентПрисваивание => Сущ{текст: "«-%=»",                род: Род::Сред},
+            ВидЛексемы::ЗвёздочкаПроцентПрисваивание => Сущ{текст: "«*%=»",            род: Род::Сред},
+            ВидЛексемы::ОстПрисваивание    => Сущ{текст: "«ост=»",                     род: Род::Сред},
+            ВидЛексемы::ИПрисваивание      => Сущ{текст: "«и=»",                       род: Род::Сред},
+            ВидЛексемы::ИлиПрисваивание    => Сущ{текст: "«или=»",                     род: Род::Сред},
+            ВидЛексемы::ЛибоПрисваивание   => Сущ{текст: "«либо=»",                    род: Род::Сред},
+            ВидЛексемы::ЛбсПрисваивание    => Сущ{текст: "«лбс=»",                     род: Род::Сред},
+            ВидЛексемы::ПбсПрисваивание    => Сущ{текст: "«пбс=»",                     род: Род::Сред},
             ВидЛексемы::Равно              => Сущ{текст: "равно",                      род: Род::Сред},
             ВидЛексемы::Меньше             => Сущ{текст: "меньше",                     род: Род::Сред},
             ВидЛексемы::Больше             => Сущ{текст: "больше",                     род: Род::Сред},
@@ -175,6 +256,7 @@ impl ВидЛексемы {
             ВидЛексемы::ЦелЧисло           => Сущ{текст: "целое число",                род: Род::Сред},
             ВидЛексемы::ВещЧисло           => Сущ{текст: "вещественное число",         род: Род::Сред},
             ВидЛексемы::Строка             => Сущ{текст: "строка",                     род: Род::Жен},
+            ВидЛексемы::Символ             => Сущ{текст: "символ",                     род: Род::Муж},
         }
     }
 }
@@ -274,6 +356,37 @@ impl<'a> Лексер<'a> {
         }
     }
 
+    /// Разбирает последовательность экранирования, начинающуюся с обратной
+    /// косой черты. Кроме «\н», «\т» и «\\» экранировать можно только
+    /// кавычки самого литерала.
+    fn разобрать_экранирование(&mut self, лок: &Лок, кавычки: &[char], незавершённый: &str) -> Результат<char> {
+        self.отрезать_символ();
+        let сим = match self.текущий_символ().cloned() {
+            Some('н') => '\n',
+            Some('т') => '\t',
+            Some('\\') => '\\',
+            Some(сим) if кавычки.contains(&сим) => сим,
+            Some('"') if кавычки.contains(&'«') => {
+                диагностика!(&self.лок(), "ОШИБКА", "Экранировать \"лапки\" внутри «ёлочек» не нужно!");
+                return Err(());
+            }
+            Some('«' | '»') if кавычки.contains(&'"') => {
+                диагностика!(&self.лок(), "ОШИБКА", "Экранировать «ёлочки» внутри \"лапок\" не нужно!");
+                return Err(());
+            }
+            Some(сим) => {
+                диагностика!(&self.лок(), "ОШИБКА", "неизвестная поседовательность экранирования начинается с «{сим}»");
+                return Err(());
+            }
+            None => {
+                диагностика!(лок, "ОШИБКА", "{незавершённый}");
+                return Err(());
+            }
+        };
+        self.отрезать_символ();
+        Ok(сим)
+    }
+
     fn подбрить_многострочные_комментарии(&mut self) {
         let mut вложенность: usize = 1;
         while вложенность > 0 && self.текущий_символ().is_some() {
@@ -322,6 +435,13 @@ impl<'a> Лексер<'a> {
             let текст = self.символы[начало..self.позиция].iter().collect();
             for &(ключ, вид) in КЛЮЧЕВЫЕ_СЛОВА.iter() {
                 if ключ == текст {
+                    if self.символы.get(self.позиция) == Some(&'=') {
+                        if let Some(&(_, вид)) = СОСТАВНЫЕ_ПРИСВАИВАНИЯ_КЛЮЧЕЙ.iter().find(|(ключ, _)| *ключ == вид) {
+                            self.отрезать_символ();
+                            let текст = format!("{текст}=");
+                            return Ok(Лексема {вид, текст, лок})
+                        }
+                    }
                     return Ok(Лексема {вид, текст, лок})
                 }
             }
@@ -399,51 +519,7 @@ impl<'a> Лексер<'a> {
                             текст.push(сим);
                         }
                         '\\' => {
-                            self.отрезать_символ();
-                            match self.текущий_символ().cloned() {
-                                Some('н') => {
-                                    self.отрезать_символ();
-                                    текст.push('\n');
-                                }
-                                Some('т') => {
-                                    self.отрезать_символ();
-                                    текст.push('\t');
-                                }
-                                Some('\\') => {
-                                    self.отрезать_символ();
-                                    текст.push('\\');
-                                }
-                                Some('"') => {
-                                    let лок = Лок {
-                                        строка: self.строка + 1,
-                                        столбец: self.позиция - self.начало_строки + 1,
-                                        путь_к_файлу: self.путь_к_файлу.to_path_buf(),
-                                    };
-                                    диагностика!(&лок, "ОШИБКА", "Экранировать \"лапки\" внутри «ёлочек» не нужно!");
-                                    return Err(());
-                                }
-                                Some('»') => {
-                                    self.отрезать_символ();
-                                    текст.push('»');
-                                }
-                                Some('«') => {
-                                    self.отрезать_символ();
-                                    текст.push('«');
-                                }
-                                Some(сим) => {
-                                    let лок = Лок {
-                                        строка: self.строка + 1,
-                                        столбец: self.позиция - self.начало_строки + 1,
-                                        путь_к_файлу: self.путь_к_файлу.to_path_buf(),
-                                    };
-                                    диагностика!(&лок, "ОШИБКА", "неизвестная поседовательность экранирования начинается с «{сим}»");
-                                    return Err(());
-                                }
-                                None => {
-                                    диагностика!(&лок, "ОШИБКА", "незавершённая строка");
-                                    return Err(());
-                                }
-                            }
+                            текст.push(self.разобрать_экранирование(&лок, &['«', '»'], "незавершённая строка")?);
                         }
                         _ => {
                             self.отрезать_символ();
@@ -472,47 +548,44 @@ impl<'a> Лексер<'a> {
                             });
                         }
                         '\\' => {
+                            текст.push(self.разобрать_экранирование(&лок, &['"'], "незавершённая строка")?);
+                        }
+                        _ => {
+                            self.отрезать_символ();
+                            текст.push(сим)
+                        }
+                    }
+                } else {
+                    диагностика!(&лок, "ОШИБКА", "незавершённая строка");
+                    return Err(());
+                }
+            }
+        }
+
+        if сим == '\'' {
+            self.отрезать_символ();
+            let mut текст = String::new();
+            loop {
+                if let Some(сим) = self.текущий_символ().cloned() {
+                    match сим {
+                        '\'' => {
                             self.отрезать_символ();
-                            match self.текущий_символ().cloned() {
-                                Some('н') => {
-                                    self.отрезать_символ();
-                                    текст.push('\n');
-                                }
-                                Some('т') => {
-                                    self.отрезать_символ();
-                                    текст.push('\t');
-                                }
-                                Some('\\') => {
-                                    self.отрезать_символ();
-                                    текст.push('\\');
-                                }
-                                Some('"') => {
-                                    self.отрезать_символ();
-                                    текст.push('\"');
-                                }
-                                Some('«') | Some('»') => {
-                                    let лок = Лок {
-                                        строка: self.строка + 1,
-                                        столбец: self.позиция - self.начало_строки + 1,
-                                        путь_к_файлу: self.путь_к_файлу.to_path_buf(),
-                                    };
-                                    диагностика!(&лок, "ОШИБКА", "Экранировать «ёлочки» внутри \"лапок\" не нужно!");
-                                    return Err(());
-                                }
-                                Some(сим) => {
-                                    let лок = Лок {
-                                        строка: self.строка + 1,
-                                        столбец: self.позиция - self.начало_строки + 1,
-                                        путь_к_файлу: self.путь_к_файлу.to_path_buf(),
-                                    };
-                                    диагностика!(&лок, "ОШИБКА", "неизвестная поседовательность экранирования начинается с «{сим}»");
-                                    return Err(());
-                                }
-                                None => {
-                                    диагностика!(&лок, "ОШИБКА", "незавершённая строка");
-                                    return Err(());
-                                }
+                            if текст.chars().count() != 1 {
+                                диагностика!(&лок, "ОШИБКА", "Символьный литерал должен содержать ровно один символ");
+                                return Err(());
                             }
+                            return Ok(Лексема {
+                                вид: ВидЛексемы::Символ,
+                                текст,
+                                лок,
+                            });
+                        }
+                        '\\' => {
+                            текст.push(self.разобрать_экранирование(&лок, &['\''], "незавершённый символьный литерал")?);
+                        }
+                        '\n' => {
+                            диагностика!(&лок, "ОШИБКА", "незавершённый символьный литерал");
+                            return Err(());
                         }
                         _ => {
                             self.отрезать_символ();
@@ -520,7 +593,7 @@ impl<'a> Лексер<'a> {
                         }
                     }
                 } else {
-                    диагностика!(&лок, "ОШИБКА", "незавершённая строка");
+                    диагностика!(&лок, "ОШИБКА", "незавершённый символьный литерал");
                     return Err(());
                 }
             }
@@ -563,6 +636,9 @@ impl<'a> Лексер<'a> {
                 return Ok(лексема)
             }
         }
+        // Неожиданная лексема возвращается обратно, чтобы разбор после
+        // ошибки мог продолжиться с неё: это может быть «кц» или «;».
+        self.буфер = Some(Ok(лексема.clone()));
 
         if ожидаемые_виды.len() == 1 {
             let ожидаемый_вид = ожидаемые_виды[0];
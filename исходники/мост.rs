@@ -0,0 +1,100 @@
+// Мост для вызова нативных процедур из интерпретатора.
+//
+// Поддерживается только соглашение о вызовах System V AMD64: целые
+// аргументы передаются через rdi, rsi, rdx, rcx, r8, r9, а
+// вещественные через xmm0-xmm7. Передача аргументов через стек пока
+// не поддерживается.
+
+use std::ffi::{CStr, CString, c_char, c_int, c_void};
+use Результат;
+
+extern "C" {
+    fn dlopen(путь: *const c_char, флаги: c_int) -> *mut c_void;
+    fn dlsym(библиотека: *mut c_void, символ: *const c_char) -> *mut c_void;
+    fn dlerror() -> *mut c_char;
+}
+
+const RTLD_NOW: c_int = 2;
+
+pub const ЦЕЛЫХ_РЕГИСТРОВ: usize = 6;
+pub const ВЕЩ_РЕГИСТРОВ: usize = 8;
+
+fn последняя_ошибка() -> String {
+    let ошибка = unsafe { dlerror() };
+    if ошибка.is_null() {
+        "неизвестная ошибка".to_string()
+    } else {
+        unsafe { CStr::from_ptr(ошибка) }.to_string_lossy().into_owned()
+    }
+}
+
+pub fn загрузить_библиотеку(имя: &str) -> Результат<*mut c_void> {
+    // Ищем библиотеки там же, где их ищет ld при компиляции (см. фазм.rs).
+    //
+    // libc.so и libm.so в glibc являются скриптами линковщика, а не
+    // библиотеками, поэтому пробуем также версионированное имя.
+    let пути = [
+        format!("./модули/lib{имя}.so"),
+        format!("lib{имя}.so"),
+        format!("lib{имя}.so.6"),
+    ];
+    let mut ошибки = Vec::new();
+    for путь in &пути {
+        let путь_си = CString::new(путь.as_str()).map_err(|_| {
+            eprintln!("ОШИБКА: некорректное имя библиотеки «{имя}»");
+        })?;
+        let библиотека = unsafe { dlopen(путь_си.as_ptr(), RTLD_NOW) };
+        if !библиотека.is_null() {
+            return Ok(библиотека)
+        }
+        ошибки.push(последняя_ошибка());
+    }
+    eprintln!("ОШИБКА: не удалось загрузить библиотеку «{имя}»");
+    for ошибка in ошибки {
+        eprintln!("    {ошибка}");
+    }
+    Err(())
+}
+
+pub fn найти_символ(библиотеки: &[*mut c_void], имя: &str) -> Результат<usize> {
+    let имя_си = CString::new(имя).map_err(|_| {
+        eprintln!("ОШИБКА: некорректное имя внешнего символа «{имя}»");
+    })?;
+    for &библиотека in библиотеки {
+        let символ = unsafe { dlsym(библиотека, имя_си.as_ptr()) };
+        if !символ.is_null() {
+            return Ok(символ as usize)
+        }
+    }
+    eprintln!("ОШИБКА: внешний символ «{имя}» не найден ни в одной из подключенных библиотек");
+    Err(())
+}
+
+/// Вызывает нативную процедуру по адресу. Вещественные аргументы
+/// передаются битами, которые будут положены в регистры xmm как есть,
+/// т.е. для float значимы только младшие 32 бита.
+///
+/// Процедура всегда вызывается как вариативная с заполнением всех
+/// регистров, поэтому в al всегда передаётся 8. Невариативные процедуры
+/// просто игнорируют лишние регистры и al, а для вариативных al является
+/// лишь верхней границей количества использованных xmm регистров, и 8
+/// для них тоже корректно.
+pub unsafe fn вызвать(адрес: usize, целые: &[u64], вещественные: &[u64], вещ_результат: bool) -> u64 {
+    type Процедура = unsafe extern "C" fn(u64, u64, u64, u64, u64, u64, ...) -> u64;
+    type ВещПроцедура = unsafe extern "C" fn(u64, u64, u64, u64, u64, u64, ...) -> f64;
+
+    let mut ц = [0u64; ЦЕЛЫХ_РЕГИСТРОВ];
+    ц[..целые.len()].copy_from_slice(целые);
+    let mut в = [0f64; ВЕЩ_РЕГИСТРОВ];
+    for (регистр, значение) in в.iter_mut().zip(вещественные) {
+        *регистр = f64::from_bits(*значение);
+    }
+
+    if вещ_результат {
+        let процедура: ВещПроцедура = std::mem::transmute(адрес);
+        процедура(ц[0], ц[1], ц[2], ц[3], ц[4], ц[5], в[0], в[1], в[2], в[3], в[4], в[5], в[6], в[7]).to_bits()
+    } else {
+        let процедура: Процедура = std::mem::transmute(адрес);
+        процедура(ц[0], ц[1], ц[2], ц[3], ц[4], ц[5], в[0], в[1], в[2], в[3], в[4], в[5], в[6], в[7])
+    }
+}
@@ -28,6 +28,9 @@ pub enum ВидБинопа {
     Сложение,
     Вычитание,
     Умножение,
+    СложениеПоМодулю,
+    ВычитаниеПоМодулю,
+    УмножениеПоМодулю,
     Деление,
     Остаток,
     Или,
@@ -50,8 +53,8 @@ impl ВидБинопа {
             Или | Либо                                          => Self::МАКС_ПРИОРИТЕТ - 7,
             И                                                   => Self::МАКС_ПРИОРИТЕТ - 6,
             Меньше | Больше | МеньшеРавно | БольшеРавно | Равно | НеРавно => Self::МАКС_ПРИОРИТЕТ - 5,
-            Сложение | Вычитание                                => Self::МАКС_ПРИОРИТЕТ - 4,
-            Умножение | Деление | Остаток                       => Self::МАКС_ПРИОРИТЕТ - 3,
+            Сложение | Вычитание | СложениеПоМодулю | ВычитаниеПоМодулю => Self::МАКС_ПРИОРИТЕТ - 4,
+            Умножение | УмножениеПоМодулю | Деление | Остаток   => Self::МАКС_ПРИОРИТЕТ - 3,
             // СДЕЛАТЬ: какой приоритет лучше всего для битовых смещений?
             ЛевоеБитовоеСмещение | ПравоеБитовоеСмещение        => Self::МАКС_ПРИОРИТЕТ - 2,
             Как                                                 => Self::МАКС_ПРИОРИТЕТ - 1,
@@ -70,6 +73,9 @@ impl ВидБинопа {
             ВидЛексемы::Плюс            => Some(ВидБинопа::Сложение),
             ВидЛексемы::Минус           => Some(ВидБинопа::Вычитание),
             ВидЛексемы::Звёздочка       => Some(ВидБинопа::Умножение),
+            ВидЛексемы::ПлюсПроцент     => Some(ВидБинопа::СложениеПоМодулю),
+            ВидЛексемы::МинусПроцент    => Some(ВидБинопа::ВычитаниеПоМодулю),
+            ВидЛексемы::ЗвёздочкаПроцент => Some(ВидБинопа::УмножениеПоМодулю),
             ВидЛексемы::ПрямаяНаклонная => Some(ВидБинопа::Деление),
             ВидЛексемы::КлючОст         => Some(ВидБинопа::Остаток),
             ВидЛексемы::КлючКак         => Some(ВидБинопа::Как),
@@ -82,6 +88,26 @@ impl ВидБинопа {
             _                           => None
         }
     }
+
+    /// Операция, которую выполняет составное присваивание («+=», «ост=» и т.д.).
+    fn по_виду_составного_присваивания(вид: &ВидЛексемы) -> Option<ВидБинопа> {
+        match вид {
+            ВидЛексемы::ПлюсПрисваивание             => Some(ВидБинопа::Сложение),
+            ВидЛексемы::МинусПрисваивание            => Some(ВидБинопа::Вычитание),
+            ВидЛексемы::ЗвёздочкаПрисваивание        => Some(ВидБинопа::Умножение),
+            ВидЛексемы::ПрямаяНаклоннаяПрисваивание  => Some(ВидБинопа::Деление),
+            ВидЛексемы::ПлюсПроцентПрисваивание      => Some(ВидБинопа::СложениеПоМодулю),
+            ВидЛексемы::МинусПроцентПрисваивание     => Some(ВидБинопа::ВычитаниеПоМодулю),
+            ВидЛексемы::ЗвёздочкаПроцентПрисваивание => Some(ВидБинопа::УмножениеПоМодулю),
+            ВидЛексемы::ОстПрисваивание              => Some(ВидБинопа::Остаток),
+            ВидЛексемы::ИПрисваивание                => Some(ВидБинопа::И),
+            ВидЛексемы::ИлиПрисваивание              => Some(ВидБинопа::Или),
+            ВидЛексемы::ЛибоПрисваивание             => Some(ВидБинопа::Либо),
+            ВидЛексемы::ЛбсПрисваивание              => Some(ВидБинопа::ЛевоеБитовоеСмещение),
+            ВидЛексемы::ПбсПрисваивание              => Some(ВидБинопа::ПравоеБитовоеСмещение),
+            _                                        => None
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -92,11 +118,18 @@ pub struct Аргументы {
 
 #[derive(Debug, Clone)]
 pub enum Выражение {
-    ЦелЧисло(Лексема, i64),
-    НатЧисло(Лексема, u64),
-    ВещЧисло(Лексема, f32),
+    /// 5, 5цел16. Последнее поле является разрядностью.
+    ЦелЧисло(Лексема, i64, u32),
+    /// 5нат, 5нат8. Последнее поле является разрядностью.
+    НатЧисло(Лексема, u64, u32),
+    /// 1.5, 1.5вещ64. Последнее поле является разрядностью. Значение
+    /// «вещ32» уже округлено до f32.
+    ВещЧисло(Лексема, f64, u32),
     Лог(Лексема, bool),
     Строка(Лексема),
+    Символ(Лексема, char),
+    /// «Привет»(0)
+    ИндексСтроки{строка: Лексема, аргументы: Аргументы},
     Идент(Лексема),
     ЦепочкаВызовов{имя: Лексема, цепочка_аргументов: Vec<Аргументы>},
     УнарныйМинус {
@@ -112,22 +145,50 @@ pub enum Выражение {
     Отрицание {
         ключ: Лексема,
         выражение: Box<Выражение>,
-    }
+    },
+    /// Координата{строка: 1, столбец: 2}
+    ЛитералСтруктуры {
+        имя: Лексема,
+        поля: Vec<(Лексема, Выражение)>,
+    },
+    /// [1, 2, 3]
+    ЛитералМассива {
+        ключ: Лексема,
+        элементы: Vec<Выражение>,
+    },
+    /// (а, б)
+    Кортеж {
+        ключ: Лексема,
+        элементы: Vec<Выражение>,
+    },
+    /// если условие то одно иначе другое
+    Если {
+        ключ: Лексема,
+        условие: Box<Выражение>,
+        то: Box<Выражение>,
+        иначе: Box<Выражение>,
+    },
 }
 
 impl Выражение {
     pub fn лок(&self) -> &Лок {
         match self {
-            Выражение::ЦелЧисло(лексема, _) |
-            Выражение::НатЧисло(лексема, _) |
-            Выражение::ВещЧисло(лексема, _) |
+            Выражение::ЦелЧисло(лексема, ..) |
+            Выражение::НатЧисло(лексема, ..) |
+            Выражение::ВещЧисло(лексема, ..) |
             Выражение::Лог(лексема, _) |
             Выражение::Строка(лексема) |
+            Выражение::Символ(лексема, _) |
             Выражение::Идент(лексема) => &лексема.лок,
             Выражение::Биноп{ключ, ..} => &ключ.лок,
             Выражение::ЦепочкаВызовов{имя, ..} => &имя.лок,
+            Выражение::ИндексСтроки{строка, ..} => &строка.лок,
             Выражение::Отрицание{ключ, ..} => &ключ.лок,
             Выражение::УнарныйМинус{ключ, ..} => &ключ.лок,
+            Выражение::Если{ключ, ..} => &ключ.лок,
+            Выражение::Кортеж{ключ, ..} => &ключ.лок,
+            Выражение::ЛитералСтруктуры{имя, ..} => &имя.лок,
+            Выражение::ЛитералМассива{ключ, ..} => &ключ.лок,
         }
     }
 
@@ -138,65 +199,94 @@ impl Выражение {
             ВидЛексемы::ВещЧисло,
             ВидЛексемы::Идент,
             ВидЛексемы::Строка,
+            ВидЛексемы::Символ,
             ВидЛексемы::ОткрытаяСкобка,
             ВидЛексемы::Не,
             ВидЛексемы::КлючИстина,
             ВидЛексемы::КлючЛожь,
             ВидЛексемы::Минус,
+            ВидЛексемы::КлючЕсли,
+            ВидЛексемы::ОткрытаяКвадратнаяСкобка,
         ])?;
         match лексема.вид {
-            ВидЛексемы::ЦелШестЧисло => {
-                if лексема.текст.len() == 0 {
-                    диагностика!(&лексема.лок, "ОШИБКА", "Нет цифр для шестнадцатеричного литерала");
-                    return Err(());
-                }
-
-                let mut число: i64 = 0;
-                for цифра in лексема.текст.chars().map(|знак| шестнадцатеричная_цифра(&знак).expect("Все шестнадцатеричные цифры должны быть проверены на этапе лексического анализа")) {
-                    число = число*16 + цифра;
-                }
-                if лекс.подсмотреть_лексему()?.вид == ВидЛексемы::Идент {
-                    if лекс.подсмотреть_лексему()?.текст == "нат" {
-                        let _ = лекс.вытащить_лексему()?;
-                        return Ok(Выражение::НатЧисло(лексема, число as u64));
-                    }
-                }
-                Ok(Выражение::ЦелЧисло(лексема, число as i64))
-            }
-            ВидЛексемы::ЦелЧисло => {
-                let число: u64 = match лексема.текст.parse() {
-                    Ok(число) => число,
-                    Err(ошибка) => match ошибка.kind() {
-                        IntErrorKind::PosOverflow => {
-                            диагностика!(&лексема.лок, "ОШИБКА", "Слишком большое целое");
-                            return Err(());
+            ВидЛексемы::ЦелШестЧисло | ВидЛексемы::ЦелЧисло => разобрать_целое(лекс, лексема, None),
+            ВидЛексемы::ВещЧисло => {
+                let mut разрядность = 32;
+                let суффикс = лекс.подсмотреть_лексему()?;
+                if суффикс.вид == ВидЛексемы::Идент {
+                    match суффикс.текст.as_str() {
+                        "вещ" | "вещ32" => {
+                            let _ = лекс.вытащить_лексему()?;
                         }
-                        IntErrorKind::Empty => unreachable!(),
-                        IntErrorKind::InvalidDigit => unreachable!(),
-                        IntErrorKind::NegOverflow => unreachable!(),
-                        IntErrorKind::Zero => unreachable!(),
-                        _ => {
-                            диагностика!(&лексема.лок, "ОШИБКА", "Некорректное целое число");
-                            return Err(());
+                        "вещ64" => {
+                            let _ = лекс.вытащить_лексему()?;
+                            разрядность = 64;
                         }
+                        _ => {}
                     }
+                }
+                // Литерал «вещ32» разбирается сразу в f32: округление через
+                // f64 может дать другое значение.
+                let число = if разрядность == 64 {
+                    лексема.текст.parse::<f64>()
+                } else {
+                    лексема.текст.parse::<f32>().map(f64::from)
                 };
-                if лекс.подсмотреть_лексему()?.вид == ВидЛексемы::Идент {
-                    if лекс.подсмотреть_лексему()?.текст == "нат" {
-                        let _ = лекс.вытащить_лексему()?;
-                        return Ok(Выражение::НатЧисло(лексема, число));
+                let Ok(число) = число else {
+                    диагностика!(&лексема.лок, "ОШИБКА", "Некорректное вещественное число");
+                    return Err(());
+                };
+                Ok(Выражение::ВещЧисло(лексема, число, разрядность))
+            }
+            ВидЛексемы::Идент if лекс.подсмотреть_лексему()?.вид == ВидЛексемы::ОткрытаяФигурнаяСкобка => {
+                let _ = лекс.вытащить_лексему()?;
+                let mut поля = Vec::new();
+                if лекс.подсмотреть_лексему()?.вид == ВидЛексемы::ЗакрытаяФигурнаяСкобка {
+                    let _ = лекс.вытащить_лексему()?;
+                } else {
+                    loop {
+                        let имя_поля = лекс.вытащить_лексему_вида(&[ВидЛексемы::Идент])?;
+                        let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::Двоеточие])?;
+                        поля.push((имя_поля, Выражение::разобрать(лекс)?));
+                        let лексема = лекс.вытащить_лексему_вида(&[
+                            ВидЛексемы::ЗакрытаяФигурнаяСкобка,
+                            ВидЛексемы::Запятая,
+                        ])?;
+                        // Допускается запятая после последнего поля.
+                        if лексема.вид == ВидЛексемы::ЗакрытаяФигурнаяСкобка {
+                            break;
+                        }
+                        if лекс.подсмотреть_лексему()?.вид == ВидЛексемы::ЗакрытаяФигурнаяСкобка {
+                            let _ = лекс.вытащить_лексему()?;
+                            break;
+                        }
                     }
                 }
-                Ok(Выражение::ЦелЧисло(лексема, число as i64))
+                Ok(Выражение::ЛитералСтруктуры{имя: лексема, поля})
             }
-            ВидЛексемы::ВещЧисло => {
-                match лексема.текст.parse() {
-                    Ok(число) => Ok(Выражение::ВещЧисло(лексема, число)),
-                    Err(_ошибка) => {
-                        диагностика!(&лексема.лок, "ОШИБКА", "Некорректное вещественное число");
-                        Err(())
+            ВидЛексемы::ОткрытаяКвадратнаяСкобка => {
+                let ключ = лексема;
+                let mut элементы = Vec::new();
+                if лекс.подсмотреть_лексему()?.вид == ВидЛексемы::ЗакрытаяКвадратнаяСкобка {
+                    let _ = лекс.вытащить_лексему()?;
+                } else {
+                    loop {
+                        элементы.push(Выражение::разобрать(лекс)?);
+                        let лексема = лекс.вытащить_лексему_вида(&[
+                            ВидЛексемы::ЗакрытаяКвадратнаяСкобка,
+                            ВидЛексемы::Запятая,
+                        ])?;
+                        // Допускается запятая после последнего элемента.
+                        if лексема.вид == ВидЛексемы::ЗакрытаяКвадратнаяСкобка {
+                            break;
+                        }
+                        if лекс.подсмотреть_лексему()?.вид == ВидЛексемы::ЗакрытаяКвадратнаяСкобка {
+                            let _ = лекс.вытащить_лексему()?;
+                            break;
+                        }
                     }
                 }
+                Ok(Выражение::ЛитералМассива{ключ, элементы})
             }
             ВидЛексемы::Идент => {
                 let mut цепочка_аргументов = Vec::new();
@@ -216,9 +306,33 @@ impl Выражение {
                     })
                 }
             },
-            ВидЛексемы::Строка => Ok(Выражение::Строка(лексема)),
+            ВидЛексемы::Символ => {
+                let символ = лексема.текст.chars().next().expect("Лексер гарантирует, что символьный литерал содержит ровно один символ");
+                Ok(Выражение::Символ(лексема, символ))
+            }
+            ВидЛексемы::Строка => {
+                if лекс.подсмотреть_лексему()?.вид == ВидЛексемы::ОткрытаяСкобка {
+                    let ключ = лекс.вытащить_лексему().unwrap();
+                    Ok(Выражение::ИндексСтроки {
+                        строка: лексема,
+                        аргументы: Аргументы {
+                            ключ,
+                            выражения: разобрать_список_аргументов_вызова(лекс)?
+                        },
+                    })
+                } else {
+                    Ok(Выражение::Строка(лексема))
+                }
+            }
             ВидЛексемы::ОткрытаяСкобка => {
+                let ключ = лексема;
                 let выражение = Выражение::разобрать(лекс)?;
+                if лекс.подсмотреть_лексему()?.вид == ВидЛексемы::Запятая {
+                    let _ = лекс.вытащить_лексему()?;
+                    let mut элементы = vec![выражение];
+                    элементы.extend(разобрать_список_аргументов_вызова(лекс)?);
+                    return Ok(Выражение::Кортеж{ключ, элементы});
+                }
                 let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::ЗакрытаяСкобка])?;
                 Ok(выражение)
             }
@@ -229,9 +343,22 @@ impl Выражение {
             }
             ВидЛексемы::Минус => {
                 let ключ = лексема;
+                if let ВидЛексемы::ЦелЧисло | ВидЛексемы::ЦелШестЧисло = лекс.подсмотреть_лексему()?.вид {
+                    let лексема = лекс.вытащить_лексему()?;
+                    return разобрать_целое(лекс, лексема, Some(ключ))
+                }
                 let выражение = Box::new(Выражение::разобрать_первичное(лекс)?);
                 Ok(Выражение::УнарныйМинус{ключ, выражение})
             }
+            ВидЛексемы::КлючЕсли => {
+                let ключ = лексема;
+                let условие = Box::new(Выражение::разобрать(лекс)?);
+                let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::КлючТо])?;
+                let то = Box::new(Выражение::разобрать(лекс)?);
+                let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::КлючИначе])?;
+                let иначе = Box::new(Выражение::разобрать(лекс)?);
+                Ok(Выражение::Если{ключ, условие, то, иначе})
+            }
             ВидЛексемы::КлючИстина => Ok(Выражение::Лог(лексема, true)),
             ВидЛексемы::КлючЛожь => Ok(Выражение::Лог(лексема, false)),
             _ => unreachable!(),
@@ -275,36 +402,172 @@ pub struct ВеткаВилки {
 #[derive(Debug)]
 pub enum Утверждение {
     Присваивание{ключ: Лексема, левое: Выражение, правое: Выражение},
+    /// а(индекс()) += 1; — левая часть вычисляется только один раз.
+    СоставноеПрисваивание{ключ: Лексема, вид: ВидБинопа, левое: Выражение, правое: Выражение},
     Выражение{выражение: Выражение},
     Пока{ключ: Лексема, условие: Выражение, тело: Vec<Утверждение>},
-    Для{ключ: Лексема, индекс: Лексема, нижняя_граница: Выражение, верхняя_граница: Выражение, тело: Vec<Утверждение>},
+    /// для индекс := 0..-?10 шаг 2 нч ... кц
+    Для{ключ: Лексема, индекс: Лексема, нижняя_граница: Выражение, верхняя_граница: Выражение, включая_конец: bool, шаг: Option<Выражение>, тело: Vec<Утверждение>},
+    /// для индекс, элемент в коллекция нч ... кц
+    ДляЭлементов{ключ: Лексема, индекс: Option<Лексема>, элемент: Лексема, коллекция: Выражение, тело: Vec<Утверждение>},
     Если{ключ: Лексема, условие: Выражение, тело: Vec<Утверждение>, иначе: Vec<Утверждение>},
     Вернуть{ключ: Лексема, выражение: Option<Выражение>},
     ДекларацияПеременной{ключ: Лексема, имя: Лексема, тип: Выражение, значение: Option<Выражение>},
     ДекларацияКонстанты{ключ: Лексема, имя: Лексема, значение: Выражение},
+    /// пер (х, ок) := разобрать_число(с);
+    ДекларацияКортежа{ключ: Лексема, имена: Vec<Лексема>, значение: Выражение},
     Вилка {ключ: Лексема, выражение: Выражение, ветки: Vec<ВеткаВилки>, любое: Option<(Лексема, Vec<Утверждение>)>},
 }
 
+impl Утверждение {
+    /// Место, с которого начинается утверждение.
+    pub fn лок(&self) -> &Лок {
+        match self {
+            Утверждение::Присваивание{левое, ..} |
+            Утверждение::СоставноеПрисваивание{левое, ..} => левое.лок(),
+            Утверждение::Выражение{выражение} => выражение.лок(),
+            Утверждение::Пока{ключ, ..} |
+            Утверждение::Для{ключ, ..} |
+            Утверждение::ДляЭлементов{ключ, ..} |
+            Утверждение::Если{ключ, ..} |
+            Утверждение::Вернуть{ключ, ..} |
+            Утверждение::ДекларацияПеременной{ключ, ..} |
+            Утверждение::ДекларацияКонстанты{ключ, ..} |
+            Утверждение::ДекларацияКортежа{ключ, ..} |
+            Утверждение::Вилка{ключ, ..} => &ключ.лок,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Параметр {
     pub имя: Лексема,
     pub тип: Выражение,
+    /// Параметр передаётся по ссылке. Пока так передаётся только «сам»
+    /// в процедурах структуры.
+    pub ссылка: bool,
 }
 
 #[derive(Debug)]
 pub enum ТелоПроцедуры {
-    Внутренее { блок: Vec<Утверждение> },
+    /// Конец тела — его «кц», либо «то», если тело состоит из одного
+    /// утверждения.
+    Внутренее { блок: Vec<Утверждение>, конец: Лексема },
     Внешнее { символ: Лексема },
+    /// В теле были синтаксические ошибки. О них уже сообщили, а
+    /// процедуру можно проверять только по её сигнатуре.
+    Испорченное,
 }
 
 #[derive(Debug)]
 pub struct Процедура {
     pub имя: Лексема,
+    /// Структура, для которой объявлена процедура («про Вектор2.длина(сам)»).
+    /// Имя такой процедуры имеет вид «Вектор2.длина».
+    pub владелец: Option<Лексема>,
     pub параметры: Vec<Параметр>,
+    /// Многоточие в конце списка параметров. Означает, что процедура
+    /// вариативная (как printf в Си).
+    pub многоточие: Option<Лексема>,
     pub тип_результата: Option<Выражение>,
     pub тело: ТелоПроцедуры,
 }
 
+/// Минус перед целым литералом относится к самому литералу, чтобы
+/// наименьшее значение знакового типа, например «-128цел8», можно было
+/// записать без переполнения.
+fn разобрать_целое(лекс: &mut Лексер, лексема: Лексема, минус: Option<Лексема>) -> Результат<Выражение> {
+    match лексема.вид {
+        ВидЛексемы::ЦелШестЧисло => {
+            if лексема.текст.is_empty() {
+                диагностика!(&лексема.лок, "ОШИБКА", "Нет цифр для шестнадцатеричного литерала");
+                return Err(());
+            }
+    
+            let mut число: i64 = 0;
+            for цифра in лексема.текст.chars().map(|знак| шестнадцатеричная_цифра(&знак).expect("Все шестнадцатеричные цифры должны быть проверены на этапе лексического анализа")) {
+                число = число*16 + цифра;
+            }
+            разобрать_суффикс_целого(лекс, лексема, число as u64, минус)
+        }
+        ВидЛексемы::ЦелЧисло => {
+            let число: u64 = match лексема.текст.parse() {
+                Ok(число) => число,
+                Err(ошибка) => match ошибка.kind() {
+                    IntErrorKind::PosOverflow => {
+                        диагностика!(&лексема.лок, "ОШИБКА", "Слишком большое целое");
+                        return Err(());
+                    }
+                    IntErrorKind::Empty => unreachable!(),
+                    IntErrorKind::InvalidDigit => unreachable!(),
+                    IntErrorKind::NegOverflow => unreachable!(),
+                    IntErrorKind::Zero => unreachable!(),
+                    _ => {
+                        диагностика!(&лексема.лок, "ОШИБКА", "Некорректное целое число");
+                        return Err(());
+                    }
+                }
+            };
+            разобрать_суффикс_целого(лекс, лексема, число, минус)
+        }
+        _ => unreachable!(),
+    }
+}
+
+/// Разрядность целого задаётся суффиксом литерала: «5нат», «5нат8»,
+/// «5цел16». Без суффикса литерал имеет тип «цел64».
+fn разобрать_суффикс_целого(лекс: &mut Лексер, лексема: Лексема, число: u64, минус: Option<Лексема>) -> Результат<Выражение> {
+    let mut знаковый = true;
+    let mut разрядность = 64;
+    let суффикс = лекс.подсмотреть_лексему()?;
+    if суффикс.вид == ВидЛексемы::Идент {
+        let известный = match суффикс.текст.as_str() {
+            "нат" | "нат64" => Some((false, 64)),
+            "нат8" => Some((false, 8)),
+            "нат16" => Some((false, 16)),
+            "нат32" => Some((false, 32)),
+            "цел" | "цел64" => Some((true, 64)),
+            "цел8" => Some((true, 8)),
+            "цел16" => Some((true, 16)),
+            "цел32" => Some((true, 32)),
+            _ => None,
+        };
+        if let Some((знаковый_суффикса, разрядность_суффикса)) = известный {
+            let _ = лекс.вытащить_лексему()?;
+            знаковый = знаковый_суффикса;
+            разрядность = разрядность_суффикса;
+            let макс = if знаковый { u64::MAX >> (65 - разрядность) } else { u64::MAX >> (64 - разрядность) };
+            match &минус {
+                Some(минус) if знаковый => if число > макс + 1 {
+                    диагностика!(&минус.лок, "ОШИБКА", "Число -{число} не помещается в тип «{тип}». Наименьшее значение этого типа -{мин}", тип = суффикс.текст, мин = макс + 1);
+                    return Err(());
+                }
+                _ => if число > макс {
+                    диагностика!(&лексема.лок, "ОШИБКА", "Число {число} не помещается в тип «{тип}». Наибольшее значение этого типа {макс}", тип = суффикс.текст);
+                    return Err(());
+                }
+            }
+        }
+    }
+    match минус {
+        Some(ключ) if знаковый => {
+            let лексема = Лексема {
+                вид: лексема.вид,
+                текст: format!("-{}", лексема.текст),
+                лок: ключ.лок,
+            };
+            Ok(Выражение::ЦелЧисло(лексема, (число as i64).wrapping_neg(), разрядность))
+        }
+        // Натуральные не бывают отрицательными: об этом сообщит компилятор.
+        Some(ключ) => Ok(Выражение::УнарныйМинус {
+            ключ,
+            выражение: Box::new(Выражение::НатЧисло(лексема, число, разрядность)),
+        }),
+        None if знаковый => Ok(Выражение::ЦелЧисло(лексема, число as i64, разрядность)),
+        None => Ok(Выражение::НатЧисло(лексема, число, разрядность)),
+    }
+}
+
 fn разобрать_утверждение(лекс: &mut Лексер) -> Результат<Утверждение> {
     match лекс.подсмотреть_лексему()?.вид {
         ВидЛексемы::КлючЕсли => {
@@ -329,12 +592,42 @@ fn разобрать_утверждение(лекс: &mut Лексер) -> Р
         ВидЛексемы::КлючДля => {
             let ключ = лекс.вытащить_лексему().unwrap();
             let индекс = лекс.вытащить_лексему_вида(&[ВидЛексемы::Идент])?;
+            let следующая = лекс.подсмотреть_лексему()?;
+            if следующая.вид == ВидЛексемы::Запятая || (следующая.вид == ВидЛексемы::Идент && следующая.текст == "в") {
+                let (индекс, элемент) = if следующая.вид == ВидЛексемы::Запятая {
+                    let _ = лекс.вытащить_лексему().unwrap();
+                    (Some(индекс), лекс.вытащить_лексему_вида(&[ВидЛексемы::Идент])?)
+                } else {
+                    (None, индекс)
+                };
+                let в = лекс.вытащить_лексему_вида(&[ВидЛексемы::Идент])?;
+                if в.текст != "в" {
+                    диагностика!(&в.лок, "ОШИБКА", "Ожидалось «в», но повстречалось «{текст}»", текст = в.текст);
+                    return Err(());
+                }
+                let коллекция = Выражение::разобрать(лекс)?;
+                let тело = разобрать_блок_кода(лекс)?;
+                return Ok(Утверждение::ДляЭлементов{ключ, индекс, элемент, коллекция, тело});
+            }
             let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::Присваивание])?;
             let нижняя_граница = Выражение::разобрать(лекс)?;
             let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::ТочкаТочка])?;
+            let включая_конец = if лекс.подсмотреть_лексему()?.вид == ВидЛексемы::Меньше {
+                let _ = лекс.вытащить_лексему().unwrap();
+                false
+            } else {
+                true
+            };
             let верхняя_граница = Выражение::разобрать(лекс)?;
+            let следующая = лекс.подсмотреть_лексему()?;
+            let шаг = if следующая.вид == ВидЛексемы::Идент && следующая.текст == "шаг" {
+                let _ = лекс.вытащить_лексему().unwrap();
+                Some(Выражение::разобрать(лекс)?)
+            } else {
+                None
+            };
             let тело = разобрать_блок_кода(лекс)?;
-            Ok(Утверждение::Для{ключ, индекс, нижняя_граница, верхняя_граница, тело})
+            Ok(Утверждение::Для{ключ, индекс, нижняя_граница, верхняя_граница, включая_конец, шаг, тело})
         }
         ВидЛексемы::КлючВернуть => {
             let ключ = лекс.вытащить_лексему().unwrap();
@@ -342,13 +635,35 @@ fn разобрать_утверждение(лекс: &mut Лексер) -> Р
                 let _ = лекс.вытащить_лексему().unwrap();
                 Ok(Утверждение::Вернуть{ключ, выражение: None})
             } else {
-                let выражение = Some(Выражение::разобрать(лекс)?);
-                let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::ТочкаЗапятая])?;
+                let mut элементы = vec![Выражение::разобрать(лекс)?];
+                while лекс.вытащить_лексему_вида(&[ВидЛексемы::ТочкаЗапятая, ВидЛексемы::Запятая])?.вид == ВидЛексемы::Запятая {
+                    элементы.push(Выражение::разобрать(лекс)?);
+                }
+                // вернуть а, б; — то же самое, что и вернуть (а, б);
+                let выражение = if элементы.len() == 1 {
+                    элементы.pop()
+                } else {
+                    Some(Выражение::Кортеж{ключ: ключ.clone(), элементы})
+                };
                 Ok(Утверждение::Вернуть{ключ, выражение})
             }
         }
         ВидЛексемы::КлючПер => {
             let ключ = лекс.вытащить_лексему().unwrap();
+            if лекс.подсмотреть_лексему()?.вид == ВидЛексемы::ОткрытаяСкобка {
+                let _ = лекс.вытащить_лексему()?;
+                let mut имена = Vec::new();
+                loop {
+                    имена.push(лекс.вытащить_лексему_вида(&[ВидЛексемы::Идент])?);
+                    if лекс.вытащить_лексему_вида(&[ВидЛексемы::ЗакрытаяСкобка, ВидЛексемы::Запятая])?.вид == ВидЛексемы::ЗакрытаяСкобка {
+                        break;
+                    }
+                }
+                let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::Присваивание])?;
+                let значение = Выражение::разобрать(лекс)?;
+                let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::ТочкаЗапятая])?;
+                return Ok(Утверждение::ДекларацияКортежа{ключ, имена, значение});
+            }
             let имя = лекс.вытащить_лексему_вида(&[ВидЛексемы::Идент])?;
             let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::Двоеточие])?;
             let тип = Выражение::разобрать(лекс)?;
@@ -407,6 +722,19 @@ fn разобрать_утверждение(лекс: &mut Лексер) -> Р
             let левое = Выражение::разобрать(лекс)?;
             let ключ = лекс.вытащить_лексему_вида(&[
                 ВидЛексемы::Присваивание,
+                ВидЛексемы::ПлюсПрисваивание,
+                ВидЛексемы::МинусПрисваивание,
+                ВидЛексемы::ЗвёздочкаПрисваивание,
+                ВидЛексемы::ПрямаяНаклоннаяПрисваивание,
+                ВидЛексемы::ПлюсПроцентПрисваивание,
+                ВидЛексемы::МинусПроцентПрисваивание,
+                ВидЛексемы::ЗвёздочкаПроцентПрисваивание,
+                ВидЛексемы::ОстПрисваивание,
+                ВидЛексемы::ИПрисваивание,
+                ВидЛексемы::ИлиПрисваивание,
+                ВидЛексемы::ЛибоПрисваивание,
+                ВидЛексемы::ЛбсПрисваивание,
+                ВидЛексемы::ПбсПрисваивание,
                 ВидЛексемы::ТочкаЗапятая,
             ])?;
             match ключ.вид {
@@ -416,27 +744,98 @@ fn разобрать_утверждение(лекс: &mut Лексер) -> Р
                     Ok(Утверждение::Присваивание {ключ, левое, правое})
                 }
                 ВидЛексемы::ТочкаЗапятая => Ok(Утверждение::Выражение {выражение: левое}),
-                _ => unreachable!(),
+                вид => {
+                    let вид = ВидБинопа::по_виду_составного_присваивания(&вид).unwrap();
+                    let правое = Выражение::разобрать(лекс)?;
+                    let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::ТочкаЗапятая])?;
+                    Ok(Утверждение::СоставноеПрисваивание {ключ, вид, левое, правое})
+                }
             }
         }
     }
 }
 
 fn разобрать_блок_кода(лекс: &mut Лексер) -> Результат<Vec<Утверждение>> {
+    let (блок, _) = разобрать_блок_кода_с_концом(лекс)?;
+    Ok(блок)
+}
+
+/// Возвращает также «кц» блока, либо «то», если блок состоит из одного
+/// утверждения.
+///
+/// Утверждения с синтаксическими ошибками пропускаются, и разбор
+/// продолжается со следующего. Поэтому о том, что блок разобран не
+/// полностью, можно узнать лишь по количеству ошибок.
+fn разобрать_блок_кода_с_концом(лекс: &mut Лексер) -> Результат<(Vec<Утверждение>, Лексема)> {
     let mut блок = Vec::new();
     let ключ = лекс.вытащить_лексему_вида(&[ВидЛексемы::КлючНч, ВидЛексемы::КлючТо])?;
     match ключ.вид {
         ВидЛексемы::КлючНч => loop {
-            if лекс.подсмотреть_лексему()?.вид == ВидЛексемы::КлючКц {
-                let _ = лекс.вытащить_лексему()?;
-                break;
+            // Ошибку лексики сообщит разбор утверждения.
+            if let Ok(Лексема{вид: ВидЛексемы::КлючКц, ..}) = лекс.подсмотреть_лексему() {
+                let конец = лекс.вытащить_лексему()?;
+                return Ok((блок, конец));
+            }
+            match разобрать_утверждение(лекс) {
+                Ok(утверждение) => блок.push(утверждение),
+                Err(()) => пропустить_утверждение(лекс)?,
             }
-            блок.push(разобрать_утверждение(лекс)?);
         }
-        ВидЛексемы::КлючТо => блок.push(разобрать_утверждение(лекс)?),
+        ВидЛексемы::КлючТо => {
+            match разобрать_утверждение(лекс) {
+                Ok(утверждение) => блок.push(утверждение),
+                Err(()) => пропустить_утверждение(лекс)?,
+            }
+            Ok((блок, ключ))
+        }
         _ => unreachable!()
     }
-    Ok(блок)
+}
+
+/// Пропускает остаток утверждения, в котором случилась ошибка: всё до
+/// точки с запятой включительно, либо до «кц» охватывающего блока.
+/// Вложенные блоки и ветки «иначе» пропускаются целиком.
+///
+/// Ошибка означает, что продолжать разбор нет смысла: файл закончился,
+/// либо ошибок уже слишком много.
+fn пропустить_утверждение(лекс: &mut Лексер) -> Результат<()> {
+    if предел_ошибок_достигнут() {
+        return Err(())
+    }
+    let mut вложенность: usize = 0;
+    loop {
+        let Ok(лексема) = лекс.подсмотреть_лексему() else {
+            // О неизвестной лексеме уже сообщили, и лексер её пропустил.
+            let _ = лекс.вытащить_лексему();
+            if предел_ошибок_достигнут() {
+                return Err(())
+            }
+            continue
+        };
+        match лексема.вид {
+            ВидЛексемы::Конец => return Err(()),
+            ВидЛексемы::КлючКц if вложенность == 0 => return Ok(()),
+            ВидЛексемы::КлючНч => {
+                let _ = лекс.вытащить_лексему();
+                вложенность += 1;
+                continue
+            }
+            ВидЛексемы::КлючКц => {
+                let _ = лекс.вытащить_лексему();
+                вложенность -= 1;
+            }
+            ВидЛексемы::ТочкаЗапятая => {
+                let _ = лекс.вытащить_лексему();
+            }
+            _ => {
+                let _ = лекс.вытащить_лексему();
+                continue
+            }
+        }
+        if вложенность == 0 && !matches!(лекс.подсмотреть_лексему(), Ok(Лексема{вид: ВидЛексемы::КлючИначе, ..})) {
+            return Ok(())
+        }
+    }
 }
 
 fn разобрать_список_аргументов_вызова(лекс: &mut Лексер) -> Результат<Vec<Выражение>> {
@@ -460,13 +859,20 @@ fn разобрать_список_аргументов_вызова(лекс: &
     Ok(аргументы)
 }
 
-fn разобрать_список_параметров_процедуры(лекс: &mut Лексер) -> Результат<Vec<Параметр>> {
+fn разобрать_список_параметров_процедуры(лекс: &mut Лексер, владелец: Option<&Лексема>) -> Результат<(Vec<Параметр>, Option<Лексема>)> {
     let mut параметры: Vec<Параметр> = Vec::new();
+    let mut многоточие = None;
     let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::ОткрытаяСкобка])?;
     if лекс.подсмотреть_лексему()?.вид == ВидЛексемы::ЗакрытаяСкобка {
         let _ = лекс.вытащить_лексему()?;
     } else {
         'разбор_параметров: loop {
+            if лекс.подсмотреть_лексему()?.вид == ВидЛексемы::Многоточие {
+                многоточие = Some(лекс.вытащить_лексему().unwrap());
+                // Многоточие может быть только последним в списке параметров.
+                let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::ЗакрытаяСкобка])?;
+                break 'разбор_параметров
+            }
             let имя = лекс.вытащить_лексему_вида(&[ВидЛексемы::Идент])?;
             if let Some(существующий_параметр) = параметры.iter().find(|параметр| параметр.имя.текст == имя.текст) {
                 диагностика!(&имя.лок, "ОШИБКА", "переопределение параметра «{имя}»",
@@ -474,9 +880,19 @@ fn разобрать_список_параметров_процедуры(ле
                 диагностика!(&существующий_параметр.имя.лок, "ИНФО", "параметр с тем же именем определен тут");
                 return Err(());
             }
-            let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::Двоеточие])?;
-            let тип = Выражение::разобрать(лекс)?;
-            параметры.push(Параметр {имя, тип});
+            // «сам» без типа может быть только первым параметром процедуры
+            // структуры и передаётся по ссылке.
+            let сам = имя.текст == "сам" && параметры.is_empty() && лекс.подсмотреть_лексему()?.вид != ВидЛексемы::Двоеточие;
+            match владелец {
+                Some(владелец) if сам => {
+                    параметры.push(Параметр {имя, тип: Выражение::Идент(владелец.clone()), ссылка: true});
+                }
+                _ => {
+                    let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::Двоеточие])?;
+                    let тип = Выражение::разобрать(лекс)?;
+                    параметры.push(Параметр {имя, тип, ссылка: false});
+                }
+            }
             let лексема = лекс.вытащить_лексему_вида(&[
                 ВидЛексемы::ЗакрытаяСкобка,
                 ВидЛексемы::Запятая
@@ -486,13 +902,20 @@ fn разобрать_список_параметров_процедуры(ле
             }
         }
     }
-    Ok(параметры)
+    Ok((параметры, многоточие))
 }
 
 impl Процедура {
     pub fn разобрать(лекс: &mut Лексер) -> Результат<Процедура> {
-        let имя = лекс.вытащить_лексему_вида(&[ВидЛексемы::Идент])?;
-        let параметры = разобрать_список_параметров_процедуры(лекс)?;
+        let mut имя = лекс.вытащить_лексему_вида(&[ВидЛексемы::Идент])?;
+        let mut владелец = None;
+        if лекс.подсмотреть_лексему()?.вид == ВидЛексемы::Точка {
+            let _ = лекс.вытащить_лексему().unwrap();
+            let имя_процедуры = лекс.вытащить_лексему_вида(&[ВидЛексемы::Идент])?;
+            владелец = Some(имя.clone());
+            имя.текст = format!("{}.{}", имя.текст, имя_процедуры.текст);
+        }
+        let (параметры, многоточие) = разобрать_список_параметров_процедуры(лекс, владелец.as_ref())?;
         let тип_результата = if лекс.подсмотреть_лексему()?.вид == ВидЛексемы::Двоеточие {
             let _ = лекс.вытащить_лексему().unwrap();
             let тип = Выражение::разобрать(лекс)?;
@@ -506,10 +929,15 @@ impl Процедура {
             let _ = лекс.вытащить_лексему_вида(&[ВидЛексемы::ТочкаЗапятая])?;
             ТелоПроцедуры::Внешнее {символ}
         } else {
-            let блок = разобрать_блок_кода(лекс)?;
-            ТелоПроцедуры::Внутренее {блок}
+            let ошибок_до_тела = количество_ошибок();
+            let (блок, конец) = разобрать_блок_кода_с_концом(лекс)?;
+            if количество_ошибок() > ошибок_до_тела {
+                ТелоПроцедуры::Испорченное
+            } else {
+                ТелоПроцедуры::Внутренее {блок, конец}
+            }
         };
-        Ok(Процедура{имя, параметры, тело, тип_результата})
+        Ok(Процедура{имя, владелец, параметры, многоточие, тело, тип_результата})
     }
 }
 
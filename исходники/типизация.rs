@@ -2,6 +2,7 @@ use super::Результат;
 use std::collections::HashMap;
 use диагностика::*;
 use лексика::*;
+use интерпретатор::РАЗМЕР_СЛОВА;
 
 #[derive(Clone)]
 pub struct Поле {
@@ -20,54 +21,127 @@ pub struct Структура {
 #[derive(PartialEq, Debug, Clone)]
 pub enum Тип {
     Нат8,
+    Нат16,
+    Нат32,
     Нат64,
+    Цел8,
+    Цел16,
+    Цел32,
     Цел64,
     Вещ32,
+    Вещ64,
     Лог,
+    /// Кодовая точка Юникода.
+    Символ,
     Массив { размер: usize, тип_элемента: Box<Тип> },
     Срез { тип_элемента: Box<Тип> },
     Структура(String),
+    /// (цел, лог). Элементы лежат в памяти друг за другом и, как поля
+    /// структуры, выровнены по слову.
+    Кортеж(Vec<Тип>),
+    /// «тип Метры := вещ отдельный;». Устроен так же, как основа, но не
+    /// смешивается ни с ней, ни с другими типами без явного «как».
+    Отдельный { имя: String, основа: Box<Тип> },
+    /// Тип объявления, которое не удалось скомпилировать. Об ошибке уже
+    /// сообщили, поэтому выражения с такими значениями молча не
+    /// компилируются, чтобы одна ошибка не тянула за собой другие.
+    Ошибка,
 }
 
 pub const СРЕЗ_РАЗМЕР_СМЕЩЕНИЕ: usize = 0;
 pub const СРЕЗ_АДРЕС_СМЕЩЕНИЕ: usize = 8;
+/// Суррогаты UTF-16 лежат в пределах Юникода, но символами не являются.
+pub const СУРРОГАТЫ: std::ops::RangeInclusive<u32> = 0xD800..=0xDFFF;
 
 impl Тип {
     pub fn примитивный(&self) -> bool {
         match self {
-            Тип::Цел64 | Тип::Нат8 | Тип::Нат64 | Тип::Вещ32 | Тип::Лог => true,
-            Тип::Массив {..} | Тип::Срез {..} | Тип::Структура {..} => false,
+            Тип::Нат8 | Тип::Нат16 | Тип::Нат32 | Тип::Нат64 |
+            Тип::Цел8 | Тип::Цел16 | Тип::Цел32 | Тип::Цел64 |
+            Тип::Вещ32 | Тип::Вещ64 | Тип::Лог | Тип::Символ => true,
+            Тип::Массив {..} | Тип::Срез {..} | Тип::Структура {..} | Тип::Кортеж {..} | Тип::Ошибка => false,
+            Тип::Отдельный {основа, ..} => основа.примитивный(),
         }
     }
 
     pub fn примитивное_знаковое_чтение(&self) -> Option<bool> {
         match self {
-            Тип::Цел64 => Some(true),
-            Тип::Нат8 | Тип::Нат64 | Тип::Вещ32 | Тип::Лог => Some(false),
-            Тип::Массив {..} | Тип::Срез {..} | Тип::Структура {..} => None,
+            Тип::Цел8 | Тип::Цел16 | Тип::Цел32 | Тип::Цел64 => Some(true),
+            Тип::Нат8 | Тип::Нат16 | Тип::Нат32 | Тип::Нат64 | Тип::Вещ32 | Тип::Вещ64 | Тип::Лог | Тип::Символ => Some(false),
+            Тип::Массив {..} | Тип::Срез {..} | Тип::Структура {..} | Тип::Кортеж {..} | Тип::Ошибка => None,
+            Тип::Отдельный {основа, ..} => основа.примитивное_знаковое_чтение(),
+        }
+    }
+
+    /// Знаковость и разрядность целочисленных типов.
+    pub fn целое(&self) -> Option<(bool, u32)> {
+        match self {
+            Тип::Нат8 => Some((false, 8)),
+            Тип::Нат16 => Some((false, 16)),
+            Тип::Нат32 => Some((false, 32)),
+            Тип::Нат64 => Some((false, 64)),
+            Тип::Цел8 => Some((true, 8)),
+            Тип::Цел16 => Some((true, 16)),
+            Тип::Цел32 => Some((true, 32)),
+            Тип::Цел64 => Some((true, 64)),
+            Тип::Символ => Some((false, 32)),
+            Тип::Вещ32 | Тип::Вещ64 | Тип::Лог | Тип::Массив {..} | Тип::Срез {..} | Тип::Структура {..} | Тип::Кортеж {..} | Тип::Ошибка => None,
+            Тип::Отдельный {основа, ..} => основа.целое(),
+        }
+    }
+
+    /// Целочисленный тип заданной знаковости и разрядности.
+    pub fn целое_разрядности(знаковый: bool, разрядность: u32) -> Тип {
+        match (знаковый, разрядность) {
+            (false, 8) => Тип::Нат8,
+            (false, 16) => Тип::Нат16,
+            (false, 32) => Тип::Нат32,
+            (false, 64) => Тип::Нат64,
+            (true, 8) => Тип::Цел8,
+            (true, 16) => Тип::Цел16,
+            (true, 32) => Тип::Цел32,
+            (true, 64) => Тип::Цел64,
+            _ => unreachable!("Целых разрядности {} нет", разрядность),
         }
     }
 
     pub fn текст(&self) -> String {
         match self {
+            Тип::Цел8 => "цел8".to_string(),
+            Тип::Цел16 => "цел16".to_string(),
+            Тип::Цел32 => "цел32".to_string(),
             Тип::Цел64 => "цел64".to_string(),
             Тип::Нат8 => "нат8".to_string(),
+            Тип::Нат16 => "нат16".to_string(),
+            Тип::Нат32 => "нат32".to_string(),
             Тип::Нат64 => "нат64".to_string(),
             Тип::Вещ32 => "вещ32".to_string(),
+            Тип::Вещ64 => "вещ64".to_string(),
             Тип::Лог => "лог".to_string(),
+            Тип::Символ => "символ".to_string(),
             Тип::Массив {тип_элемента, размер} => format!("массив({размер}, {тип_элемента})", тип_элемента = тип_элемента.текст()),
             Тип::Срез {тип_элемента} => format!("срез({тип_элемента})", тип_элемента = тип_элемента.текст()),
             Тип::Структура(имя) => имя.clone(),
+            Тип::Кортеж(элементы) => format!("({})", элементы.iter().map(|тип| тип.текст()).collect::<Vec<_>>().join(", ")),
+            Тип::Отдельный {имя, ..} => имя.clone(),
+            Тип::Ошибка => "<ошибка>".to_string(),
         }
     }
 
     pub fn размер(&self, структуры: &HashMap<String, Структура>) -> usize {
         match self {
             Тип::Нат8 => 1,
+            Тип::Нат16 => 2,
+            Тип::Нат32 => 4,
             Тип::Нат64 => 8,
+            Тип::Цел8 => 1,
+            Тип::Цел16 => 2,
+            Тип::Цел32 => 4,
             Тип::Цел64 => 8,
             Тип::Вещ32 => 4,
+            Тип::Вещ64 => 8,
             Тип::Лог => 8,
+            Тип::Символ => 4,
             Тип::Массив {тип_элемента, размер} => тип_элемента.размер(структуры) * размер,
             Тип::Срез {..} => 16, // Два 64-х битных числа: указатель на начало и размер.
             Тип::Структура (имя) => {
@@ -76,17 +150,60 @@ impl Тип {
                     .expect("Существование структуры должно быть уже проверено на этапе компиляции типа")
                     .размер
             }
+            Тип::Кортеж(элементы) => элементы.iter().map(|тип| выравнять_размер_по_слову(тип.размер(структуры))).sum(),
+            Тип::Отдельный {основа, ..} => основа.размер(структуры),
+            Тип::Ошибка => 0,
+        }
+    }
+
+    /// Содержит ли тип где-нибудь внутри себя «Тип::Ошибка».
+    pub fn отравлен(&self) -> bool {
+        match self {
+            Тип::Ошибка => true,
+            Тип::Массив {тип_элемента, ..} | Тип::Срез {тип_элемента} => тип_элемента.отравлен(),
+            Тип::Кортеж(элементы) => элементы.iter().any(|тип| тип.отравлен()),
+            Тип::Отдельный {основа, ..} => основа.отравлен(),
+            _ => false,
         }
     }
+
+    /// Тип, над которым выполняются операции. Для отдельных типов это
+    /// их основа, для остальных сам тип.
+    pub fn основа(&self) -> &Тип {
+        match self {
+            Тип::Отдельный {основа, ..} => основа.основа(),
+            тип => тип,
+        }
+    }
+
+    /// Смещения элементов кортежа относительно его начала.
+    pub fn смещения_элементов(элементы: &[Тип], структуры: &HashMap<String, Структура>) -> Vec<usize> {
+        let mut смещение = 0;
+        элементы.iter().map(|тип| {
+            let текущее = смещение;
+            смещение += выравнять_размер_по_слову(тип.размер(структуры));
+            текущее
+        }).collect()
+    }
+}
+
+pub fn выравнять_размер_по_слову(размер: usize) -> usize {
+    размер.div_ceil(РАЗМЕР_СЛОВА)*РАЗМЕР_СЛОВА
 }
 
 pub fn проверить_типы(лок: &Лок, ожидаемый_тип: &Тип, действительный_тип: &Тип) -> Результат<()> {
+    if ожидаемый_тип.отравлен() || действительный_тип.отравлен() {
+        return Err(())
+    }
     if ожидаемый_тип == действительный_тип {
         Ok(())
     } else {
         диагностика!(лок, "ОШИБКА", "Несоответствие типов данных. Ожидался тип «{ожидаемый}», но повстречался тип «{действительный}»",
                      ожидаемый = ожидаемый_тип.текст(),
                      действительный = действительный_тип.текст());
+        if *ожидаемый_тип == Тип::Нат8 && *действительный_тип == Тип::Символ {
+            диагностика!(лок, "ИНФО", "Только символы ASCII имеют тип «нат8». Остальные символы имеют тип «символ»");
+        }
         Err(())
     }
 }
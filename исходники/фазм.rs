@@ -1,12 +1,343 @@
 use std::fs;
 use std::io::{self, Write};
 use std::path::Path;
+use std::convert::TryInto;
 use std::process::{Command, Stdio};
 use компилятор::ПП;
-use компилятор::ВидИнструкции;
+use компилятор::{ВидИнструкции, Арифметика, пределы_целого, пределы_вещественных};
 use типизация::*;
 use Результат;
 
+/// Фрагменты сообщений об ошибках времени исполнения. Печатаются
+/// процедурами паники в stderr.
+const СООБЩЕНИЯ_ПАНИКИ: &[(&str, &str)] = &[
+    ("паника_заголовок", ": ОШИБКА ВРЕМЕНИ ИСПОЛНЕНИЯ: "),
+    ("паника_минус", "-"),
+    ("паника_индекс", "Индекс "),
+    ("паника_срез_начало", "Срез с началом "),
+    ("паника_срез_размер", " и размером "),
+    ("паника_за_границы", " выходит за границы [0.."),
+    ("паника_конец", ")\n"),
+    ("паника_сложение", "Переполнение при сложении "),
+    ("паника_вычитание", "Переполнение при вычитании "),
+    ("паника_умножение", "Переполнение при умножении "),
+    ("паника_деление", "Переполнение при делении "),
+    ("паника_отрицание", "Переполнение при отрицании "),
+    ("паника_и", " и "),
+    ("паника_из", " из "),
+    ("паника_на", " на "),
+    ("паника_деление_начало", "Деление "),
+    ("паника_на_ноль", " на ноль"),
+    ("паника_значение", "Значение "),
+    ("паника_вещ_значение", "Вещественное значение"),
+    ("паника_за_пределы", " выходит за пределы ["),
+    ("паника_точка_точка", ".."),
+    ("паника_конец_пределов", "]"),
+    ("паника_суррогат", " является суррогатом UTF-16 и не может быть символом"),
+    ("паника_перевод_строки", "\n"),
+];
+
+fn напечатать_сообщение_паники(файл: &mut impl Write, метка: &str) {
+    let (_, текст) = СООБЩЕНИЯ_ПАНИКИ.iter().find(|(имя, _)| *имя == метка).expect("Неизвестное сообщение паники");
+    let _ = writeln!(файл, "    mov rsi, {метка}");
+    let _ = writeln!(файл, "    mov rdx, {длина}", длина = текст.len());
+    let _ = writeln!(файл, "    call паника_напечатать_строку");
+}
+
+/// Инструкции, которые могут завершить программу с ошибкой. Для каждой
+/// из них в данных хранится строка с её локацией.
+fn проверка_времени_исполнения(вид: &ВидИнструкции) -> bool {
+    matches!(вид,
+        ВидИнструкции::ПроверитьГраницы{..} |
+        ВидИнструкции::ПроверитьСрез |
+        ВидИнструкции::ПроверитьАрифметику{..} |
+        ВидИнструкции::ПроверитьДиапазон{..} |
+        ВидИнструкции::ПроверитьДиапазонВещ32{..} |
+        ВидИнструкции::ПроверитьДиапазонВещ64{..} |
+        ВидИнструкции::ПроверитьСимвол)
+}
+
+fn записать_байты(файл: &mut impl Write, байты: &[u8]) {
+    for строка in байты.chunks(10) {
+        let _ = write!(файл, "    db");
+        for (столбец, байт) in строка.iter().enumerate() {
+            let _ = write!(файл, " {байт:#04X}");
+            if столбец + 1 < строка.len() {
+                let _ = write!(файл, ",");
+            }
+        }
+        let _ = writeln!(файл);
+    }
+}
+
+/// Указатели внутри инициализированных данных записываются
+/// относительно метки «данные», остальное как есть.
+fn записать_иниц_данные(файл: &mut impl Write, пп: &ПП) {
+    let mut заплатки = пп.заплатки_иниц_указателей.clone();
+    заплатки.sort();
+    let mut начало = 0;
+    for заплатка in заплатки {
+        записать_байты(файл, &пп.иниц_данные[начало..заплатка]);
+        let смещение = u64::from_le_bytes(пп.иниц_данные[заплатка..заплатка+8].try_into().unwrap());
+        let _ = writeln!(файл, "    dq данные+{смещение}");
+        начало = заплатка + 8;
+    }
+    записать_байты(файл, &пп.иниц_данные[начало..]);
+}
+
+fn лок_инструкции(пп: &ПП, индекс: usize) -> String {
+    let лок = &пп.код[индекс].лок;
+    format!("{путь_к_файлу}:{строка}:{столбец}", путь_к_файлу = лок.путь_к_файлу.display(), строка = лок.строка, столбец = лок.столбец)
+}
+
+fn загрузить_лок_инструкции(файл: &mut impl Write, пп: &ПП, индекс: usize) {
+    let _ = writeln!(файл, "    mov rdi, лок_{индекс}");
+    let _ = writeln!(файл, "    mov rsi, {длина}", длина = лок_инструкции(пп, индекс).len());
+}
+
+/// Процедуры паники. Все они ожидают локацию проверки в rdi (указатель)
+/// и rsi (длина), печатают сообщение в stderr и завершают программу с
+/// кодом 1.
+fn сгенерировать_процедуры_паники(файл: &mut impl Write) {
+    // rsi - указатель на строку, rdx - длина
+    let _ = writeln!(файл, "паника_напечатать_строку:");
+    let _ = writeln!(файл, "    mov rax, 1 ; SYS_write");
+    let _ = writeln!(файл, "    mov rdi, 2 ; stderr");
+    let _ = writeln!(файл, "    syscall");
+    let _ = writeln!(файл, "    ret");
+
+    // rax - число
+    let _ = writeln!(файл, "паника_напечатать_нат:");
+    let _ = writeln!(файл, "    sub rsp, 32");
+    let _ = writeln!(файл, "    lea rsi, [rsp+32]");
+    let _ = writeln!(файл, "    mov rcx, 10");
+    let _ = writeln!(файл, "паника_напечатать_нат_цикл:");
+    let _ = writeln!(файл, "    xor rdx, rdx");
+    let _ = writeln!(файл, "    div rcx");
+    let _ = writeln!(файл, "    add dl, 48");
+    let _ = writeln!(файл, "    dec rsi");
+    let _ = writeln!(файл, "    mov [rsi], dl");
+    let _ = writeln!(файл, "    test rax, rax");
+    let _ = writeln!(файл, "    jnz паника_напечатать_нат_цикл");
+    let _ = writeln!(файл, "    lea rdx, [rsp+32]");
+    let _ = writeln!(файл, "    sub rdx, rsi");
+    let _ = writeln!(файл, "    call паника_напечатать_строку");
+    let _ = writeln!(файл, "    add rsp, 32");
+    let _ = writeln!(файл, "    ret");
+
+    // rax - число
+    let _ = writeln!(файл, "паника_напечатать_цел:");
+    let _ = writeln!(файл, "    test rax, rax");
+    let _ = writeln!(файл, "    jns паника_напечатать_нат");
+    let _ = writeln!(файл, "    push rax");
+    напечатать_сообщение_паники(файл, "паника_минус");
+    let _ = writeln!(файл, "    pop rax");
+    let _ = writeln!(файл, "    neg rax");
+    let _ = writeln!(файл, "    jmp паника_напечатать_нат");
+
+    let _ = writeln!(файл, "паника_заголовок_сообщения:");
+    let _ = writeln!(файл, "    mov rdx, rsi");
+    let _ = writeln!(файл, "    mov rsi, rdi");
+    let _ = writeln!(файл, "    call паника_напечатать_строку");
+    напечатать_сообщение_паники(файл, "паника_заголовок");
+    let _ = writeln!(файл, "    ret");
+
+    let _ = writeln!(файл, "паника_выход:");
+    let _ = writeln!(файл, "    mov rax, 60 ; SYS_exit");
+    let _ = writeln!(файл, "    mov rdi, 1");
+    let _ = writeln!(файл, "    syscall");
+
+    // rax - индекс, rbx - длина
+    for (метка, печать_индекса) in [("паника_индекс_нат", "паника_напечатать_нат"), ("паника_индекс_цел", "паника_напечатать_цел")] {
+        let _ = writeln!(файл, "{метка}:");
+        let _ = writeln!(файл, "    push rbx");
+        let _ = writeln!(файл, "    push rax");
+        let _ = writeln!(файл, "    call паника_заголовок_сообщения");
+        напечатать_сообщение_паники(файл, "паника_индекс");
+        let _ = writeln!(файл, "    pop rax");
+        let _ = writeln!(файл, "    call {печать_индекса}");
+        let _ = writeln!(файл, "    jmp паника_за_границы_длины");
+    }
+
+    // rax - начало, rcx - размер, rbx - длина
+    let _ = writeln!(файл, "паника_срез:");
+    let _ = writeln!(файл, "    push rbx");
+    let _ = writeln!(файл, "    push rcx");
+    let _ = writeln!(файл, "    push rax");
+    let _ = writeln!(файл, "    call паника_заголовок_сообщения");
+    напечатать_сообщение_паники(файл, "паника_срез_начало");
+    let _ = writeln!(файл, "    pop rax");
+    let _ = writeln!(файл, "    call паника_напечатать_нат");
+    напечатать_сообщение_паники(файл, "паника_срез_размер");
+    let _ = writeln!(файл, "    pop rax");
+    let _ = writeln!(файл, "    call паника_напечатать_нат");
+
+    // длина лежит на вершине стека
+    let _ = writeln!(файл, "паника_за_границы_длины:");
+    напечатать_сообщение_паники(файл, "паника_за_границы");
+    let _ = writeln!(файл, "    pop rax");
+    let _ = writeln!(файл, "    call паника_напечатать_нат");
+    напечатать_сообщение_паники(файл, "паника_конец");
+    let _ = writeln!(файл, "    jmp паника_выход");
+
+    for (суффикс, печать_числа) in [("нат", "паника_напечатать_нат"), ("цел", "паника_напечатать_цел")] {
+        // rax - левый операнд, rbx - правый операнд
+        for (метка, начало, середина, правый_первым) in [
+            ("паника_сложение", "паника_сложение", "паника_и", false),
+            ("паника_вычитание", "паника_вычитание", "паника_из", true),
+            ("паника_умножение", "паника_умножение", "паника_и", false),
+            ("паника_деление", "паника_деление", "паника_на", false),
+        ] {
+            let _ = writeln!(файл, "{метка}_{суффикс}:");
+            if правый_первым {
+                let _ = writeln!(файл, "    push rax");
+                let _ = writeln!(файл, "    push rbx");
+            } else {
+                let _ = writeln!(файл, "    push rbx");
+                let _ = writeln!(файл, "    push rax");
+            }
+            let _ = writeln!(файл, "    call паника_заголовок_сообщения");
+            напечатать_сообщение_паники(файл, начало);
+            let _ = writeln!(файл, "    pop rax");
+            let _ = writeln!(файл, "    call {печать_числа}");
+            напечатать_сообщение_паники(файл, середина);
+            let _ = writeln!(файл, "    pop rax");
+            let _ = writeln!(файл, "    call {печать_числа}");
+            let _ = writeln!(файл, "    jmp паника_конец_строки");
+        }
+
+        // rax - делимое
+        let _ = writeln!(файл, "паника_деление_на_ноль_{суффикс}:");
+        let _ = writeln!(файл, "    push rax");
+        let _ = writeln!(файл, "    call паника_заголовок_сообщения");
+        напечатать_сообщение_паники(файл, "паника_деление_начало");
+        let _ = writeln!(файл, "    pop rax");
+        let _ = writeln!(файл, "    call {печать_числа}");
+        напечатать_сообщение_паники(файл, "паника_на_ноль");
+        let _ = writeln!(файл, "    jmp паника_конец_строки");
+
+        // rax - значение, rbx - мин, rcx - макс
+        let _ = writeln!(файл, "паника_диапазон_{суффикс}:");
+        let _ = writeln!(файл, "    push rcx");
+        let _ = writeln!(файл, "    push rbx");
+        let _ = writeln!(файл, "    push rax");
+        let _ = writeln!(файл, "    call паника_заголовок_сообщения");
+        напечатать_сообщение_паники(файл, "паника_значение");
+        let _ = writeln!(файл, "    pop rax");
+        let _ = writeln!(файл, "    call {печать_числа}");
+        let _ = writeln!(файл, "    jmp паника_за_пределы_диапазона");
+    }
+
+    // rax - операнд
+    let _ = writeln!(файл, "паника_отрицание_цел:");
+    let _ = writeln!(файл, "    push rax");
+    let _ = writeln!(файл, "    call паника_заголовок_сообщения");
+    напечатать_сообщение_паники(файл, "паника_отрицание");
+    let _ = writeln!(файл, "    pop rax");
+    let _ = writeln!(файл, "    call паника_напечатать_цел");
+    let _ = writeln!(файл, "    jmp паника_конец_строки");
+
+    // rax - значение
+    let _ = writeln!(файл, "паника_символ:");
+    let _ = writeln!(файл, "    push rax");
+    let _ = writeln!(файл, "    call паника_заголовок_сообщения");
+    напечатать_сообщение_паники(файл, "паника_значение");
+    let _ = writeln!(файл, "    pop rax");
+    let _ = writeln!(файл, "    call паника_напечатать_нат");
+    напечатать_сообщение_паники(файл, "паника_суррогат");
+    let _ = writeln!(файл, "    jmp паника_конец_строки");
+
+    // rbx - мин, rcx - макс
+    let _ = writeln!(файл, "паника_диапазон_вещ:");
+    let _ = writeln!(файл, "    push rcx");
+    let _ = writeln!(файл, "    push rbx");
+    let _ = writeln!(файл, "    call паника_заголовок_сообщения");
+    напечатать_сообщение_паники(файл, "паника_вещ_значение");
+
+    // мин и макс лежат на вершине стека
+    let _ = writeln!(файл, "паника_за_пределы_диапазона:");
+    напечатать_сообщение_паники(файл, "паника_за_пределы");
+    let _ = writeln!(файл, "    pop rax");
+    let _ = writeln!(файл, "    call паника_напечатать_цел");
+    напечатать_сообщение_паники(файл, "паника_точка_точка");
+    let _ = writeln!(файл, "    pop rax");
+    let _ = writeln!(файл, "    call паника_напечатать_нат");
+    напечатать_сообщение_паники(файл, "паника_конец_пределов");
+
+    let _ = writeln!(файл, "паника_конец_строки:");
+    напечатать_сообщение_паники(файл, "паника_перевод_строки");
+    let _ = writeln!(файл, "    jmp паника_выход");
+}
+
+/// Переходит на метку «успех», если целое в регистре лежит в пределах
+/// [мин..макс], иначе на метку «провал». Портит rdx.
+fn проверить_пределы(файл: &mut impl Write, регистр: &str, знаковый: bool, мин: i64, макс: u64, успех: &str, провал: &str) {
+    if знаковый {
+        let _ = writeln!(файл, "    mov rdx, {мин}");
+        let _ = writeln!(файл, "    cmp {регистр}, rdx");
+        let _ = writeln!(файл, "    jl {провал}");
+        if макс <= i64::MAX as u64 {
+            let _ = writeln!(файл, "    mov rdx, {макс}");
+            let _ = writeln!(файл, "    cmp {регистр}, rdx");
+            let _ = writeln!(файл, "    jle {успех}");
+            let _ = writeln!(файл, "    jmp {провал}");
+        } else {
+            let _ = writeln!(файл, "    jmp {успех}");
+        }
+    } else {
+        let _ = writeln!(файл, "    mov rdx, {макс}");
+        let _ = writeln!(файл, "    cmp {регистр}, rdx");
+        let _ = writeln!(файл, "    jbe {успех}");
+        let _ = writeln!(файл, "    jmp {провал}");
+    }
+}
+
+/// Конвертирует натуральное из rax в вещественное в xmm0. Процессор
+/// умеет конвертировать только знаковые целые, поэтому числа от 2^63 и
+/// больше делятся пополам с сохранением младшего бита для верного
+/// округления, а результат удваивается. Так же поступают компиляторы Си.
+/// Портит rbx.
+fn конвертировать_нат64_в_вещ(файл: &mut impl Write, индекс: usize, вещ64: bool) {
+    let точность = if вещ64 { "sd" } else { "ss" };
+    let _ = writeln!(файл, "    pxor xmm0, xmm0");
+    let _ = writeln!(файл, "    test rax, rax");
+    let _ = writeln!(файл, "    js инструкция_{индекс}_большое");
+    let _ = writeln!(файл, "    cvtsi2{точность} xmm0, rax");
+    let _ = writeln!(файл, "    jmp инструкция_{индекс}_готово");
+    let _ = writeln!(файл, "инструкция_{индекс}_большое:");
+    let _ = writeln!(файл, "    mov rbx, rax");
+    let _ = writeln!(файл, "    shr rbx, 1");
+    let _ = writeln!(файл, "    and rax, 1");
+    let _ = writeln!(файл, "    or rbx, rax");
+    let _ = writeln!(файл, "    cvtsi2{точность} xmm0, rbx");
+    let _ = writeln!(файл, "    add{точность} xmm0, xmm0");
+    let _ = writeln!(файл, "инструкция_{индекс}_готово:");
+}
+
+/// Конвертирует вещественное из xmm0 в натуральное в rax, отбрасывая
+/// дробную часть. Числа от 2^63 и больше не помещаются в знаковое целое,
+/// поэтому из них вычитается 2^63, а старший бит результата затем
+/// выставляется обратно. Портит xmm1.
+fn конвертировать_вещ_в_нат64(файл: &mut impl Write, индекс: usize, вещ64: bool) {
+    let точность = if вещ64 { "sd" } else { "ss" };
+    if вещ64 {
+        let _ = writeln!(файл, "    mov rax, {}", ((1u64 << 63) as f64).to_bits());
+        let _ = writeln!(файл, "    movq xmm1, rax");
+    } else {
+        let _ = writeln!(файл, "    mov eax, {}", ((1u64 << 63) as f32).to_bits());
+        let _ = writeln!(файл, "    movd xmm1, eax");
+    }
+    let _ = writeln!(файл, "    comi{точность} xmm0, xmm1");
+    let _ = writeln!(файл, "    jae инструкция_{индекс}_большое");
+    let _ = writeln!(файл, "    cvtt{точность}2si rax, xmm0");
+    let _ = writeln!(файл, "    jmp инструкция_{индекс}_готово");
+    let _ = writeln!(файл, "инструкция_{индекс}_большое:");
+    let _ = writeln!(файл, "    sub{точность} xmm0, xmm1");
+    let _ = writeln!(файл, "    cvtt{точность}2si rax, xmm0");
+    let _ = writeln!(файл, "    btc rax, 63");
+    let _ = writeln!(файл, "инструкция_{индекс}_готово:");
+}
+
 fn сгенерировать_инструкции(файл: &mut impl Write, пп: &ПП, точка_входа_программы: usize) -> Результат<()> {
     let mut внешние_символы: Vec<_> = пп.внешние_символы.iter().collect();
     внешние_символы.sort_by_key(|(_, индекс)| *индекс);
@@ -24,7 +355,7 @@ fn сгенерировать_инструкции(файл: &mut impl Write, п
                          столбец = инструкция.лок.столбец,
                          вид_инструкции = инструкция.вид);
         match &инструкция.вид {
-            ВидИнструкции::Ноп => {}
+            ВидИнструкции::Ноп | ВидИнструкции::АдресПамяти => {}
             ВидИнструкции::Вытолкнуть => {
                 let _ = writeln!(файл, "    pop rax");
             }
@@ -33,6 +364,12 @@ fn сгенерировать_инструкции(файл: &mut impl Write, п
                 let _ = writeln!(файл, "    push rax");
                 let _ = writeln!(файл, "    push rax");
             }
+            ВидИнструкции::Поменять => {
+                let _ = writeln!(файл, "    pop rax");
+                let _ = writeln!(файл, "    pop rbx");
+                let _ = writeln!(файл, "    push rax");
+                let _ = writeln!(файл, "    push rbx");
+            }
             ВидИнструкции::Натуральное(значение) => {
                 let _ = writeln!(файл, "    mov rax, {значение}");
                 let _ = writeln!(файл, "    push rax");
@@ -91,8 +428,9 @@ fn сгенерировать_инструкции(файл: &mut impl Write, п
                 let _ = writeln!(файл, "    mov [rsi], al");
             }
             ВидИнструкции::Записать16 => {
-                сделать!(&инструкция.лок, "Кодогенерация инструкции Записать16");
-                return Err(())
+                let _ = writeln!(файл, "    pop rsi");
+                let _ = writeln!(файл, "    pop rax");
+                let _ = writeln!(файл, "    mov [rsi], ax");
             }
             ВидИнструкции::Записать32 => {
                 let _ = writeln!(файл, "    pop rsi");
@@ -111,8 +449,9 @@ fn сгенерировать_инструкции(файл: &mut impl Write, п
                 let _ = writeln!(файл, "    push rax");
             }
             ВидИнструкции::ПрочитатьБезЗнак16 => {
-                сделать!(&инструкция.лок, "Кодогенерация инструкции ПрочитатьБезЗнак16");
-                return Err(())
+                let _ = writeln!(файл, "    pop rsi");
+                let _ = writeln!(файл, "    movzx rax, word [rsi]");
+                let _ = writeln!(файл, "    push rax");
             }
             ВидИнструкции::ПрочитатьБезЗнак32 => {
                 let _ = writeln!(файл, "    pop rsi");
@@ -121,16 +460,19 @@ fn сгенерировать_инструкции(файл: &mut impl Write, п
                 let _ = writeln!(файл, "    push rax");
             }
             ВидИнструкции::ПрочитатьЗнак8 => {
-                сделать!(&инструкция.лок, "Кодогенерация инструкции ПрочитатьЗнак8");
-                return Err(())
+                let _ = writeln!(файл, "    pop rsi");
+                let _ = writeln!(файл, "    movsx rax, byte [rsi]");
+                let _ = writeln!(файл, "    push rax");
             }
             ВидИнструкции::ПрочитатьЗнак16 => {
-                сделать!(&инструкция.лок, "Кодогенерация инструкции ПрочитатьЗнак16");
-                return Err(())
+                let _ = writeln!(файл, "    pop rsi");
+                let _ = writeln!(файл, "    movsx rax, word [rsi]");
+                let _ = writeln!(файл, "    push rax");
             }
             ВидИнструкции::ПрочитатьЗнак32 => {
-                сделать!(&инструкция.лок, "Кодогенерация инструкции ПрочитатьЗнак32");
-                return Err(())
+                let _ = writeln!(файл, "    pop rsi");
+                let _ = writeln!(файл, "    movsxd rax, dword [rsi]");
+                let _ = writeln!(файл, "    push rax");
             }
             ВидИнструкции::Прочитать64 => {
                 let _ = writeln!(файл, "    pop rsi");
@@ -288,18 +630,8 @@ fn сгенерировать_инструкции(файл: &mut impl Write, п
                 let _ = writeln!(файл, "    push rax");
             }
             ВидИнструкции::КонвертНат64Вещ32 => {
-                // СДЕЛАТЬ: конвертация натуральных чисел в вещественные (и обратно) использует знаковую конвертацию.
-                //
-                // В общем, ничего плохого в этом нет до тех пор пока натуральные числе
-                // не слишком большие.
-                //
-                // Для больших натуральных чисел нам нужно использовать трюки, которые
-                // используют компиляторы языка Си: делим значение на два, чтобы убрать
-                // бит знака, конвертируем споловиненное значение, и умножаем вещественный
-                // результат обратно на два. С учётом остатков конечно же.
                 let _ = writeln!(файл, "    pop rax");
-                let _ = writeln!(файл, "    pxor xmm0, xmm0");
-                let _ = writeln!(файл, "    cvtsi2ss xmm0, rax");
+                конвертировать_нат64_в_вещ(файл, индекс, false);
                 let _ = writeln!(файл, "    movd eax, xmm0");
                 let _ = writeln!(файл, "    push rax");
             }
@@ -313,7 +645,7 @@ fn сгенерировать_инструкции(файл: &mut impl Write, п
             ВидИнструкции::КонвертВещ32Нат64 => {
                 let _ = writeln!(файл, "    pop rax");
                 let _ = writeln!(файл, "    movd xmm0, eax");
-                let _ = writeln!(файл, "    cvttss2si rax, xmm0");
+                конвертировать_вещ_в_нат64(файл, индекс, false);
                 let _ = writeln!(файл, "    push rax");
             }
             ВидИнструкции::КонвертВещ32Цел64 => {
@@ -340,6 +672,22 @@ fn сгенерировать_инструкции(файл: &mut impl Write, п
                 let _ = writeln!(файл, "    movd eax, xmm0");
                 let _ = writeln!(файл, "    push rax");
             }
+            ВидИнструкции::Вещ32Остаток => {
+                // В SSE нет остатка от деления, а fprem сопроцессора x87
+                // вычисляет его точно. За один раз fprem уменьшает порядок
+                // делимого не более чем на 63, поэтому повторяем, пока
+                // флаг C2 говорит о неполном результате.
+                let _ = writeln!(файл, "    fld dword [rsp]");
+                let _ = writeln!(файл, "    fld dword [rsp+8]");
+                let _ = writeln!(файл, "инструкция_{индекс}_остаток:");
+                let _ = writeln!(файл, "    fprem");
+                let _ = writeln!(файл, "    fnstsw ax");
+                let _ = writeln!(файл, "    test ah, 4");
+                let _ = writeln!(файл, "    jnz инструкция_{индекс}_остаток");
+                let _ = writeln!(файл, "    fstp dword [rsp+8]");
+                let _ = writeln!(файл, "    fstp st0");
+                let _ = writeln!(файл, "    add rsp, 8");
+            }
             ВидИнструкции::Вещ32Сложение => {
                 let _ = writeln!(файл, "    pop rax");
                 let _ = writeln!(файл, "    pop rbx");
@@ -394,6 +742,21 @@ fn сгенерировать_инструкции(файл: &mut impl Write, п
                 let _ = writeln!(файл, "    movd eax, xmm0");
                 let _ = writeln!(файл, "    push rax");
             }
+            ВидИнструкции::Вещ32Равно => {
+                // Неупорядоченное сравнение с NaN выставляет ZF вместе с PF,
+                // поэтому равенство требует ещё и сброшенного PF.
+                let _ = writeln!(файл, "    pop rbx");
+                let _ = writeln!(файл, "    pop rax");
+                let _ = writeln!(файл, "    movd xmm0, eax");
+                let _ = writeln!(файл, "    movd xmm1, ebx");
+                let _ = writeln!(файл, "    xor ecx, ecx");
+                let _ = writeln!(файл, "    xor edx, edx");
+                let _ = writeln!(файл, "    ucomiss xmm0, xmm1");
+                let _ = writeln!(файл, "    sete cl");
+                let _ = writeln!(файл, "    setnp dl");
+                let _ = writeln!(файл, "    and ecx, edx");
+                let _ = writeln!(файл, "    push rcx");
+            }
             ВидИнструкции::Вещ32Отриц => {
                 let _ = writeln!(файл, "    mov eax, 0x80000000");
                 let _ = writeln!(файл, "    pop rbx");
@@ -403,6 +766,153 @@ fn сгенерировать_инструкции(файл: &mut impl Write, п
                 let _ = writeln!(файл, "    movd eax, xmm0");
                 let _ = writeln!(файл, "    push rax");
             }
+            ВидИнструкции::КонвертНат64Вещ64 => {
+                let _ = writeln!(файл, "    pop rax");
+                конвертировать_нат64_в_вещ(файл, индекс, true);
+                let _ = writeln!(файл, "    movq rax, xmm0");
+                let _ = writeln!(файл, "    push rax");
+            }
+            ВидИнструкции::КонвертЦел64Вещ64 => {
+                let _ = writeln!(файл, "    pop rax");
+                let _ = writeln!(файл, "    pxor xmm0, xmm0");
+                let _ = writeln!(файл, "    cvtsi2sd xmm0, rax");
+                let _ = writeln!(файл, "    movq rax, xmm0");
+                let _ = writeln!(файл, "    push rax");
+            }
+            ВидИнструкции::КонвертВещ64Нат64 => {
+                let _ = writeln!(файл, "    pop rax");
+                let _ = writeln!(файл, "    movq xmm0, rax");
+                конвертировать_вещ_в_нат64(файл, индекс, true);
+                let _ = writeln!(файл, "    push rax");
+            }
+            ВидИнструкции::КонвертВещ64Цел64 => {
+                let _ = writeln!(файл, "    pop rax");
+                let _ = writeln!(файл, "    movq xmm0, rax");
+                let _ = writeln!(файл, "    cvttsd2si rax, xmm0");
+                let _ = writeln!(файл, "    push rax");
+            }
+            ВидИнструкции::КонвертВещ32Вещ64 => {
+                let _ = writeln!(файл, "    pop rax");
+                let _ = writeln!(файл, "    movd xmm0, eax");
+                let _ = writeln!(файл, "    cvtss2sd xmm0, xmm0");
+                let _ = writeln!(файл, "    movq rax, xmm0");
+                let _ = writeln!(файл, "    push rax");
+            }
+            ВидИнструкции::КонвертВещ64Вещ32 => {
+                let _ = writeln!(файл, "    pop rax");
+                let _ = writeln!(файл, "    movq xmm0, rax");
+                let _ = writeln!(файл, "    cvtsd2ss xmm0, xmm0");
+                let _ = writeln!(файл, "    movd eax, xmm0");
+                let _ = writeln!(файл, "    push rax");
+            }
+            ВидИнструкции::Вещ64Умножение => {
+                let _ = writeln!(файл, "    pop rbx");
+                let _ = writeln!(файл, "    pop rax");
+                let _ = writeln!(файл, "    movq xmm0, rax");
+                let _ = writeln!(файл, "    movq xmm1, rbx");
+                let _ = writeln!(файл, "    mulsd xmm0, xmm1");
+                let _ = writeln!(файл, "    movq rax, xmm0");
+                let _ = writeln!(файл, "    push rax");
+            }
+            ВидИнструкции::Вещ64Деление => {
+                let _ = writeln!(файл, "    pop rbx");
+                let _ = writeln!(файл, "    pop rax");
+                let _ = writeln!(файл, "    movq xmm0, rax");
+                let _ = writeln!(файл, "    movq xmm1, rbx");
+                let _ = writeln!(файл, "    divsd xmm0, xmm1");
+                let _ = writeln!(файл, "    movq rax, xmm0");
+                let _ = writeln!(файл, "    push rax");
+            }
+            ВидИнструкции::Вещ64Остаток => {
+                let _ = writeln!(файл, "    fld qword [rsp]");
+                let _ = writeln!(файл, "    fld qword [rsp+8]");
+                let _ = writeln!(файл, "инструкция_{индекс}_остаток:");
+                let _ = writeln!(файл, "    fprem");
+                let _ = writeln!(файл, "    fnstsw ax");
+                let _ = writeln!(файл, "    test ah, 4");
+                let _ = writeln!(файл, "    jnz инструкция_{индекс}_остаток");
+                let _ = writeln!(файл, "    fstp qword [rsp+8]");
+                let _ = writeln!(файл, "    fstp st0");
+                let _ = writeln!(файл, "    add rsp, 8");
+            }
+            ВидИнструкции::Вещ64Сложение => {
+                let _ = writeln!(файл, "    pop rbx");
+                let _ = writeln!(файл, "    pop rax");
+                let _ = writeln!(файл, "    movq xmm0, rax");
+                let _ = writeln!(файл, "    movq xmm1, rbx");
+                let _ = writeln!(файл, "    addsd xmm0, xmm1");
+                let _ = writeln!(файл, "    movq rax, xmm0");
+                let _ = writeln!(файл, "    push rax");
+            }
+            ВидИнструкции::Вещ64Вычитание => {
+                let _ = writeln!(файл, "    pop rbx");
+                let _ = writeln!(файл, "    pop rax");
+                let _ = writeln!(файл, "    movq xmm0, rax");
+                let _ = writeln!(файл, "    movq xmm1, rbx");
+                let _ = writeln!(файл, "    subsd xmm0, xmm1");
+                let _ = writeln!(файл, "    movq rax, xmm0");
+                let _ = writeln!(файл, "    push rax");
+            }
+            ВидИнструкции::Вещ64Меньше => {
+                // Неупорядоченное сравнение с NaN выставляет CF и ZF, поэтому
+                // seta и setae дают ложь, как и положено.
+                let _ = writeln!(файл, "    pop rbx");
+                let _ = writeln!(файл, "    pop rax");
+                let _ = writeln!(файл, "    movq xmm0, rax");
+                let _ = writeln!(файл, "    movq xmm1, rbx");
+                let _ = writeln!(файл, "    xor ecx, ecx");
+                let _ = writeln!(файл, "    ucomisd xmm1, xmm0");
+                let _ = writeln!(файл, "    seta cl");
+                let _ = writeln!(файл, "    push rcx");
+            }
+            ВидИнструкции::Вещ64МеньшеРавно => {
+                let _ = writeln!(файл, "    pop rbx");
+                let _ = writeln!(файл, "    pop rax");
+                let _ = writeln!(файл, "    movq xmm0, rax");
+                let _ = writeln!(файл, "    movq xmm1, rbx");
+                let _ = writeln!(файл, "    xor ecx, ecx");
+                let _ = writeln!(файл, "    ucomisd xmm1, xmm0");
+                let _ = writeln!(файл, "    setae cl");
+                let _ = writeln!(файл, "    push rcx");
+            }
+            ВидИнструкции::Вещ64Больше => {
+                let _ = writeln!(файл, "    pop rbx");
+                let _ = writeln!(файл, "    pop rax");
+                let _ = writeln!(файл, "    movq xmm0, rax");
+                let _ = writeln!(файл, "    movq xmm1, rbx");
+                let _ = writeln!(файл, "    xor ecx, ecx");
+                let _ = writeln!(файл, "    ucomisd xmm0, xmm1");
+                let _ = writeln!(файл, "    seta cl");
+                let _ = writeln!(файл, "    push rcx");
+            }
+            ВидИнструкции::Вещ64БольшеРавно => {
+                let _ = writeln!(файл, "    pop rbx");
+                let _ = writeln!(файл, "    pop rax");
+                let _ = writeln!(файл, "    movq xmm0, rax");
+                let _ = writeln!(файл, "    movq xmm1, rbx");
+                let _ = writeln!(файл, "    xor ecx, ecx");
+                let _ = writeln!(файл, "    ucomisd xmm0, xmm1");
+                let _ = writeln!(файл, "    setae cl");
+                let _ = writeln!(файл, "    push rcx");
+            }
+            ВидИнструкции::Вещ64Равно => {
+                let _ = writeln!(файл, "    pop rbx");
+                let _ = writeln!(файл, "    pop rax");
+                let _ = writeln!(файл, "    movq xmm0, rax");
+                let _ = writeln!(файл, "    movq xmm1, rbx");
+                let _ = writeln!(файл, "    xor ecx, ecx");
+                let _ = writeln!(файл, "    xor edx, edx");
+                let _ = writeln!(файл, "    ucomisd xmm0, xmm1");
+                let _ = writeln!(файл, "    sete cl");
+                let _ = writeln!(файл, "    setnp dl");
+                let _ = writeln!(файл, "    and ecx, edx");
+                let _ = writeln!(файл, "    push rcx");
+            }
+            ВидИнструкции::Вещ64Отриц => {
+                let _ = writeln!(файл, "    pop rax");
+                let _ = writeln!(файл, "    btc rax, 63");
+                let _ = writeln!(файл, "    push rax");
+            }
             ВидИнструкции::ЛогОтрицание => {
                 let _ = writeln!(файл, "    xor rbx, rbx");
                 let _ = writeln!(файл, "    pop rax");
@@ -434,13 +944,18 @@ fn сгенерировать_инструкции(файл: &mut impl Write, п
                 let _ = writeln!(файл, "    shl rax, cl");
                 let _ = writeln!(файл, "    push rax");
             }
-            // СДЕЛАТЬ: правое битовое смещение положительных и отрицательных чисел может отличаться
             ВидИнструкции::БитСмещениеВправо => {
                 let _ = writeln!(файл, "    pop rcx");
                 let _ = writeln!(файл, "    pop rax");
                 let _ = writeln!(файл, "    shr rax, cl");
                 let _ = writeln!(файл, "    push rax");
             }
+            ВидИнструкции::ЦелСмещениеВправо => {
+                let _ = writeln!(файл, "    pop rcx");
+                let _ = writeln!(файл, "    pop rax");
+                let _ = writeln!(файл, "    sar rax, cl");
+                let _ = writeln!(файл, "    push rax");
+            }
             ВидИнструкции::ПечатьСтроки => {
                 let _ = writeln!(файл, "    pop rbx");
                 let _ = writeln!(файл, "    mov rsi, [rbx+{}]", СРЕЗ_АДРЕС_СМЕЩЕНИЕ);
@@ -463,25 +978,86 @@ fn сгенерировать_инструкции(файл: &mut impl Write, п
             ВидИнструкции::ВнутреннийВызов(индекс_инструкции_пп_цели) => {
                 let _ = writeln!(файл, "    call инструкция_{индекс_инструкции_пп_цели}");
             }
-            ВидИнструкции::ВнешнийВызов{индекс, арность, результат} => {
+            ВидИнструкции::ВнешнийВызов{индекс, параметры, вариативные_аргументы, результат} => {
+                // https://gitlab.com/x86-psABIs/x86-64-ABI
                 let регистры = &["rdi", "rsi", "rdx", "rcx", "r8", "r9"];
-                if *арность > регистры.len() {
-                    сделать!(&инструкция.лок, "Слишком большая арность");
-                    return Err(())
+                let вещ_регистры = &["xmm0", "xmm1", "xmm2", "xmm3", "xmm4", "xmm5", "xmm6", "xmm7"];
+                let mut количество_регистров = 0;
+                let mut количество_вещ_регистров = 0;
+                let вариативные = вариативные_аргументы.iter().flatten().map(|тип| (тип, true));
+                for (тип, вариативный) in параметры.iter().map(|тип| (тип, false)).chain(вариативные) {
+                    match тип {
+                        Тип::Вещ64 => {
+                            let Some(регистр) = вещ_регистры.get(количество_вещ_регистров) else {
+                                сделать!(&инструкция.лок, "Передача вещественных аргументов внешним процедурам через стек");
+                                return Err(())
+                            };
+                            количество_вещ_регистров += 1;
+                            let _ = writeln!(файл, "    pop rax");
+                            let _ = writeln!(файл, "    movq {регистр}, rax");
+                        }
+                        Тип::Вещ32 => {
+                            let Some(регистр) = вещ_регистры.get(количество_вещ_регистров) else {
+                                сделать!(&инструкция.лок, "Передача вещественных аргументов внешним процедурам через стек");
+                                return Err(())
+                            };
+                            количество_вещ_регистров += 1;
+                            let _ = writeln!(файл, "    pop rax");
+                            let _ = writeln!(файл, "    movd {регистр}, eax");
+                            if вариативный {
+                                // Вариативные вещественные аргументы повышаются до double
+                                let _ = writeln!(файл, "    cvtss2sd {регистр}, {регистр}");
+                            }
+                        }
+                        _ => {
+                            let Some(регистр) = регистры.get(количество_регистров) else {
+                                сделать!(&инструкция.лок, "Слишком большая арность");
+                                return Err(())
+                            };
+                            количество_регистров += 1;
+                            let _ = writeln!(файл, "    pop {регистр}");
+                        }
+                    }
                 }
-                for регистр in &регистры[0..*арность] {
-                    let _ = writeln!(файл, "    pop {регистр}");
+                if вариативные_аргументы.is_some() {
+                    let _ = writeln!(файл, "    mov eax, {количество_вещ_регистров}");
                 }
+                // Стек должен быть выравнен по 16 байт в момент вызова. r14 сохраняется вызываемой процедурой.
+                let _ = writeln!(файл, "    mov r14, rsp");
+                let _ = writeln!(файл, "    and rsp, -16");
                 let _ = writeln!(файл, "    call {имя}", имя = внешние_символы[*индекс].0);
+                let _ = writeln!(файл, "    mov rsp, r14");
                 if let Some(результат) = результат {
                     match результат {
-                        Тип::Нат64 | Тип::Цел64 | Тип::Лог => {
+                        Тип::Нат64 | Тип::Цел64 => {
+                            let _ = writeln!(файл, "    push rax");
+                        },
+                        // Старшие биты узких целых в регистре не определены.
+                        Тип::Нат8 | Тип::Нат16 | Тип::Нат32 | Тип::Цел8 | Тип::Цел16 | Тип::Цел32 => {
+                            let расширение = match результат {
+                                Тип::Нат8 => "movzx rax, al",
+                                Тип::Нат16 => "movzx rax, ax",
+                                Тип::Нат32 => "mov eax, eax",
+                                Тип::Цел8 => "movsx rax, al",
+                                Тип::Цел16 => "movsx rax, ax",
+                                _ => "movsxd rax, eax",
+                            };
+                            let _ = writeln!(файл, "    {расширение}");
+                            let _ = writeln!(файл, "    push rax");
+                        }
+                        Тип::Лог => {
+                            // Сишный bool определен только в al
+                            let _ = writeln!(файл, "    movzx rax, al");
                             let _ = writeln!(файл, "    push rax");
                         },
                         Тип::Вещ32 => {
                             let _ = writeln!(файл, "    movd eax, xmm0");
                             let _ = writeln!(файл, "    push rax");
                         }
+                        Тип::Вещ64 => {
+                            let _ = writeln!(файл, "    movq rax, xmm0");
+                            let _ = writeln!(файл, "    push rax");
+                        }
                         _ => {
                             сделать!(&инструкция.лок, "Кодогенерация возврата типа «{тип}» из внешних процедур",
                                      тип = результат.текст());
@@ -502,8 +1078,184 @@ fn сгенерировать_инструкции(файл: &mut impl Write, п
                 сделать!(&инструкция.лок, "Кодогенерация для инструкции «{вид:?}»", вид = инструкция.вид);
                 return Err(())
             }
+            ВидИнструкции::ПроверитьГраницы{знаковый} => {
+                let _ = writeln!(файл, "    pop rax");
+                let _ = writeln!(файл, "    pop rbx");
+                let _ = writeln!(файл, "    push rax");
+                let _ = writeln!(файл, "    cmp rax, rbx");
+                let _ = writeln!(файл, "    jb инструкция_{следующая}", следующая = индекс + 1);
+                загрузить_лок_инструкции(файл, пп, индекс);
+                if *знаковый {
+                    let _ = writeln!(файл, "    jmp паника_индекс_цел");
+                } else {
+                    let _ = writeln!(файл, "    jmp паника_индекс_нат");
+                }
+            }
+            ВидИнструкции::ПроверитьСрез => {
+                let _ = writeln!(файл, "    pop rbx");
+                let _ = writeln!(файл, "    mov rax, [rsp]");
+                let _ = writeln!(файл, "    mov rcx, [rsp+16]");
+                let _ = writeln!(файл, "    cmp rax, rbx");
+                let _ = writeln!(файл, "    ja инструкция_{индекс}_паника");
+                let _ = writeln!(файл, "    mov rdx, rbx");
+                let _ = writeln!(файл, "    sub rdx, rax");
+                let _ = writeln!(файл, "    cmp rcx, rdx");
+                let _ = writeln!(файл, "    jbe инструкция_{следующая}", следующая = индекс + 1);
+                let _ = writeln!(файл, "инструкция_{индекс}_паника:");
+                загрузить_лок_инструкции(файл, пп, индекс);
+                let _ = writeln!(файл, "    jmp паника_срез");
+            }
+            ВидИнструкции::ПроверитьАрифметику{операция, знаковый, разрядность} => {
+                let (мин, макс) = пределы_целого(*знаковый, *разрядность);
+                let успех = format!("инструкция_{следующая}", следующая = индекс + 1);
+                let провал = format!("инструкция_{индекс}_паника");
+                let суффикс = if *знаковый { "цел" } else { "нат" };
+                if let Арифметика::Отрицание = операция {
+                    let _ = writeln!(файл, "    mov rax, [rsp]");
+                    if *знаковый {
+                        let _ = writeln!(файл, "    mov rdx, {мин}");
+                        let _ = writeln!(файл, "    cmp rax, rdx");
+                        let _ = writeln!(файл, "    jne {успех}");
+                    } else {
+                        let _ = writeln!(файл, "    test rax, rax");
+                        let _ = writeln!(файл, "    jz {успех}");
+                    }
+                    let _ = writeln!(файл, "{провал}:");
+                    загрузить_лок_инструкции(файл, пп, индекс);
+                    let _ = writeln!(файл, "    jmp паника_отрицание_цел");
+                    continue;
+                }
+                let _ = writeln!(файл, "    mov rax, [rsp+8]");
+                let _ = writeln!(файл, "    mov rbx, [rsp]");
+                let полная_разрядность = *разрядность == 64;
+                let метка_паники = match операция {
+                    Арифметика::Сложение => {
+                        let _ = writeln!(файл, "    mov rcx, rax");
+                        let _ = writeln!(файл, "    add rcx, rbx");
+                        if полная_разрядность {
+                            let _ = writeln!(файл, "    {переход} {успех}", переход = if *знаковый { "jno" } else { "jnc" });
+                        } else {
+                            проверить_пределы(файл, "rcx", *знаковый, мин, макс, &успех, &провал);
+                        }
+                        "паника_сложение"
+                    }
+                    Арифметика::Вычитание => {
+                        let _ = writeln!(файл, "    mov rcx, rax");
+                        let _ = writeln!(файл, "    sub rcx, rbx");
+                        if !*знаковый {
+                            let _ = writeln!(файл, "    jnc {успех}");
+                        } else if полная_разрядность {
+                            let _ = writeln!(файл, "    jno {успех}");
+                        } else {
+                            проверить_пределы(файл, "rcx", *знаковый, мин, макс, &успех, &провал);
+                        }
+                        "паника_вычитание"
+                    }
+                    Арифметика::Умножение => {
+                        if полная_разрядность && !*знаковый {
+                            let _ = writeln!(файл, "    mul rbx");
+                            let _ = writeln!(файл, "    jno {успех}");
+                        } else {
+                            let _ = writeln!(файл, "    mov rcx, rax");
+                            let _ = writeln!(файл, "    imul rcx, rbx");
+                            if полная_разрядность {
+                                let _ = writeln!(файл, "    jno {успех}");
+                            } else {
+                                проверить_пределы(файл, "rcx", *знаковый, мин, макс, &успех, &провал);
+                            }
+                        }
+                        "паника_умножение"
+                    }
+                    Арифметика::Деление => {
+                        let _ = writeln!(файл, "    test rbx, rbx");
+                        let _ = writeln!(файл, "    jz инструкция_{индекс}_ноль");
+                        if *знаковый {
+                            let _ = writeln!(файл, "    cmp rbx, -1");
+                            let _ = writeln!(файл, "    jne {успех}");
+                            let _ = writeln!(файл, "    mov rdx, {мин}");
+                            let _ = writeln!(файл, "    cmp rax, rdx");
+                            let _ = writeln!(файл, "    jne {успех}");
+                        } else {
+                            let _ = writeln!(файл, "    jmp {успех}");
+                        }
+                        "паника_деление"
+                    }
+                    Арифметика::Отрицание => unreachable!(),
+                };
+                let _ = writeln!(файл, "{провал}:");
+                let _ = writeln!(файл, "    mov rax, [rsp+8]");
+                загрузить_лок_инструкции(файл, пп, индекс);
+                let _ = writeln!(файл, "    jmp {метка_паники}_{суффикс}");
+                if let Арифметика::Деление = операция {
+                    let _ = writeln!(файл, "инструкция_{индекс}_ноль:");
+                    загрузить_лок_инструкции(файл, пп, индекс);
+                    let _ = writeln!(файл, "    jmp паника_деление_на_ноль_{суффикс}");
+                }
+            }
+            ВидИнструкции::ПроверитьДиапазон{знаковый, мин, макс} => {
+                let успех = format!("инструкция_{следующая}", следующая = индекс + 1);
+                let провал = format!("инструкция_{индекс}_паника");
+                let _ = writeln!(файл, "    mov rax, [rsp]");
+                проверить_пределы(файл, "rax", *знаковый, *мин, *макс, &успех, &провал);
+                let _ = writeln!(файл, "{провал}:");
+                let _ = writeln!(файл, "    mov rbx, {мин}");
+                let _ = writeln!(файл, "    mov rcx, {макс}");
+                загрузить_лок_инструкции(файл, пп, индекс);
+                let _ = writeln!(файл, "    jmp паника_диапазон_{суффикс}", суффикс = if *знаковый { "цел" } else { "нат" });
+            }
+            ВидИнструкции::ПроверитьДиапазонВещ32{мин, макс} => {
+                let (нижний, включительно, верхний) = пределы_вещественных(*мин, *макс);
+                let провал = format!("инструкция_{индекс}_паника");
+                let _ = writeln!(файл, "    movd xmm0, [rsp]");
+                let _ = writeln!(файл, "    cvtss2sd xmm0, xmm0");
+                let _ = writeln!(файл, "    mov rax, {нижний}", нижний = нижний.to_bits());
+                let _ = writeln!(файл, "    movq xmm1, rax");
+                // NaN выставляет флаг чётности.
+                let _ = writeln!(файл, "    ucomisd xmm0, xmm1");
+                let _ = writeln!(файл, "    jp {провал}");
+                let _ = writeln!(файл, "    {переход} {провал}", переход = if включительно { "jb" } else { "jbe" });
+                let _ = writeln!(файл, "    mov rax, {верхний}", верхний = верхний.to_bits());
+                let _ = writeln!(файл, "    movq xmm1, rax");
+                let _ = writeln!(файл, "    ucomisd xmm0, xmm1");
+                let _ = writeln!(файл, "    jb инструкция_{следующая}", следующая = индекс + 1);
+                let _ = writeln!(файл, "{провал}:");
+                let _ = writeln!(файл, "    mov rbx, {мин}");
+                let _ = writeln!(файл, "    mov rcx, {макс}");
+                загрузить_лок_инструкции(файл, пп, индекс);
+                let _ = writeln!(файл, "    jmp паника_диапазон_вещ");
+            }
+            ВидИнструкции::ПроверитьСимвол => {
+                let _ = writeln!(файл, "    mov rax, [rsp]");
+                let _ = writeln!(файл, "    mov rbx, rax");
+                let _ = writeln!(файл, "    sub rbx, {начало}", начало = СУРРОГАТЫ.start());
+                let _ = writeln!(файл, "    cmp rbx, {количество}", количество = СУРРОГАТЫ.end() - СУРРОГАТЫ.start() + 1);
+                let _ = writeln!(файл, "    jae инструкция_{следующая}", следующая = индекс + 1);
+                загрузить_лок_инструкции(файл, пп, индекс);
+                let _ = writeln!(файл, "    jmp паника_символ");
+            }
+            ВидИнструкции::ПроверитьДиапазонВещ64{мин, макс} => {
+                let (нижний, включительно, верхний) = пределы_вещественных(*мин, *макс);
+                let провал = format!("инструкция_{индекс}_паника");
+                let _ = writeln!(файл, "    movq xmm0, [rsp]");
+                let _ = writeln!(файл, "    mov rax, {нижний}", нижний = нижний.to_bits());
+                let _ = writeln!(файл, "    movq xmm1, rax");
+                // NaN выставляет флаг чётности.
+                let _ = writeln!(файл, "    ucomisd xmm0, xmm1");
+                let _ = writeln!(файл, "    jp {провал}");
+                let _ = writeln!(файл, "    {переход} {провал}", переход = if включительно { "jb" } else { "jbe" });
+                let _ = writeln!(файл, "    mov rax, {верхний}", верхний = верхний.to_bits());
+                let _ = writeln!(файл, "    movq xmm1, rax");
+                let _ = writeln!(файл, "    ucomisd xmm0, xmm1");
+                let _ = writeln!(файл, "    jb инструкция_{следующая}", следующая = индекс + 1);
+                let _ = writeln!(файл, "{провал}:");
+                let _ = writeln!(файл, "    mov rbx, {мин}");
+                let _ = writeln!(файл, "    mov rcx, {макс}");
+                загрузить_лок_инструкции(файл, пп, индекс);
+                let _ = writeln!(файл, "    jmp паника_диапазон_вещ");
+            }
         }
     }
+    сгенерировать_процедуры_паники(файл);
     Ok(())
 }
 
@@ -535,32 +1287,19 @@ pub fn сгенерировать_исполняемый_файл(путь_к_и
         let _ = writeln!(&mut файл, "section \".data\" writable");
     }
 
-    let _ = writeln!(&mut файл, "данные:");
-    let длинна_строки = 10;
-    let количество_строк = пп.иниц_данные.len()/10;
-    let остаток_строки = пп.иниц_данные.len()%длинна_строки;
-    for строка in 0..количество_строк {
-        let _ = write!(&mut файл, "    db");
-        for столбец in 0..длинна_строки {
-            let индекс = строка*длинна_строки + столбец;
-            let _ = write!(&mut файл, " {байт:#04X}", байт = пп.иниц_данные[индекс]);
-            if столбец + 1 < длинна_строки {
-                let _ = write!(&mut файл, ",");
-            }
-        }
-        let _ = writeln!(&mut файл);
+    for (метка, текст) in СООБЩЕНИЯ_ПАНИКИ {
+        let _ = writeln!(&mut файл, "{метка}:");
+        записать_байты(&mut файл, текст.as_bytes());
     }
-    if остаток_строки > 0 {
-        let _ = write!(&mut файл, "    db");
-        for столбец in 0..остаток_строки {
-            let индекс = количество_строк*длинна_строки + столбец;
-            let _ = write!(&mut файл, " {байт:#04X}", байт = пп.иниц_данные[индекс]);
-            if столбец + 1 < остаток_строки {
-                let _ = write!(&mut файл, ",");
-            }
+    for (индекс, инструкция) in пп.код.iter().enumerate() {
+        if проверка_времени_исполнения(&инструкция.вид) {
+            let _ = writeln!(&mut файл, "лок_{индекс}:");
+            записать_байты(&mut файл, лок_инструкции(пп, индекс).as_bytes());
         }
-        let _ = writeln!(&mut файл);
     }
+
+    let _ = writeln!(&mut файл, "данные:");
+    записать_иниц_данные(&mut файл, пп);
     let размер_второго_стека = 1_000_000;
     let _ = writeln!(&mut файл, "    rb {}", пп.размер_неиниц_данных + размер_второго_стека);
     let _ = writeln!(&mut файл, "начало_второго_стека:");
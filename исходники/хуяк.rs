@@ -19,8 +19,10 @@ mod интерпретатор;
 mod типизация;
 #[path="./фазм.rs"]
 mod фазм;
+#[path="./мост.rs"]
+mod мост;
 
-use диагностика::Лок;
+use диагностика::{Лок, Предупреждение, ВСЕ_ПРЕДУПРЕЖДЕНИЯ, ЧИСУЩ_ОШИБКА};
 use лексика::Лексер;
 use компилятор::{Программа, ТочкаВхода};
 use интерпретатор::Машина;
@@ -45,6 +47,83 @@ fn прочитать_содержимое_файла(путь_к_файлу: &P
         })
 }
 
+/// Об ошибках сообщается по ходу компиляции, и первая из них может уйти
+/// далеко вверх, поэтому в конце печатается итог.
+fn сообщить_итог_компиляции() {
+    let количество = диагностика::количество_ошибок();
+    if количество > 0 {
+        eprintln!("ИНФО: Компиляция не удалась: {количество} {ошибок}.", ошибок = ЧИСУЩ_ОШИБКА.текст(количество));
+    }
+    if диагностика::предел_ошибок_достигнут() {
+        eprintln!("ИНФО: Компиляция прервана: достигнут предел, заданный флагом «-макс-ошибок».");
+    }
+}
+
+fn задать_предел_ошибок(флаг: &str, значение: Option<String>) -> Результат<()> {
+    match значение.map(|значение| значение.parse::<usize>()) {
+        Some(Ok(предел)) => {
+            диагностика::задать_предел_ошибок(предел);
+            Ok(())
+        }
+        Some(Err(_)) => {
+            eprintln!("ОШИБКА: Значением флага «{флаг}» должно быть натуральное число либо 0.");
+            Err(())
+        }
+        None => {
+            eprintln!("ОШИБКА: Флаг «{флаг}» требует значение.");
+            Err(())
+        }
+    }
+}
+
+/// Имя «все» относится ко всем предупреждениям сразу.
+fn включить_предупреждение(флаг: &str, значение: Option<String>, включить: bool) -> Результат<()> {
+    let Some(имя) = значение else {
+        eprintln!("ОШИБКА: Флаг «{флаг}» требует значение.");
+        return Err(())
+    };
+    if имя == "все" {
+        for &предупреждение in ВСЕ_ПРЕДУПРЕЖДЕНИЯ {
+            диагностика::включить_предупреждение(предупреждение, включить);
+        }
+        return Ok(())
+    }
+    match Предупреждение::найти(&имя) {
+        Some(предупреждение) => {
+            диагностика::включить_предупреждение(предупреждение, включить);
+            Ok(())
+        }
+        None => {
+            let известные: Vec<&str> = ВСЕ_ПРЕДУПРЕЖДЕНИЯ.iter().map(|предупреждение| предупреждение.имя()).collect();
+            eprintln!("ОШИБКА: Неизвестное предупреждение «{имя}». Известные предупреждения: {известные}.", известные = известные.join(", "));
+            Err(())
+        }
+    }
+}
+
+/// Флаги диагностики и путей поиска модулей общие для всех команд,
+/// которые компилируют программу. Возвращает `true`, если флаг был
+/// распознан и обработан.
+fn разобрать_общий_флаг(арг: &str, аргы: &mut env::Args, пути_поиска: &mut Vec<PathBuf>) -> Результат<bool> {
+    match арг {
+        "-макс-ошибок" => задать_предел_ошибок(арг, аргы.next())?,
+        "-предупр" => включить_предупреждение(арг, аргы.next(), true)?,
+        "-без-предупр" => включить_предупреждение(арг, аргы.next(), false)?,
+        "-ошибки-из-предупреждений" => диагностика::задать_ошибки_из_предупреждений(true),
+        "-путь" => {
+            match аргы.next() {
+                Some(путь) => пути_поиска.push(PathBuf::from(путь)),
+                None => {
+                    eprintln!("ОШИБКА: Флаг «{арг}» требует значение.");
+                    return Err(())
+                }
+            }
+        }
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
 struct Команда {
     имя: &'static str,
     сигнатура: &'static str,
@@ -59,17 +138,20 @@ struct Команда {
 const КОМАНДЫ: &[Команда] = &[
     Команда {
         имя: "комп",
-        сигнатура: "[-пуск] [-вывод <файл-вывода>] <файл-ввода>",
+        сигнатура: "[-пуск] [-вывод <файл-вывода>] [-без-проверок] [-макс-ошибок <число>] [-предупр <имя>]... [-без-предупр <имя>]... [-ошибки-из-предупреждений] [-путь <каталог>]... <файл-ввода>",
         описание: "Скомпилировать файлы исходного кода в исполняемый файл для платформы Linux x86_64.",
         запустить: |программа, mut аргы| {
             let mut пуск = false;
+            let mut без_проверок = false;
             let mut файл_ввода = None;
             let mut файл_вывода = None;
+            let mut пути_поиска = Vec::new();
 
             loop {
                 match аргы.next() {
                     Some(арг) => match арг.as_str() {
                         "-пуск" => пуск = true,
+                        "-без-проверок" => без_проверок = true,
                         "-вывод" => {
                             match аргы.next() {
                                 Some(арг) => файл_вывода = Some(арг),
@@ -79,6 +161,7 @@ const КОМАНДЫ: &[Команда] = &[
                                 }
                             }
                         }
+                        _ if разобрать_общий_флаг(&арг, &mut аргы, &mut пути_поиска)? => {}
                         _ => {
                             if файл_ввода.is_some() {
                                 пример(программа);
@@ -102,9 +185,11 @@ const КОМАНДЫ: &[Команда] = &[
             };
 
             let mut программа = Программа::default();
+            программа.пп.без_проверок = без_проверок;
+            программа.пути_поиска = пути_поиска;
             let содержимое: Vec<char> = прочитать_содержимое_файла(&файл_ввода, None)?;
             let mut лекс = Лексер::новый(&файл_ввода, &содержимое);
-            программа.скомпилировать_лексемы(&mut лекс)?;
+            программа.скомпилировать_лексемы(&mut лекс).map_err(|()| сообщить_итог_компиляции())?;
             программа.завершить_компиляцию();
             let процедура_точки_входа = "главная";
             if let Some(процедура) = программа.имена.процедуры.get(процедура_точки_входа) {
@@ -161,16 +246,20 @@ const КОМАНДЫ: &[Команда] = &[
     },
     Команда {
         имя: "интер",
-        сигнатура: "[-отлад] <путь_к_файлу>",
+        сигнатура: "[-отлад] [-без-проверок] [-макс-ошибок <число>] [-предупр <имя>]... [-без-предупр <имя>]... [-ошибки-из-предупреждений] [-путь <каталог>]... <путь_к_файлу>",
         описание: "Интерпретировать Промежуточное Представление скомпилированного файла",
         запустить: |программа, mut аргы| {
             let mut режим_отладки = false;
+            let mut без_проверок = false;
             let mut путь_к_файлу = None;
+            let mut пути_поиска = Vec::new();
 
             loop {
                 match аргы.next() {
                     Some(арг) => match арг.as_str() {
                         "-отлад" => режим_отладки = true,
+                        "-без-проверок" => без_проверок = true,
+                        _ if разобрать_общий_флаг(&арг, &mut аргы, &mut пути_поиска)? => {}
                         _ => {
                             if путь_к_файлу.is_some() {
                                 пример(программа);
@@ -196,7 +285,9 @@ const КОМАНДЫ: &[Команда] = &[
             let содержимое: Vec<char> = прочитать_содержимое_файла(&путь_к_файлу, None)?;
             let mut лекс = Лексер::новый(&путь_к_файлу, &содержимое);
             let mut программа = Программа::default();
-            программа.скомпилировать_лексемы(&mut лекс)?;
+            программа.пп.без_проверок = без_проверок;
+            программа.пути_поиска = пути_поиска;
+            программа.скомпилировать_лексемы(&mut лекс).map_err(|()| сообщить_итог_компиляции())?;
             программа.завершить_компиляцию();
             let процедура_точки_входа = "главная";
             if let Some(процедура) = программа.имена.процедуры.get(процедура_точки_входа) {
@@ -209,6 +300,7 @@ const КОМАНДЫ: &[Команда] = &[
                 };
                 let объём_второго_стека = 1_000_000;
                 let mut машина = Машина::новая(&программа.пп, объём_второго_стека);
+                машина.загрузить_внешние_символы(&программа.пп)?;
                 машина.интерпретировать(&программа.имена, точка_входа, режим_отладки)
             } else {
                 eprintln!("ОШИБКА: процедура точки входа «{процедура_точки_входа}» не найдена! Пожалуйста определите её!");
@@ -231,7 +323,7 @@ const КОМАНДЫ: &[Команда] = &[
             let содержимое: Vec<char> = прочитать_содержимое_файла(&путь_к_файлу, None)?;
             let mut лекс = Лексер::новый(&путь_к_файлу, &содержимое);
             let mut программа = Программа::default();
-            программа.скомпилировать_лексемы(&mut лекс)?;
+            программа.скомпилировать_лексемы(&mut лекс).map_err(|()| сообщить_итог_компиляции())?;
             программа.завершить_компиляцию();
             let процедура_точки_входа = "главная";
             if let Some(процедура) = программа.имена.процедуры.get(процедура_точки_входа) {
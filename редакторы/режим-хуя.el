@@ -18,7 +18,7 @@
   (defconst ключевые-слова-хуя
     '("пер" "про" "конст" "пока" "нч" "кц" "для"
       "если" "то" "иначе" "вернуть" "замкнуть" "пропустить"
-      "структ" "союз" "как" "вкл" "внешняя" "библ" "или"
+      "структ" "тип" "союз" "как" "вкл" "экспорт" "открытая" "закрытая" "внешняя" "библ" "или"
       "и" "истина" "ложь" "лбс" "пбс" "ост" "вилка" "когда" "любое"
       "либо")))
 
@@ -55,6 +55,7 @@
 ;; Она почему-то не работает из коробки
 (defconst подсветка-хуя
   `((строковый-литерал-хуя . font-lock-string-face)
+    ("'\\(\\\\.\\|[^'\\\\\n]\\)'" . font-lock-string-face)
     (,(regexp-opt ключевые-слова-хуя 'symbols) . font-lock-keyword-face)))
 
 ;;;###autoload
@@ -18,6 +18,30 @@ test_fasm() {
     ./сборка/хуяк комп -вывод ./сборка/примеры/09-правило110 ./примеры/09-правило110.хуя
     ./сборка/хуяк комп -вывод ./сборка/примеры/10-игра-жизнь ./примеры/10-игра-жизнь.хуя
     ./сборка/хуяк комп -вывод ./сборка/тесты/тест            ./тесты/тест.хуя
+    ./сборка/хуяк комп -вывод ./сборка/тесты/вариативные     ./тесты/вариативные.хуя
+    ./сборка/хуяк комп -вывод ./сборка/тесты/границы         ./тесты/границы.хуя
+    ./сборка/хуяк комп -вывод ./сборка/тесты/границы-без-проверок -без-проверок ./тесты/границы.хуя
+    ./сборка/хуяк комп -вывод ./сборка/тесты/переполнение    ./тесты/переполнение.хуя
+    ./сборка/хуяк комп -вывод ./сборка/тесты/переполнение-без-проверок -без-проверок ./тесты/переполнение.хуя
+    ./сборка/хуяк комп -вывод ./сборка/тесты/константы       ./тесты/константы.хуя
+    ./сборка/хуяк комп -вывод ./сборка/тесты/индекс-строк    ./тесты/индекс-строк.хуя
+    ./сборка/хуяк комп -вывод ./сборка/тесты/символы         ./тесты/символы.хуя
+    ./сборка/хуяк комп -вывод ./сборка/тесты/строки          ./тесты/строки.хуя
+    ./сборка/хуяк комп -вывод ./сборка/тесты/составное-присваивание ./тесты/составное-присваивание.хуя
+    ./сборка/хуяк комп -вывод ./сборка/тесты/если-выражение         ./тесты/если-выражение.хуя
+    ./сборка/хуяк комп -вывод ./сборка/тесты/сокращённые-операции   ./тесты/сокращённые-операции.хуя
+    ./сборка/хуяк комп -вывод ./сборка/тесты/для                    ./тесты/для.хуя
+    ./сборка/хуяк комп -вывод ./сборка/тесты/кортежи                ./тесты/кортежи.хуя
+    ./сборка/хуяк комп -вывод ./сборка/тесты/литералы               ./тесты/литералы.хуя
+    ./сборка/хуяк комп -вывод ./сборка/тесты/методы                 ./тесты/методы.хуя
+    ./сборка/хуяк комп -путь ./тесты/модули -вывод ./сборка/тесты/модули ./тесты/модули.хуя
+    ./сборка/хуяк комп -путь ./тесты/модули -вывод ./сборка/тесты/видимость ./тесты/видимость.хуя
+    ./сборка/хуяк комп -путь ./тесты/модули -вывод ./сборка/тесты/типы      ./тесты/типы.хуя
+    ./сборка/хуяк комп -вывод ./сборка/тесты/целые     ./тесты/целые.хуя
+    ./сборка/хуяк комп -вывод ./сборка/тесты/вещ64     ./тесты/вещ64.хуя
+    ./сборка/хуяк комп -вывод ./сборка/тесты/конвертации ./тесты/конвертации.хуя
+    ./сборка/хуяк комп -вывод ./сборка/тесты/математика  ./тесты/математика.хуя
+    ./сборка/хуяк комп -вывод ./сборка/тесты/возвраты    ./тесты/возвраты.хуя
 
     ./сборка/примеры/01-привет               > ./сборка/примеры/01-привет.хуя.вывод.фазм     && diff -u ./примеры/01-привет.хуя.вывод     ./сборка/примеры/01-привет.хуя.вывод.фазм
     ./сборка/примеры/02-цикл                 > ./сборка/примеры/02-цикл.хуя.вывод.фазм       && diff -u ./примеры/02-цикл.хуя.вывод       ./сборка/примеры/02-цикл.хуя.вывод.фазм
@@ -30,9 +54,46 @@ test_fasm() {
     ./сборка/примеры/09-правило110           > ./сборка/примеры/09-правило110.хуя.вывод.фазм && diff -u ./примеры/09-правило110.хуя.вывод ./сборка/примеры/09-правило110.хуя.вывод.фазм
     ./сборка/примеры/10-игра-жизнь           > ./сборка/примеры/10-игра-жизнь.хуя.вывод.фазм && diff -u ./примеры/10-игра-жизнь.хуя.вывод ./сборка/примеры/10-игра-жизнь.хуя.вывод.фазм
     ./сборка/тесты/тест                      > ./сборка/тесты/тест.хуя.вывод.фазм            && diff -u ./тесты/тест.хуя.вывод            ./сборка/тесты/тест.хуя.вывод.фазм
+    ./сборка/тесты/вариативные               > ./сборка/тесты/вариативные.хуя.вывод.фазм     && diff -u ./тесты/вариативные.хуя.вывод     ./сборка/тесты/вариативные.хуя.вывод.фазм
+    echo 1 | ./сборка/тесты/границы 2>&1 | grep -qF 'Индекс 4 выходит за границы [0..4)'
+    echo 2 | ./сборка/тесты/границы 2>&1 | grep -qF 'Индекс -1 выходит за границы [0..5)'
+    echo 3 | ./сборка/тесты/границы 2>&1 | grep -qF 'Срез с началом 3 и размером 10 выходит за границы [0..5)'
+    echo 1 | ./сборка/тесты/границы-без-проверок > ./сборка/тесты/границы.хуя.вывод.фазм && diff -u ./тесты/границы.хуя.вывод ./сборка/тесты/границы.хуя.вывод.фазм
+    echo 1 | ./сборка/тесты/переполнение 2>&1 | grep -qF 'Переполнение при сложении 200 и 100'
+    echo 2 | ./сборка/тесты/переполнение 2>&1 | grep -qF 'Переполнение при сложении 9223372036854775807 и 1'
+    echo 3 | ./сборка/тесты/переполнение 2>&1 | grep -qF 'Переполнение при вычитании 3 из 2'
+    echo 4 | ./сборка/тесты/переполнение 2>&1 | grep -qF 'Переполнение при умножении 18446744073709551615 и 2'
+    echo 5 | ./сборка/тесты/переполнение 2>&1 | grep -qF 'Деление 7 на ноль'
+    echo 6 | ./сборка/тесты/переполнение 2>&1 | grep -qF 'Значение 300 выходит за пределы [0..255]'
+    echo 7 | ./сборка/тесты/переполнение 2>&1 | grep -qF 'Значение -1 выходит за пределы [0..18446744073709551615]'
+    echo 8 | ./сборка/тесты/переполнение 2>&1 | grep -qF 'Вещественное значение выходит за пределы [0..255]'
+    echo 9 | ./сборка/тесты/переполнение 2>&1 | grep -qF 'Переполнение при сложении 100 и 100'
+    echo : | ./сборка/тесты/переполнение 2>&1 | grep -qF 'Значение 55296 является суррогатом UTF-16 и не может быть символом'
+    echo ';' | ./сборка/тесты/переполнение 2>&1 | grep -qF 'Значение 1114112 выходит за пределы [0..1114111]'
+    echo 0 | ./сборка/тесты/переполнение-без-проверок > ./сборка/тесты/переполнение.хуя.вывод.фазм && diff -u ./тесты/переполнение.хуя.вывод ./сборка/тесты/переполнение.хуя.вывод.фазм
+    ./сборка/тесты/константы                 > ./сборка/тесты/константы.хуя.вывод.фазм       && diff -u ./тесты/константы.хуя.вывод       ./сборка/тесты/константы.хуя.вывод.фазм
+    ./сборка/тесты/индекс-строк              > ./сборка/тесты/индекс-строк.хуя.вывод.фазм    && diff -u ./тесты/индекс-строк.хуя.вывод    ./сборка/тесты/индекс-строк.хуя.вывод.фазм
+    ./сборка/тесты/символы                   > ./сборка/тесты/символы.хуя.вывод.фазм         && diff -u ./тесты/символы.хуя.вывод         ./сборка/тесты/символы.хуя.вывод.фазм
+    ./сборка/тесты/строки                    > ./сборка/тесты/строки.хуя.вывод.фазм          && diff -u ./тесты/строки.хуя.вывод          ./сборка/тесты/строки.хуя.вывод.фазм
+    ./сборка/тесты/составное-присваивание    > ./сборка/тесты/составное-присваивание.хуя.вывод.фазм && diff -u ./тесты/составное-присваивание.хуя.вывод ./сборка/тесты/составное-присваивание.хуя.вывод.фазм
+    ./сборка/тесты/если-выражение            > ./сборка/тесты/если-выражение.хуя.вывод.фазм         && diff -u ./тесты/если-выражение.хуя.вывод         ./сборка/тесты/если-выражение.хуя.вывод.фазм
+    ./сборка/тесты/сокращённые-операции      > ./сборка/тесты/сокращённые-операции.хуя.вывод.фазм   && diff -u ./тесты/сокращённые-операции.хуя.вывод   ./сборка/тесты/сокращённые-операции.хуя.вывод.фазм
+    ./сборка/тесты/для                       > ./сборка/тесты/для.хуя.вывод.фазм                    && diff -u ./тесты/для.хуя.вывод                    ./сборка/тесты/для.хуя.вывод.фазм
+    ./сборка/тесты/кортежи                   > ./сборка/тесты/кортежи.хуя.вывод.фазм                && diff -u ./тесты/кортежи.хуя.вывод                ./сборка/тесты/кортежи.хуя.вывод.фазм
+    ./сборка/тесты/литералы                  > ./сборка/тесты/литералы.хуя.вывод.фазм               && diff -u ./тесты/литералы.хуя.вывод               ./сборка/тесты/литералы.хуя.вывод.фазм
+    ./сборка/тесты/методы                    > ./сборка/тесты/методы.хуя.вывод.фазм                 && diff -u ./тесты/методы.хуя.вывод                 ./сборка/тесты/методы.хуя.вывод.фазм
+    ./сборка/тесты/модули                    > ./сборка/тесты/модули.хуя.вывод.фазм                 && diff -u ./тесты/модули.хуя.вывод                 ./сборка/тесты/модули.хуя.вывод.фазм
+    ./сборка/тесты/видимость                 > ./сборка/тесты/видимость.хуя.вывод.фазм              && diff -u ./тесты/видимость.хуя.вывод              ./сборка/тесты/видимость.хуя.вывод.фазм
+    ./сборка/тесты/типы                      > ./сборка/тесты/типы.хуя.вывод.фазм                   && diff -u ./тесты/типы.хуя.вывод                   ./сборка/тесты/типы.хуя.вывод.фазм
+    ./сборка/тесты/целые                     > ./сборка/тесты/целые.хуя.вывод.фазм                  && diff -u ./тесты/целые.хуя.вывод                  ./сборка/тесты/целые.хуя.вывод.фазм
+    ./сборка/тесты/вещ64                     > ./сборка/тесты/вещ64.хуя.вывод.фазм                  && diff -u ./тесты/вещ64.хуя.вывод                  ./сборка/тесты/вещ64.хуя.вывод.фазм
+    ./сборка/тесты/конвертации               > ./сборка/тесты/конвертации.хуя.вывод.фазм            && diff -u ./тесты/конвертации.хуя.вывод            ./сборка/тесты/конвертации.хуя.вывод.фазм
+    ./сборка/тесты/математика                > ./сборка/тесты/математика.хуя.вывод.фазм             && diff -u ./тесты/математика.хуя.вывод             ./сборка/тесты/математика.хуя.вывод.фазм
+    ./сборка/тесты/возвраты                  > ./сборка/тесты/возвраты.хуя.вывод.фазм               && diff -u ./тесты/возвраты.хуя.вывод               ./сборка/тесты/возвраты.хуя.вывод.фазм
 }
 
 test_inter() {
+    cc -shared -fPIC -o ./сборка/тесты/libффи.so ./тесты/ффи.c
     ./сборка/хуяк интер ./примеры/01-привет.хуя               > ./сборка/примеры/01-привет.хуя.вывод.интер     && diff -u ./примеры/01-привет.хуя.вывод     ./сборка/примеры/01-привет.хуя.вывод.интер
     ./сборка/хуяк интер ./примеры/02-цикл.хуя                 > ./сборка/примеры/02-цикл.хуя.вывод.интер       && diff -u ./примеры/02-цикл.хуя.вывод       ./сборка/примеры/02-цикл.хуя.вывод.интер
     echo 'Алексей' | ./сборка/хуяк интер ./примеры/03-имя.хуя > ./сборка/примеры/03-имя.хуя.вывод.интер        && diff -u ./примеры/03-имя.хуя.вывод        ./сборка/примеры/03-имя.хуя.вывод.интер
@@ -44,6 +105,75 @@ test_inter() {
     ./сборка/хуяк интер ./примеры/09-правило110.хуя           > ./сборка/примеры/09-правило110.хуя.вывод.интер && diff -u ./примеры/09-правило110.хуя.вывод ./сборка/примеры/09-правило110.хуя.вывод.интер
     ./сборка/хуяк интер ./примеры/10-игра-жизнь.хуя           > ./сборка/примеры/10-игра-жизнь.хуя.вывод.интер && diff -u ./примеры/10-игра-жизнь.хуя.вывод ./сборка/примеры/10-игра-жизнь.хуя.вывод.интер
     ./сборка/хуяк интер ./тесты/тест.хуя                      > ./сборка/тесты/тест.хуя.вывод.интер            && diff -u ./тесты/тест.хуя.вывод            ./сборка/тесты/тест.хуя.вывод.интер
+    ./сборка/хуяк интер ./тесты/вариативные.хуя               > ./сборка/тесты/вариативные.хуя.вывод.интер     && diff -u ./тесты/вариативные.хуя.вывод     ./сборка/тесты/вариативные.хуя.вывод.интер
+    LD_LIBRARY_PATH=./сборка/тесты ./сборка/хуяк интер ./тесты/ффи.хуя > ./сборка/тесты/ффи.хуя.вывод.интер   && diff -u ./тесты/ффи.хуя.вывод             ./сборка/тесты/ффи.хуя.вывод.интер
+    echo 1 | ./сборка/хуяк интер ./тесты/границы.хуя 2>&1 | grep -qF 'Индекс 4 выходит за границы [0..4)'
+    echo 2 | ./сборка/хуяк интер ./тесты/границы.хуя 2>&1 | grep -qF 'Индекс -1 выходит за границы [0..5)'
+    echo 3 | ./сборка/хуяк интер ./тесты/границы.хуя 2>&1 | grep -qF 'Срез с началом 3 и размером 10 выходит за границы [0..5)'
+    echo 1 | ./сборка/хуяк интер -без-проверок ./тесты/границы.хуя > ./сборка/тесты/границы.хуя.вывод.интер && diff -u ./тесты/границы.хуя.вывод ./сборка/тесты/границы.хуя.вывод.интер
+    echo 1 | ./сборка/хуяк интер ./тесты/переполнение.хуя 2>&1 | grep -qF 'Переполнение при сложении 200 и 100'
+    echo 2 | ./сборка/хуяк интер ./тесты/переполнение.хуя 2>&1 | grep -qF 'Переполнение при сложении 9223372036854775807 и 1'
+    echo 3 | ./сборка/хуяк интер ./тесты/переполнение.хуя 2>&1 | grep -qF 'Переполнение при вычитании 3 из 2'
+    echo 4 | ./сборка/хуяк интер ./тесты/переполнение.хуя 2>&1 | grep -qF 'Переполнение при умножении 18446744073709551615 и 2'
+    echo 5 | ./сборка/хуяк интер ./тесты/переполнение.хуя 2>&1 | grep -qF 'Деление 7 на ноль'
+    echo 6 | ./сборка/хуяк интер ./тесты/переполнение.хуя 2>&1 | grep -qF 'Значение 300 выходит за пределы [0..255]'
+    echo 7 | ./сборка/хуяк интер ./тесты/переполнение.хуя 2>&1 | grep -qF 'Значение -1 выходит за пределы [0..18446744073709551615]'
+    echo 8 | ./сборка/хуяк интер ./тесты/переполнение.хуя 2>&1 | grep -qF 'Вещественное значение выходит за пределы [0..255]'
+    echo 9 | ./сборка/хуяк интер ./тесты/переполнение.хуя 2>&1 | grep -qF 'Переполнение при сложении 100 и 100'
+    echo : | ./сборка/хуяк интер ./тесты/переполнение.хуя 2>&1 | grep -qF 'Значение 55296 является суррогатом UTF-16 и не может быть символом'
+    echo ';' | ./сборка/хуяк интер ./тесты/переполнение.хуя 2>&1 | grep -qF 'Значение 1114112 выходит за пределы [0..1114111]'
+    echo '<' | ./сборка/хуяк интер ./тесты/переполнение.хуя 2>&1 | grep -qF 'переполнение второго стека'
+    echo '=' | ./сборка/хуяк интер ./тесты/переполнение.хуя 2>&1 | grep -qF 'Попытка получить доступ к некорректнному диапазону памяти [999824..999832)'
+    echo 0 | ./сборка/хуяк интер ./тесты/переполнение.хуя > ./сборка/тесты/переполнение.хуя.вывод.интер && diff -u ./тесты/переполнение.хуя.вывод ./сборка/тесты/переполнение.хуя.вывод.интер
+    ./сборка/хуяк интер ./тесты/константы.хуя             > ./сборка/тесты/константы.хуя.вывод.интер       && diff -u ./тесты/константы.хуя.вывод       ./сборка/тесты/константы.хуя.вывод.интер
+    ./сборка/хуяк интер ./тесты/индекс-строк.хуя          > ./сборка/тесты/индекс-строк.хуя.вывод.интер    && diff -u ./тесты/индекс-строк.хуя.вывод    ./сборка/тесты/индекс-строк.хуя.вывод.интер
+    ./сборка/хуяк интер ./тесты/символы.хуя               > ./сборка/тесты/символы.хуя.вывод.интер         && diff -u ./тесты/символы.хуя.вывод         ./сборка/тесты/символы.хуя.вывод.интер
+    ./сборка/хуяк интер ./тесты/строки.хуя                > ./сборка/тесты/строки.хуя.вывод.интер          && diff -u ./тесты/строки.хуя.вывод          ./сборка/тесты/строки.хуя.вывод.интер
+    ./сборка/хуяк интер ./тесты/составное-присваивание.хуя > ./сборка/тесты/составное-присваивание.хуя.вывод.интер && diff -u ./тесты/составное-присваивание.хуя.вывод ./сборка/тесты/составное-присваивание.хуя.вывод.интер
+    ./сборка/хуяк интер ./тесты/если-выражение.хуя         > ./сборка/тесты/если-выражение.хуя.вывод.интер         && diff -u ./тесты/если-выражение.хуя.вывод         ./сборка/тесты/если-выражение.хуя.вывод.интер
+    ./сборка/хуяк интер ./тесты/сокращённые-операции.хуя   > ./сборка/тесты/сокращённые-операции.хуя.вывод.интер   && diff -u ./тесты/сокращённые-операции.хуя.вывод   ./сборка/тесты/сокращённые-операции.хуя.вывод.интер
+    ./сборка/хуяк интер ./тесты/для.хуя                    > ./сборка/тесты/для.хуя.вывод.интер                    && diff -u ./тесты/для.хуя.вывод                    ./сборка/тесты/для.хуя.вывод.интер
+    ./сборка/хуяк интер ./тесты/кортежи.хуя                > ./сборка/тесты/кортежи.хуя.вывод.интер                && diff -u ./тесты/кортежи.хуя.вывод                ./сборка/тесты/кортежи.хуя.вывод.интер
+    ./сборка/хуяк интер ./тесты/литералы.хуя               > ./сборка/тесты/литералы.хуя.вывод.интер               && diff -u ./тесты/литералы.хуя.вывод               ./сборка/тесты/литералы.хуя.вывод.интер
+    ./сборка/хуяк интер ./тесты/методы.хуя                 > ./сборка/тесты/методы.хуя.вывод.интер                 && diff -u ./тесты/методы.хуя.вывод                 ./сборка/тесты/методы.хуя.вывод.интер
+    ./сборка/хуяк интер -путь ./тесты/модули ./тесты/модули.хуя > ./сборка/тесты/модули.хуя.вывод.интер                 && diff -u ./тесты/модули.хуя.вывод                 ./сборка/тесты/модули.хуя.вывод.интер
+    ./сборка/хуяк интер -путь ./тесты/модули ./тесты/видимость.хуя > ./сборка/тесты/видимость.хуя.вывод.интер              && diff -u ./тесты/видимость.хуя.вывод              ./сборка/тесты/видимость.хуя.вывод.интер
+    ./сборка/хуяк интер -путь ./тесты/модули ./тесты/типы.хуя      > ./сборка/тесты/типы.хуя.вывод.интер                   && diff -u ./тесты/типы.хуя.вывод                   ./сборка/тесты/типы.хуя.вывод.интер
+    ./сборка/хуяк интер ./тесты/целые.хуя     > ./сборка/тесты/целые.хуя.вывод.интер                  && diff -u ./тесты/целые.хуя.вывод                  ./сборка/тесты/целые.хуя.вывод.интер
+    ./сборка/хуяк интер ./тесты/вещ64.хуя     > ./сборка/тесты/вещ64.хуя.вывод.интер                  && diff -u ./тесты/вещ64.хуя.вывод                  ./сборка/тесты/вещ64.хуя.вывод.интер
+    ./сборка/хуяк интер ./тесты/конвертации.хуя > ./сборка/тесты/конвертации.хуя.вывод.интер            && diff -u ./тесты/конвертации.хуя.вывод            ./сборка/тесты/конвертации.хуя.вывод.интер
+    ./сборка/хуяк интер ./тесты/математика.хуя  > ./сборка/тесты/математика.хуя.вывод.интер             && diff -u ./тесты/математика.хуя.вывод             ./сборка/тесты/математика.хуя.вывод.интер
+    ./сборка/хуяк интер ./тесты/возвраты.хуя    > ./сборка/тесты/возвраты.хуя.вывод.интер               && diff -u ./тесты/возвраты.хуя.вывод               ./сборка/тесты/возвраты.хуя.вывод.интер
+    ./сборка/хуяк интер ./тесты/ошибки/без-возврата.хуя 2>&1 | grep -qF 'без-возврата.хуя:9:1: ОШИБКА: Исполнение процедуры «знак» может дойти до конца'
+    ./сборка/хуяк интер ./тесты/ошибки/вилка-без-любого.хуя 2>&1 | grep -qF 'вилка-без-любого.хуя:8:1: ОШИБКА: Исполнение процедуры «название» может дойти до конца'
+    ./сборка/хуяк интер ./тесты/ошибки/много-ошибок.хуя 2>&1 | grep -qF 'много-ошибок.хуя:28:16: ОШИБКА: не существует ни констант, ни переменных с имением «нет_такой»'
+    ./сборка/хуяк интер ./тесты/ошибки/много-ошибок.хуя 2>&1 | grep -qF 'много-ошибок.хуя:32:17: ОШИБКА: Неизвестный тип «Нет»'
+    ./сборка/хуяк интер ./тесты/ошибки/много-ошибок.хуя 2>&1 | grep -qF 'Компиляция не удалась: 8 ошибок.'
+    ./сборка/хуяк интер -макс-ошибок 2 ./тесты/ошибки/много-ошибок.хуя 2>&1 | grep -qF 'Компиляция не удалась: 2 ошибки.'
+    ./сборка/хуяк интер ./тесты/ошибки/адрес-в-константе.хуя 2>&1 | grep -qE 'адрес-в-константе.хуя:6:17: АдресПамяти: ОШИБКА ВРЕМЕНИ ИСПОЛНЕНИЯ: [0-9]+: Адреса памяти недоступны во время компиляции'
+    ./сборка/хуяк интер ./тесты/ошибки/адрес-в-константе.хуя 2>&1 | grep -qE 'адрес-в-константе.хуя:9:13: АдресПамяти: ОШИБКА ВРЕМЕНИ ИСПОЛНЕНИЯ: [0-9]+: Адреса памяти недоступны во время компиляции'
+    ./сборка/хуяк интер ./тесты/ошибки/адрес-в-константе.хуя 2>&1 | grep -qF 'Компиляция не удалась: 2 ошибки.'
+    ./сборка/хуяк интер ./тесты/ошибки/срез-в-константе.хуя 2>&1 | grep -qF 'срез-в-константе.хуя:12:18: ИНФО: Ошибка возникла при вычислении значения этой константы'
+    ./сборка/хуяк интер ./тесты/ошибки/срез-в-константе.хуя 2>&1 | grep -qF 'Компиляция не удалась: 1 ошибка.'
+    ./сборка/хуяк интер ./тесты/ошибки/шаг-не-константа.хуя 2>&1 | grep -qF 'шаг-не-константа.хуя:7:24: ОШИБКА: Шаг цикла «для» должен быть известен во время компиляции, но зависит от переменной «ш»'
+    ./сборка/хуяк интер ./тесты/ошибки/итог-компиляции.хуя 2>&1 | grep -qF 'Компиляция не удалась: 2 ошибки.'
+    ./сборка/хуяк интер ./тесты/ошибки/предупреждения.хуя 2>&1 | grep -qF 'предупреждения.хуя:8:5: ПРЕДУПРЕЖДЕНИЕ: Недостижимый код [недостижимый-код]'
+    ./сборка/хуяк интер ./тесты/ошибки/предупреждения.хуя 2>&1 | grep -qF 'предупреждения.хуя:6:21: ПРЕДУПРЕЖДЕНИЕ: Параметр «лишний» процедуры «удвоить» нигде не используется'
+    ./сборка/хуяк интер ./тесты/ошибки/предупреждения.хуя 2>&1 | grep -qF 'предупреждения.хуя:19:13: ПРЕДУПРЕЖДЕНИЕ: Имя «х» затеняет другое определение'
+    ./сборка/хуяк интер ./тесты/ошибки/предупреждения.хуя 2>&1 | grep -qF 'предупреждения.хуя:15:9: ПРЕДУПРЕЖДЕНИЕ: Переменная «ненужная» нигде не используется'
+    ./сборка/хуяк интер ./тесты/ошибки/предупреждения.хуя 2>&1 | grep -qF 'предупреждения.хуя:11:5: ПРЕДУПРЕЖДЕНИЕ: Процедура «забытая» нигде не вызывается'
+    ./сборка/хуяк интер ./тесты/ошибки/предупреждения.хуя 2>/dev/null | grep -qxF '7'
+    test -z "$(./сборка/хуяк интер -без-предупр все ./тесты/ошибки/предупреждения.хуя 2>&1 >/dev/null)"
+    ./сборка/хуяк интер ./тесты/ошибки/предупреждения.хуя 2>&1 | grep -qF 'предупреждения.хуя:26:9: ПРЕДУПРЕЖДЕНИЕ: Переменная «только_запись» нигде не используется'
+    ./сборка/хуяк интер ./тесты/ошибки/предупреждения.хуя 2>&1 | grep -qF 'предупреждения.хуя:24:14: ПРЕДУПРЕЖДЕНИЕ: Параметр «п» процедуры «записать» нигде не используется'
+    ./сборка/хуяк интер ./тесты/ошибки/предупреждения.хуя 2>&1 | grep -qF 'предупреждения.хуя:30:5: ПРЕДУПРЕЖДЕНИЕ: Процедура «обратный_отсчёт» нигде не вызывается'
+    ./сборка/хуяк интер -ошибки-из-предупреждений ./тесты/ошибки/предупреждения.хуя 2>&1 | grep -qF 'Компиляция не удалась: 9 ошибок.'
+    ./сборка/хуяк интер -путь ./тесты/ошибки/модули ./тесты/ошибки/циклическое-включение.хуя 2>&1 | grep -qF 'цикл_б.хуя:3:5: ОШИБКА: Циклическое включение модулей: цикл_а → цикл_б → цикл_а'
+    ./сборка/хуяк интер -путь ./тесты/модули ./тесты/ошибки/неоткрытое-имя.хуя 2>&1 | grep -qF 'неоткрытое-имя.хуя:8:27: ОШИБКА: Модуль «счётчик» не открывает имя «утроенный»'
+    ./сборка/хуяк интер -путь ./тесты/модули ./тесты/ошибки/закрытое-имя.хуя 2>&1 | grep -qF 'закрытое-имя.хуя:8:7: ОШИБКА: Процедура «прибавить» структуры «счётчик.Счётчик» закрыта'
+    ./сборка/хуяк интер -путь ./тесты/модули ./тесты/ошибки/закрытое-имя.хуя 2>&1 | grep -qF 'закрытое-имя.хуя:9:22: ОШИБКА: Модуль «счётчик» не открывает имя «ШАГ_ПО_УМОЛЧАНИЮ»'
+    HUYA_PATH=./тесты/модули ./сборка/хуяк интер ./тесты/ошибки/неоткрытое-имя.хуя 2>&1 | grep -qF 'неоткрытое-имя.хуя:8:27: ОШИБКА: Модуль «счётчик» не открывает имя «утроенный»'
+    ./сборка/хуяк интер ./тесты/ошибки/неоткрытое-имя.хуя 2>&1 | grep -qF 'неоткрытое-имя.хуя:3:5: ОШИБКА: Не удалось найти модуль «счётчик».'
 }
 
 test_fasm
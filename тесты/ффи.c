@@ -0,0 +1,74 @@
+// Тестовая библиотека для проверки вызова внешних процедур из интерпретатора.
+// Собирается в ./сборка/тесты/libффи.so скриптом тест.sh.
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+
+int64_t ffi_weighted_sum6(int64_t a, int64_t b, int64_t c, int64_t d, int64_t e, int64_t f)
+{
+    return a + 10*b + 100*c + 1000*d + 10000*e + 100000*f;
+}
+
+float ffi_mix(int64_t a, float x, int64_t b, float y)
+{
+    return (float)a*x + (float)b*y;
+}
+
+double ffi_mix64(int64_t a, double x, float y, double z)
+{
+    return (double)a*x + (double)y*z;
+}
+
+bool ffi_is_even(uint64_t n)
+{
+    return n%2 == 0;
+}
+
+int16_t ffi_sub16(int16_t a, int16_t b)
+{
+    return a - b;
+}
+
+uint32_t ffi_mul32(uint32_t a, uint32_t b)
+{
+    return a*b;
+}
+
+int8_t ffi_neg8(int8_t a)
+{
+    return -a;
+}
+
+void ffi_fill(uint8_t *buffer, uint64_t size, uint8_t value)
+{
+    for (uint64_t i = 0; i < size; ++i) buffer[i] = value + i;
+}
+
+uint8_t *ffi_find(uint8_t *buffer, uint64_t size, uint8_t value)
+{
+    for (uint64_t i = 0; i < size; ++i) {
+        if (buffer[i] == value) return &buffer[i];
+    }
+    return 0;
+}
+
+int64_t ffi_sum_ints(uint64_t count, ...)
+{
+    va_list args;
+    va_start(args, count);
+    int64_t sum = 0;
+    for (uint64_t i = 0; i < count; ++i) sum += va_arg(args, int64_t);
+    va_end(args);
+    return sum;
+}
+
+float ffi_sum_floats(uint64_t count, ...)
+{
+    va_list args;
+    va_start(args, count);
+    double sum = 0;
+    for (uint64_t i = 0; i < count; ++i) sum += va_arg(args, double);
+    va_end(args);
+    return (float)sum;
+}